@@ -10,7 +10,7 @@ use smart_config::{
     metadata::ConfigMetadata,
     value::{FileFormat, ValueOrigin, WithOrigin},
     visit::{ConfigVisitor, VisitConfig},
-    ConfigRepository, ParseError, ParseErrors,
+    ConfigRepository, ParseError, ParseErrors, Severity,
 };
 
 use crate::{
@@ -35,6 +35,10 @@ const ERROR_LABEL: Style = Style::new()
     .bold()
     .bg_color(Some(Color::Ansi(AnsiColor::Red)))
     .fg_color(None);
+const WARNING_LABEL: Style = Style::new()
+    .bold()
+    .bg_color(Some(Color::Ansi(AnsiColor::Yellow)))
+    .fg_color(None);
 
 #[derive(Debug)]
 struct ParamValuesVisitor {
@@ -275,10 +279,23 @@ fn write_origin(writer: &mut impl io::Write, origin: &ValueOrigin) -> io::Result
                 Ok(())
             }
         }
+        ValueOrigin::FileLocation {
+            source,
+            path,
+            line,
+            column,
+        } => {
+            write_origin(writer, source)?;
+            write!(writer, " {ARROW}->{ARROW:#} .{path}:{line}:{column}")
+        }
         ValueOrigin::Synthetic { source, transform } => {
             write_origin(writer, source)?;
             write!(writer, " {ARROW}->{ARROW:#} {transform}")
         }
+        ValueOrigin::Fallback { source, param, .. } => {
+            write_origin(writer, source)?;
+            write!(writer, " {ARROW}->{ARROW:#} fallback for `{param}`")
+        }
         _ => write!(writer, "{origin}"),
     }
 }
@@ -351,16 +368,33 @@ fn write_param(
 }
 
 fn write_de_errors(writer: &mut impl io::Write, errors: &[ParseError]) -> io::Result<()> {
-    if errors.len() == 1 {
-        write!(writer, "  {ERROR_LABEL}Error:{ERROR_LABEL:#} ")?;
-        write_de_error(writer, &errors[0])
-    } else {
-        writeln!(writer, "  {ERROR_LABEL}Errors:{ERROR_LABEL:#}")?;
-        for err in errors {
-            write!(writer, "  - ")?;
-            write_de_error(writer, err)?;
+    let (warnings, errors): (Vec<_>, Vec<_>) = errors
+        .iter()
+        .partition(|err| err.severity() == Severity::Warning);
+    write_de_error_group(writer, &errors, ERROR_LABEL, "Error")?;
+    write_de_error_group(writer, &warnings, WARNING_LABEL, "Warning")
+}
+
+fn write_de_error_group(
+    writer: &mut impl io::Write,
+    errors: &[&ParseError],
+    label_style: Style,
+    label: &str,
+) -> io::Result<()> {
+    match errors {
+        [] => Ok(()),
+        [err] => {
+            write!(writer, "  {label_style}{label}:{label_style:#} ")?;
+            write_de_error(writer, err)
+        }
+        _ => {
+            writeln!(writer, "  {label_style}{label}s:{label_style:#}")?;
+            for err in errors {
+                write!(writer, "  - ")?;
+                write_de_error(writer, err)?;
+            }
+            Ok(())
         }
-        Ok(())
     }
 }
 
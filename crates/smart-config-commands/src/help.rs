@@ -237,6 +237,10 @@ impl ParamRef<'_> {
             writeln!(writer)?;
         }
 
+        if let Some(since) = self.param.since() {
+            writeln!(writer, "{INDENT}{FIELD}Since{FIELD:#}: {since}")?;
+        }
+
         if let Some(fallback) = self.param.fallback {
             write!(writer, "{INDENT}{FIELD}Fallbacks{FIELD:#}: ")?;
             let fallback = fallback.to_string();
@@ -44,7 +44,7 @@ pub(crate) struct TestConfig {
     #[config(example = HashSet::from_iter(["./local".into()]))]
     pub dir_paths: HashSet<PathBuf>,
     /// Timeout for some operation.
-    #[config(default_t = 1 * TimeUnit::Minutes, with = TimeUnit::Seconds)]
+    #[config(default_t = 1 * TimeUnit::Minutes, with = TimeUnit::Seconds, since = "0.2.0")]
     pub timeout_sec: Duration,
     /// In-memory cache size.
     #[config(default_t = 16 * SizeUnit::MiB, deprecated = ".experimental.cache_size")]
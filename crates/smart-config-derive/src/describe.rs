@@ -3,11 +3,11 @@
 use proc_macro::TokenStream;
 use proc_macro2::Ident;
 use quote::{quote, quote_spanned, ToTokens};
-use syn::{spanned::Spanned, DeriveInput, LitStr, Type};
+use syn::{spanned::Spanned, DeriveInput, ExprRange, LitStr, Type};
 
 use crate::utils::{
-    wrap_in_option, Alias, ConfigContainer, ConfigContainerFields, ConfigEnumVariant, ConfigField,
-    DefaultValue, RenameRule, Validation,
+    wrap_in_option, Alias, AllowedOrigin, ConfigContainer, ConfigContainerFields,
+    ConfigEnumVariant, ConfigField, DefaultValue, RenameRule, Validation,
 };
 
 impl DefaultValue {
@@ -67,7 +67,9 @@ impl ConfigField {
             }
         } else {
             let ty = &self.ty;
-            quote_spanned!(ty.span()=> <#ty as #cr::de::WellKnown>::DE)
+            quote_spanned! {ty.span()=>
+                #cr::de::_private::Customizable::<#ty, _>::new(<#ty as #cr::de::WellKnown>::DE)
+            }
         };
 
         if self.attrs.is_secret {
@@ -85,13 +87,27 @@ impl ConfigField {
         if let Some(default_fn) = self.default_fn() {
             deserializer = quote!(#cr::de::WithDefault::new(#deserializer, #default_fn));
         }
-        if !self.attrs.validations.is_empty() {
+        if self.attrs.range.is_some() || self.attrs.non_empty || !self.attrs.validations.is_empty()
+        {
+            let range = self.attrs.range.as_ref().map(|range| {
+                let description = format!("range {}", Self::describe_range(range));
+                quote_spanned! {range.span()=>
+                    &(#cr::validation::_private::WithDescription::new(#range, #description))
+                }
+            });
+            let non_empty = self.attrs.non_empty.then(|| {
+                quote!(&#cr::validation::_private::WithDescription::new(
+                    #cr::validation::NotEmpty,
+                    "non_empty"
+                ))
+            });
             let validations = self.attrs.validations.iter().map(|val| {
                 let wrapped = val.wrap(cr);
                 // A reference is required to convert to `&dyn Validate<_>`. `()`s are here to correctly handle some validation expressions
                 // (e.g., `a..b` ranges; unless a range is parenthesized, `&` will be interpreted as a part of the range start).
                 quote_spanned!(val.expr.span()=> &(#wrapped))
             });
+            let validations = range.into_iter().chain(non_empty).chain(validations);
             deserializer =
                 quote!(#cr::de::_private::Validated::new(#deserializer, &[#(#validations,)*]));
         }
@@ -99,6 +115,13 @@ impl ConfigField {
         deserializer
     }
 
+    /// Describes a range in a human-readable way, e.g. `1024..=65535`.
+    fn describe_range(range: &ExprRange) -> String {
+        let mut description = range.to_token_stream().to_string();
+        description.retain(|ch| !ch.is_whitespace());
+        description
+    }
+
     fn validate_names(&self, parent: &ConfigContainer) -> proc_macro2::TokenStream {
         let name_span = self.name_span();
         let param_name = self.param_name();
@@ -153,8 +176,19 @@ impl ConfigField {
             .as_ref()
             .map(quote::ToTokens::to_token_stream);
         let fallback = wrap_in_option(fallback);
+        let since = wrap_in_option(self.attrs.since.as_ref().map(|since| quote!(#since)));
+        let unit = wrap_in_option(self.attrs.unit.as_ref().map(|unit| quote!(#unit)));
+        let examples = &self.attrs.examples;
 
         let cr = parent.cr(name_span);
+        let allowed_origin = self.attrs.allowed_origin.map(|origin| match origin {
+            AllowedOrigin::EnvVars => quote!(#cr::value::OriginKind::EnvVars),
+            AllowedOrigin::File => quote!(#cr::value::OriginKind::File),
+        });
+        let allowed_origin = wrap_in_option(allowed_origin);
+        let is_rest = self.attrs.is_rest;
+        let range = self.attrs.range.as_ref().map(Self::describe_range);
+        let range = wrap_in_option(range.map(|range| quote!(#range)));
         let deserializer = self.deserializer(&cr);
         let tag_variant = wrap_in_option(variant_idx.map(|idx| quote!(&TAG_VARIANTS[#idx])));
 
@@ -175,6 +209,12 @@ impl ConfigField {
                 default_value: #default_value,
                 example_value: #example_value,
                 fallback: #fallback,
+                since: #since,
+                unit: #unit,
+                examples: &[#(#examples,)*],
+                allowed_origin: #allowed_origin,
+                is_rest: #is_rest,
+                range: #range,
             }
         }}
     }
@@ -194,6 +234,7 @@ impl ConfigField {
             self.param_name()
         };
         let tag_variant = wrap_in_option(variant_idx.map(|idx| quote!(&TAG_VARIANTS[#idx])));
+        let is_optional = Self::is_option(&self.ty) || self.attrs.default.is_some();
 
         quote_spanned! {self.name_span()=>
             #cr::metadata::NestedConfigMetadata {
@@ -201,6 +242,7 @@ impl ConfigField {
                 aliases: &[#(#aliases,)*],
                 rust_field_name: ::core::stringify!(#name),
                 tag_variant: #tag_variant,
+                is_optional: #is_optional,
                 meta: &<#ty as #cr::DescribeConfig>::DESCRIPTION,
             }
         }
@@ -352,11 +394,27 @@ impl ConfigContainer {
         }
     }
 
+    fn post_parse(&self) -> proc_macro2::TokenStream {
+        let name = &self.name;
+        let post_parse = self.attrs.post_parse.as_ref().map(|path| {
+            quote_spanned! {path.span()=>
+                |boxed_config| {
+                    let config = boxed_config
+                        .downcast_mut::<#name>()
+                        .expect("Internal error: post-parse target has incorrect type");
+                    #path(config)
+                }
+            }
+        });
+        wrap_in_option(post_parse)
+    }
+
     fn derive_describe_config(&self) -> proc_macro2::TokenStream {
         let name = &self.name;
         let cr = self.cr(name.span());
         let name_str = name.to_string();
         let help = &self.help;
+        let doc = &self.doc;
 
         let all_fields = self.fields.all_fields();
         let validations = all_fields
@@ -423,6 +481,8 @@ impl ConfigContainer {
             None
         });
 
+        let deny_unknown = self.attrs.deny_unknown;
+        let post_parse = self.post_parse();
         let config_validations = self
             .attrs
             .validations
@@ -438,9 +498,11 @@ impl ConfigContainer {
                     #cr::metadata::ConfigMetadata {
                         ty: #cr::metadata::RustType::of::<#name>(#name_str),
                         help: #help,
+                        doc: #doc,
                         params: PARAMS,
                         tag: #tag_description,
                         nested_configs: &[#(#nested_configs,)*],
+                        deny_unknown: #deny_unknown,
                         deserializer: |ctx| {
                             use #cr::metadata::_private::DeserializeBoxedConfig as _;
                             let receiver = &::core::marker::PhantomData::<#name>;
@@ -448,6 +510,7 @@ impl ConfigContainer {
                         },
                         visitor: #cr::metadata::_private::box_config_visitor::<Self>(),
                         validations: &[#(#config_validations,)*],
+                        post_parse: #post_parse,
                     }
                 };
             }
@@ -8,9 +8,9 @@ use std::{
 use proc_macro2::Ident;
 use quote::{quote, quote_spanned};
 use syn::{
-    ext::IdentExt, parse::ParseStream, spanned::Spanned, Attribute, Data, DataEnum, DataStruct,
-    DeriveInput, Expr, Field, Fields, GenericArgument, Index, Lit, LitStr, Member, Path,
-    PathArguments, Token, Type, TypePath,
+    ext::IdentExt, parse::ParseStream, punctuated::Punctuated, spanned::Spanned, Attribute, Data,
+    DataEnum, DataStruct, DeriveInput, Expr, ExprRange, Field, Fields, GenericArgument, Index, Lit,
+    LitStr, Member, Path, PathArguments, Token, Type, TypePath,
 };
 
 pub(crate) fn wrap_in_option(val: Option<proc_macro2::TokenStream>) -> proc_macro2::TokenStream {
@@ -21,8 +21,8 @@ pub(crate) fn wrap_in_option(val: Option<proc_macro2::TokenStream>) -> proc_macr
     }
 }
 
-fn parse_docs(attrs: &[Attribute]) -> String {
-    let doc_lines = attrs.iter().filter_map(|attr| {
+fn doc_lines(attrs: &[Attribute]) -> impl Iterator<Item = String> + '_ {
+    attrs.iter().filter_map(|attr| {
         if attr.meta.path().is_ident("doc") {
             let name_value = attr.meta.require_name_value().ok()?;
             let Expr::Lit(doc_literal) = &name_value.value else {
@@ -35,10 +35,12 @@ fn parse_docs(attrs: &[Attribute]) -> String {
         } else {
             None
         }
-    });
+    })
+}
 
+fn parse_docs(attrs: &[Attribute]) -> String {
     let mut docs = String::new();
-    for line in doc_lines {
+    for line in doc_lines(attrs) {
         let line = line.trim();
         if line.is_empty() {
             if !docs.is_empty() {
@@ -55,6 +57,16 @@ fn parse_docs(attrs: &[Attribute]) -> String {
     docs
 }
 
+/// Joins doc comment lines without normalization, only stripping the leading space inserted by `///` comments
+/// and leading / trailing empty lines.
+fn parse_verbatim_docs(attrs: &[Attribute]) -> String {
+    let lines: Vec<_> = doc_lines(attrs)
+        .map(|line| line.strip_prefix(' ').map(str::to_owned).unwrap_or(line))
+        .collect();
+    let lines = lines.join("\n");
+    lines.trim_matches('\n').to_owned()
+}
+
 #[derive(Debug)]
 pub(crate) struct Validation {
     pub(crate) expr: Expr,
@@ -150,7 +162,15 @@ pub(crate) struct Alias {
     pub(crate) is_deprecated: bool,
 }
 
+/// Restriction on the origin of param values.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum AllowedOrigin {
+    EnvVars,
+    File,
+}
+
 #[derive(Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub(crate) struct ConfigFieldAttrs {
     pub(crate) rename: Option<LitStr>,
     pub(crate) aliases: Vec<Alias>,
@@ -163,6 +183,13 @@ pub(crate) struct ConfigFieldAttrs {
     pub(crate) with: Option<Expr>,
     pub(crate) deserialize_if: Option<Validation>,
     pub(crate) validations: Vec<Validation>,
+    pub(crate) non_empty: bool,
+    pub(crate) range: Option<ExprRange>,
+    pub(crate) since: Option<LitStr>,
+    pub(crate) unit: Option<LitStr>,
+    pub(crate) examples: Vec<LitStr>,
+    pub(crate) allowed_origin: Option<AllowedOrigin>,
+    pub(crate) is_rest: bool,
 }
 
 impl ConfigFieldAttrs {
@@ -181,6 +208,14 @@ impl ConfigFieldAttrs {
         let mut secret_span = None;
         let mut deserialize_if = None;
         let mut validations = vec![];
+        let mut since = None;
+        let mut unit = None;
+        let mut examples_span = None;
+        let mut examples = vec![];
+        let mut allowed_origin = None;
+        let mut rest_span = None;
+        let mut non_empty_span = None;
+        let mut range = None;
         for attr in config_attrs {
             attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("rename") {
@@ -232,6 +267,49 @@ impl ConfigFieldAttrs {
                 } else if meta.path.is_ident("deserialize_if") {
                     deserialize_if = Some(Validation::new(meta.input)?);
                     Ok(())
+                } else if meta.path.is_ident("since") {
+                    since = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("unit") {
+                    unit = Some(meta.value()?.parse::<LitStr>()?);
+                    Ok(())
+                } else if meta.path.is_ident("examples") {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let lits = Punctuated::<LitStr, Token![,]>::parse_terminated(&content)?;
+                    examples.extend(lits);
+                    examples_span = Some(meta.path.span());
+                    Ok(())
+                } else if meta.path.is_ident("env_only") || meta.path.is_ident("file_only") {
+                    if allowed_origin.is_some() {
+                        let msg =
+                            "cannot specify `env_only` / `file_only` multiple times for a param";
+                        return Err(meta.error(msg));
+                    }
+                    let origin = if meta.path.is_ident("env_only") {
+                        AllowedOrigin::EnvVars
+                    } else {
+                        AllowedOrigin::File
+                    };
+                    allowed_origin = Some((origin, meta.path.span()));
+                    Ok(())
+                } else if meta.path.is_ident("rest") {
+                    rest_span = Some(meta.path.span());
+                    Ok(())
+                } else if meta.path.is_ident("non_empty") {
+                    non_empty_span = Some(meta.path.span());
+                    Ok(())
+                } else if meta.path.is_ident("range") {
+                    match meta.value()?.parse::<Expr>()? {
+                        Expr::Range(expr) => {
+                            range = Some(expr);
+                            Ok(())
+                        }
+                        expr => {
+                            let msg = "`range` must be a range expression, e.g. `1024..=65535`";
+                            Err(syn::Error::new(expr.span(), msg))
+                        }
+                    }
                 } else {
                     Err(meta.error("Unsupported attribute"))
                 }
@@ -280,6 +358,35 @@ impl ConfigFieldAttrs {
             let msg = "only params can be marked as secret, sub-configs cannot";
             return Err(syn::Error::new(secret_span, msg));
         }
+        if let (Some(since), true) = (&since, nest) {
+            let msg = "`since` can only be specified for params, not for sub-configs";
+            return Err(syn::Error::new(since.span(), msg));
+        }
+        if let (Some(unit), true) = (&unit, nest) {
+            let msg = "`unit` can only be specified for params, not for sub-configs";
+            return Err(syn::Error::new(unit.span(), msg));
+        }
+        if let (Some(examples_span), true) = (examples_span, nest) {
+            let msg = "`examples` can only be specified for params, not for sub-configs";
+            return Err(syn::Error::new(examples_span, msg));
+        }
+        if let (Some((_, span)), true) = (allowed_origin, nest) {
+            let msg =
+                "`env_only` / `file_only` can only be specified for params, not for sub-configs";
+            return Err(syn::Error::new(span, msg));
+        }
+        if let (Some(non_empty_span), true) = (non_empty_span, nest) {
+            let msg = "`non_empty` can only be specified for params, not for sub-configs";
+            return Err(syn::Error::new(non_empty_span, msg));
+        }
+        if let (Some(range), true) = (&range, nest) {
+            let msg = "`range` can only be specified for params, not for sub-configs";
+            return Err(syn::Error::new(range.span(), msg));
+        }
+        if let (Some(rest_span), true) = (rest_span, nest) {
+            let msg = "`rest` can only be specified for params, not for sub-configs";
+            return Err(syn::Error::new(rest_span, msg));
+        }
 
         Ok(Self {
             rename,
@@ -292,7 +399,14 @@ impl ConfigFieldAttrs {
             with,
             deserialize_if,
             validations,
+            non_empty: non_empty_span.is_some(),
+            range,
             is_secret: secret_span.is_some(),
+            since,
+            unit,
+            examples,
+            allowed_origin: allowed_origin.map(|(origin, _)| origin),
+            is_rest: rest_span.is_some(),
         })
     }
 }
@@ -486,6 +600,8 @@ pub(crate) struct ConfigContainerAttrs {
     pub(crate) tag: Option<LitStr>,
     pub(crate) validations: Vec<Validation>,
     pub(crate) derive_default: bool,
+    pub(crate) deny_unknown: bool,
+    pub(crate) post_parse: Option<Path>,
 }
 
 impl ConfigContainerAttrs {
@@ -497,6 +613,8 @@ impl ConfigContainerAttrs {
         let mut tag = None;
         let mut validations = vec![];
         let mut derive_default = false;
+        let mut deny_unknown = false;
+        let mut post_parse = None;
         for attr in config_attrs {
             attr.parse_nested_meta(|meta| {
                 if meta.path.is_ident("crate") {
@@ -525,6 +643,12 @@ impl ConfigContainerAttrs {
                         return Err(syn::Error::new(tr.span(), msg));
                     }
                     Ok(())
+                } else if meta.path.is_ident("deny_unknown") {
+                    deny_unknown = true;
+                    Ok(())
+                } else if meta.path.is_ident("post_parse") {
+                    post_parse = Some(meta.value()?.parse()?);
+                    Ok(())
                 } else {
                     Err(meta.error("Unsupported attribute"))
                 }
@@ -548,6 +672,8 @@ impl ConfigContainerAttrs {
             tag,
             validations,
             derive_default,
+            deny_unknown,
+            post_parse,
         })
     }
 }
@@ -612,6 +738,7 @@ pub(crate) struct ConfigContainer {
     pub(crate) attrs: ConfigContainerAttrs,
     pub(crate) name: Ident,
     pub(crate) help: String,
+    pub(crate) doc: String,
     pub(crate) fields: ConfigContainerFields,
 }
 
@@ -641,6 +768,7 @@ impl ConfigContainer {
             attrs,
             name,
             help: parse_docs(&raw.attrs),
+            doc: parse_verbatim_docs(&raw.attrs),
             fields,
         })
     }
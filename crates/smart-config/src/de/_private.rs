@@ -7,9 +7,12 @@ use serde_json::Value;
 
 use super::{deserializer::ValueDeserializer, DeserializeContext, DeserializeParam};
 use crate::{
-    error::{ErrorWithOrigin, LowLevelError},
+    error::{ErrorKind, ErrorWithOrigin, LowLevelError},
     metadata::{BasicTypes, ParamMetadata, TypeDescription},
+    utils::tracing,
     validation::Validate,
+    value::ValueOrigin,
+    ParseErrorCategory, Severity,
 };
 
 pub const fn extract_expected_types<T, De: DeserializeParam<T>>(_: &De) -> BasicTypes {
@@ -73,6 +76,57 @@ impl<T: 'static, De: DeserializeParam<T>> ErasedDeserializer for Erased<T, De> {
     }
 }
 
+/// Wrapper for [`WellKnown`](super::WellKnown) deserializers allowing to override them
+/// with [`CustomDeserializers`](super::CustomDeserializers).
+pub struct Customizable<T, De> {
+    inner: De,
+    _ty: PhantomData<fn(T)>,
+}
+
+impl<T, D: fmt::Debug> fmt::Debug for Customizable<T, D> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_tuple("Customizable")
+            .field(&self.inner)
+            .finish()
+    }
+}
+
+impl<T: 'static, De: DeserializeParam<T>> Customizable<T, De> {
+    pub const fn new(inner: De) -> Self {
+        Self {
+            inner,
+            _ty: PhantomData,
+        }
+    }
+}
+
+impl<T: 'static, De: DeserializeParam<T>> DeserializeParam<T> for Customizable<T, De> {
+    const EXPECTING: BasicTypes = De::EXPECTING;
+
+    fn describe(&self, description: &mut TypeDescription) {
+        self.inner.describe(description);
+    }
+
+    fn deserialize_param(
+        &self,
+        ctx: DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+    ) -> Result<T, ErrorWithOrigin> {
+        if let Some(custom) = ctx.de_options.custom_deserializers.get::<T>() {
+            let value = custom.deserialize_param(ctx, param)?;
+            return Ok(*value
+                .downcast()
+                .expect("Internal error: custom deserializer output has wrong type"));
+        }
+        self.inner.deserialize_param(ctx, param)
+    }
+
+    fn serialize_param(&self, param: &T) -> serde_json::Value {
+        self.inner.serialize_param(param)
+    }
+}
+
 /// Deserializer for enum tags.
 #[derive(Debug)]
 pub struct TagDeserializer {
@@ -117,12 +171,12 @@ impl DeserializeParam<&'static str> for TagDeserializer {
             .copied()
             .find(|&variant| variant == s)
             .ok_or_else(|| {
-                let err = DeError::unknown_variant(&s, self.expected);
+                let err: ErrorWithOrigin = DeError::unknown_variant(&s, self.expected);
                 let origin = ctx
                     .current_value()
                     .map(|val| val.origin.clone())
                     .unwrap_or_default();
-                ErrorWithOrigin::json(err, origin)
+                err.set_origin_if_unset(&origin)
             })
     }
 
@@ -158,16 +212,29 @@ fn validate<T>(
     ctx: &mut DeserializeContext<'_>,
     validations: &[&'static dyn Validate<T>],
 ) -> Result<(), ErrorWithOrigin> {
+    let coalesce = ctx.de_options.coalesce_validation_errors;
     let mut has_errors = false;
+    let mut failures = vec![];
     for &validation in validations {
         let _span = tracing::trace_span!("validation", %validation).entered();
         if let Err(err) = validation.validate(value) {
             tracing::warn!(%validation, %err, "validation failed");
-            ctx.push_generic_error(err, Some(validation.to_string()));
-            has_errors = true;
+            let severity = validation.severity();
+            has_errors |= severity == Severity::Error;
+            if coalesce {
+                failures.push((validation.to_string(), err, severity));
+            } else {
+                ctx.push_generic_error(err, Some(validation.to_string()), severity);
+            }
         }
     }
 
+    if failures.len() > 1 {
+        push_coalesced_error(ctx, failures);
+    } else if let Some((validation, err, severity)) = failures.pop() {
+        ctx.push_generic_error(err, Some(validation), severity);
+    }
+
     if has_errors {
         Err(ErrorWithOrigin::new(
             LowLevelError::Validation,
@@ -178,6 +245,59 @@ fn validate<T>(
     }
 }
 
+/// Combines multiple validation failures for a single param into a single error.
+#[cold]
+fn push_coalesced_error(
+    ctx: &mut DeserializeContext<'_>,
+    failures: Vec<(String, ErrorWithOrigin, Severity)>,
+) {
+    let severity = if failures
+        .iter()
+        .any(|(_, _, severity)| *severity == Severity::Error)
+    {
+        Severity::Error
+    } else {
+        Severity::Warning
+    };
+    // The category and kind of the coalesced error are taken from the most severe failure (the first one on a tie).
+    let primary_failure = failures
+        .iter()
+        .find(|(_, _, failure_severity)| *failure_severity == severity)
+        .expect("at least one failure has the maximum severity");
+    let (category, kind) = match &primary_failure.1.inner {
+        LowLevelError::Json { category, kind, .. } => (*category, *kind),
+        _ => (ParseErrorCategory::Generic, ErrorKind::Custom),
+    };
+    let origin = failures
+        .iter()
+        .map(|(_, err, _)| &err.origin)
+        .find(|origin| !matches!(origin.as_ref(), ValueOrigin::Unknown))
+        .cloned()
+        .unwrap_or_default();
+
+    let notes: Vec<_> = failures
+        .iter()
+        .filter_map(|(_, err, _)| match &err.inner {
+            LowLevelError::Json { note, .. } => note.as_deref(),
+            _ => None,
+        })
+        .collect();
+    let note = (!notes.is_empty()).then(|| notes.join("; "));
+
+    let (descriptions, messages): (Vec<_>, Vec<_>) = failures
+        .into_iter()
+        .map(|(validation, err, _)| (validation, err.inner.to_string()))
+        .unzip();
+    let err = LowLevelError::Json {
+        err: DeError::custom(messages.join("; ")),
+        category,
+        kind,
+        note,
+    };
+    let err = ErrorWithOrigin::new(err, origin);
+    ctx.push_generic_error(err, Some(descriptions.join(", ")), severity);
+}
+
 impl<T, De: DeserializeParam<T>> DeserializeParam<T> for Validated<T, De> {
     const EXPECTING: BasicTypes = De::EXPECTING;
 
@@ -1,27 +1,174 @@
 //! `serde`-compatible deserializer based on a value with origin.
 
-use std::sync::Arc;
+use std::{any, collections::HashMap, fmt, sync::Arc};
 
 use serde::{
     de::{
         self,
-        value::{MapDeserializer, SeqDeserializer},
+        value::{MapDeserializer, SeqDeserializer, StrDeserializer},
         DeserializeSeed, Error as DeError, IntoDeserializer,
     },
     Deserialize, Deserializer,
 };
 
+use super::{
+    _private::{Erased, ErasedDeserializer},
+    DeserializeParam,
+};
 use crate::{
-    error::ErrorWithOrigin,
+    error::{ErrorKind, ErrorWithOrigin},
     utils::EnumVariant,
     value::{Map, StrValue, Value, ValueOrigin, WithOrigin},
 };
 
 /// Available deserialization options.
+///
+/// New options may be added in the future, so this struct cannot be constructed using struct literal syntax;
+/// use [`Default`] and mutate the fields (e.g., via [`ConfigRepository::deserializer_options()`](crate::ConfigRepository::deserializer_options())).
 #[derive(Debug, Clone, Default)]
+#[allow(clippy::struct_excessive_bools)] // options are independent flags
+#[non_exhaustive]
 pub struct DeserializerOptions {
     /// Enables coercion of variant names between cases, e.g. from `SHOUTING_CASE` to `shouting_case`.
     pub coerce_variant_names: bool,
+    /// Enables lenient coercion of strings to Boolean values. If set, params expecting a Boolean value accept
+    /// `true` / `false`, `1` / `0`, `yes` / `no` and `on` / `off` strings (case-insensitive). By default,
+    /// only `true` and `false` strings are accepted.
+    pub coerce_bool_strings: bool,
+    /// Disables coercion of numeric strings (e.g., `"8080"`) to numbers for values from file formats that natively
+    /// support numbers (JSON, YAML and TOML); such strings lead to a type mismatch error instead. Values from
+    /// string-only sources (e.g., env variables or `.env` files) are still coerced. By default, numeric strings
+    /// are coerced regardless of the source.
+    pub deny_numeric_strings: bool,
+    /// Coalesces all validation failures for a single param into a single [`ParseError`](crate::ParseError)
+    /// listing all failed validations. By default, each failed validation produces a separate error.
+    pub coalesce_validation_errors: bool,
+    /// Policy for reporting [unknown keys](crate::ConfigRepository::unknown_keys()) in sources. By default,
+    /// unknown keys are silently ignored.
+    pub unknown_keys: UnknownKeys,
+    /// Custom deserializers for param types. By default, no custom deserializers are registered.
+    pub custom_deserializers: CustomDeserializers,
+}
+
+impl DeserializerOptions {
+    /// Checks whether numeric strings originating from `origin` should be coerced to numbers.
+    pub(crate) fn coerces_numeric_strings(&self, origin: &ValueOrigin) -> bool {
+        !self.deny_numeric_strings || !origin.has_native_numbers()
+    }
+}
+
+/// Registry of custom param deserializers keyed by the param type. Used in [`DeserializerOptions`].
+///
+/// A custom deserializer registered for type `T` is used for all params of type `T` that rely on the default
+/// [`WellKnown`](super::WellKnown) deserializer. Params with an explicit `#[config(with = _)]` attribute
+/// are not affected. Param decorators (e.g., default values and validations) are applied on top
+/// of the custom deserializer as usual. The param type must match exactly; e.g., a deserializer registered for `T`
+/// is not used for `Option<T>` params.
+///
+/// Custom deserializers only affect deserialization. In particular, param values are still [coerced](crate::de)
+/// to the types expected by the default deserializer, and the default deserializer is used for serialization
+/// and in the config metadata.
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::{
+/// #     de::{DeserializeContext, DeserializeParam}, metadata::{BasicTypes, ParamMetadata},
+/// #     ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig, ErrorWithOrigin,
+/// # };
+/// # use serde::Deserialize;
+/// /// Deserializer for ports accepting `:`-prefixed strings, like `:8080`.
+/// #[derive(Debug)]
+/// struct ColonPrefixed;
+///
+/// impl DeserializeParam<u16> for ColonPrefixed {
+///     const EXPECTING: BasicTypes = BasicTypes::STRING;
+///
+///     fn deserialize_param(
+///         &self,
+///         ctx: DeserializeContext<'_>,
+///         param: &'static ParamMetadata,
+///     ) -> Result<u16, ErrorWithOrigin> {
+///         let de = ctx.current_value_deserializer(param.name)?;
+///         let s = String::deserialize(de)?;
+///         let port = s.strip_prefix(':').unwrap_or(&s);
+///         port.parse().map_err(ErrorWithOrigin::custom)
+///     }
+///
+///     fn serialize_param(&self, param: &u16) -> serde_json::Value {
+///         format!(":{param}").into()
+///     }
+/// }
+///
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     port: u16,
+///     #[config(default_t = 9090)]
+///     metrics_port: u16,
+/// }
+///
+/// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "");
+/// let mut repo = ConfigRepository::new(&schema)
+///     .with(smart_config::config!("port": ":8080"));
+/// repo.deserializer_options()
+///     .custom_deserializers
+///     .insert::<u16, _>(ColonPrefixed);
+/// let config: TestConfig = repo.single()?.parse()?;
+/// assert_eq!(config.port, 8080);
+/// assert_eq!(config.metrics_port, 9090);
+/// # anyhow::Ok(())
+/// ```
+#[derive(Clone, Default)]
+pub struct CustomDeserializers {
+    inner: HashMap<any::TypeId, (&'static str, Arc<dyn ErasedDeserializer>)>,
+}
+
+impl fmt::Debug for CustomDeserializers {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries = self.inner.values().map(|(ty, de)| (ty, de));
+        formatter.debug_map().entries(entries).finish()
+    }
+}
+
+impl CustomDeserializers {
+    /// Registers a custom deserializer for the param type `T`, replacing the previously registered deserializer
+    /// for this type (if any).
+    pub fn insert<T: 'static, De: DeserializeParam<T>>(&mut self, deserializer: De) -> &mut Self {
+        let erased = Arc::new(Erased::<T, _>::new(deserializer));
+        self.inner
+            .insert(any::TypeId::of::<T>(), (any::type_name::<T>(), erased));
+        self
+    }
+
+    /// Removes a custom deserializer for the param type `T`. Returns `true` if the deserializer was registered.
+    pub fn remove<T: 'static>(&mut self) -> bool {
+        self.inner.remove(&any::TypeId::of::<T>()).is_some()
+    }
+
+    pub(super) fn get<T: 'static>(&self) -> Option<&dyn ErasedDeserializer> {
+        if self.inner.is_empty() {
+            return None; // fast path
+        }
+        let (_, de) = self.inner.get(&any::TypeId::of::<T>())?;
+        Some(de.as_ref())
+    }
+}
+
+/// Policy for reporting unknown keys in configuration sources, i.e. keys not corresponding to any param or config
+/// in the schema. Used in [`DeserializerOptions`].
+///
+/// Unknown keys are reported by the parser of the closest enclosing config as [`ParseError`](crate::ParseError)s
+/// with the [`unknown_key`](crate::ParseError::code()) code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum UnknownKeys {
+    /// Ignore unknown keys.
+    #[default]
+    Ignore,
+    /// Report unknown keys as [warnings](crate::Severity::Warning).
+    Warn,
+    /// Report unknown keys as errors, failing parsing.
+    Deny,
 }
 
 impl WithOrigin {
@@ -46,10 +193,25 @@ impl WithOrigin {
             Value::Array(_) => de::Unexpected::Seq,
             Value::Object(_) => de::Unexpected::Map,
         };
-        ErrorWithOrigin::json(
-            DeError::invalid_type(actual, &expected),
-            self.origin.clone(),
-        )
+        let err: ErrorWithOrigin = DeError::invalid_type(actual, &expected);
+        err.set_origin_if_unset(&self.origin)
+    }
+}
+
+const LENIENT_BOOL_FORMS: &str = "`true` / `false`, `1` / `0`, `yes` / `no`, `on` / `off`";
+
+/// Parses a Boolean value in the lenient mode, i.e. with [`DeserializerOptions::coerce_bool_strings`] enabled.
+pub(super) fn parse_lenient_bool(s: &str) -> Option<bool> {
+    const TRUE_FORMS: [&str; 4] = ["true", "1", "yes", "on"];
+    const FALSE_FORMS: [&str; 4] = ["false", "0", "no", "off"];
+
+    let s = s.trim();
+    if TRUE_FORMS.iter().any(|form| s.eq_ignore_ascii_case(form)) {
+        Some(true)
+    } else if FALSE_FORMS.iter().any(|form| s.eq_ignore_ascii_case(form)) {
+        Some(false)
+    } else {
+        None
     }
 }
 
@@ -58,16 +220,19 @@ macro_rules! parse_int_value {
         $(
         fn $method<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
             let result = match self.value() {
+                Value::String(_) if !self.options.coerces_numeric_strings(&self.value.origin) => {
+                    return Err(self.invalid_type(&format!("{} number", stringify!($ty))));
+                }
                 Value::String(s) => {
                     match s.expose().parse::<$ty>() {
                         Ok(val) => val.into_deserializer().$method(visitor),
                         Err(err) => {
                             let err = DeError::custom(format_args!("{err} while parsing {} value '{s}'", stringify!($ty)));
-                            return Err(self.enrich_err(err));
+                            return Err(self.enrich_err_with_kind(err, ErrorKind::InvalidValue));
                         }
                     }
                 }
-                Value::Number(number) => number.deserialize_any(visitor).map_err(|err| self.enrich_err(err.into())),
+                Value::Number(number) => self.visit_number(number, visitor),
                 _ => return Err(self.invalid_type(&format!("{} number", stringify!($ty)))),
             };
             result.map_err(|err| err.set_origin_if_unset(&self.value.origin))
@@ -99,6 +264,35 @@ impl<'a> ValueDeserializer<'a> {
         ErrorWithOrigin::json(err, self.value.origin.clone())
     }
 
+    pub(super) fn enrich_err_with_kind(
+        &self,
+        err: serde_json::Error,
+        kind: ErrorKind,
+    ) -> ErrorWithOrigin {
+        ErrorWithOrigin::json_with_kind(err, kind, self.value.origin.clone())
+    }
+
+    /// Visits a number directly (rather than via `serde_json::Number::deserialize_any()`) so that the visitor errors
+    /// retain their kind.
+    fn visit_number<'de, V: de::Visitor<'de>>(
+        &self,
+        number: &serde_json::Number,
+        visitor: V,
+    ) -> Result<V::Value, ErrorWithOrigin> {
+        let result = if let Some(value) = number.as_u64() {
+            visitor.visit_u64(value)
+        } else if let Some(value) = number.as_i64() {
+            visitor.visit_i64(value)
+        } else if let Some(value) = number.as_f64() {
+            visitor.visit_f64(value)
+        } else {
+            return number
+                .deserialize_any(visitor)
+                .map_err(|err| self.enrich_err(err));
+        };
+        result.map_err(|err: ErrorWithOrigin| err.set_origin_if_unset(&self.value.origin))
+    }
+
     pub(super) fn invalid_type(&self, expected: &str) -> ErrorWithOrigin {
         self.value.invalid_type(expected)
     }
@@ -141,9 +335,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'_> {
         let result = match self.value() {
             Value::Null => visitor.visit_none(),
             Value::Bool(value) => visitor.visit_bool(*value),
-            Value::Number(value) => value
-                .deserialize_any(visitor)
-                .map_err(|err| self.enrich_err(err)),
+            Value::Number(value) => self.visit_number(value, visitor),
             Value::String(value) => visitor.visit_str(value.expose()),
             Value::Array(array) => self.parse_array(array, visitor),
             Value::Object(object) => self.parse_object(object, visitor),
@@ -258,17 +450,26 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'_> {
     fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
         let result = match self.value() {
             Value::Bool(value) => visitor.visit_bool(*value),
+            Value::String(s) if self.options.coerce_bool_strings => {
+                let Some(val) = parse_lenient_bool(s.expose()) else {
+                    let err = DeError::custom(format_args!(
+                        "cannot parse value '{s}' as boolean; expected one of {LENIENT_BOOL_FORMS} (case-insensitive)"
+                    ));
+                    return Err(self.enrich_err_with_kind(err, ErrorKind::InvalidValue));
+                };
+                visitor.visit_bool(val)
+            }
             Value::String(s) => match s.expose().parse::<bool>() {
                 Ok(val) => visitor.visit_bool(val),
                 Err(err) => {
                     let err =
                         DeError::custom(format_args!("{err} while parsing value '{s}' as boolean"));
-                    return Err(self.enrich_err(err));
+                    return Err(self.enrich_err_with_kind(err, ErrorKind::InvalidValue));
                 }
             },
             _ => return Err(self.invalid_type("boolean or boolean-like string")),
         };
-        result.map_err(|err: serde_json::Error| self.enrich_err(err))
+        result.map_err(|err: ErrorWithOrigin| err.set_origin_if_unset(&self.value.origin))
     }
 
     parse_int_value! {
@@ -348,7 +549,7 @@ impl<'de> Deserializer<'de> for ValueDeserializer<'_> {
     ) -> Result<V::Value, Self::Error> {
         visitor
             .visit_unit()
-            .map_err(|err: serde_json::Error| self.enrich_err(err))
+            .map_err(|err: ErrorWithOrigin| err.set_origin_if_unset(&self.value.origin))
     }
 }
 
@@ -374,10 +575,10 @@ impl<'a, 'de> de::EnumAccess<'de> for EnumDeserializer<'a> {
         self,
         seed: V,
     ) -> Result<(V::Value, Self::Variant), Self::Error> {
-        let variant = self.variant.into_deserializer();
+        let variant: StrDeserializer<'_, ErrorWithOrigin> = self.variant.into_deserializer();
         match seed.deserialize(variant) {
             Ok(val) => Ok((val, self.inner)),
-            Err(err) => Err(ErrorWithOrigin::json(err, self.inner.origin().clone())),
+            Err(err) => Err(err.set_origin_if_unset(self.inner.origin())),
         }
     }
 }
@@ -0,0 +1,120 @@
+//! Tri-state params distinguishing missing values from explicit `null`s.
+
+use super::{DeserializeContext, DeserializeParam, WellKnown};
+use crate::{
+    error::ErrorWithOrigin,
+    metadata::{BasicTypes, ParamMetadata, TypeDescription},
+    value::Value,
+};
+
+/// Tri-state param value that distinguishes between a missing value, an explicit `null` and a set value.
+///
+/// Unlike [`Option`], which maps both missing and `null` values to `None`, this type allows to express
+/// toggleable subsystems, e.g. with the missing value meaning "use defaults" and `null` meaning "disable".
+/// `Maybe<T>` is [`WellKnown`] if `T` is; it's deserialized using the [`Tristate`] decorator.
+///
+/// It usually makes sense to mark `Maybe` params with `#[config(default)]`; this will make the param
+/// non-required in the config docs, and will allow to derive `Default` for the containing config.
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::{de::Maybe, testing, DescribeConfig, DeserializeConfig};
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(default)]
+///     port: Maybe<u16>,
+/// }
+///
+/// let config: TestConfig = testing::test(smart_config::config!())?;
+/// assert_eq!(config.port, Maybe::Absent);
+/// let config: TestConfig = testing::test(smart_config::config!("port": ()))?;
+/// assert_eq!(config.port, Maybe::Null);
+/// let config: TestConfig = testing::test(smart_config::config!("port": 8080))?;
+/// assert_eq!(config.port, Maybe::Set(8080));
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Maybe<T> {
+    /// Param value is missing.
+    #[default]
+    Absent,
+    /// Param value is explicitly set to `null`.
+    Null,
+    /// Param value is set.
+    Set(T),
+}
+
+impl<T> Maybe<T> {
+    /// Checks whether the param value is missing.
+    pub const fn is_absent(&self) -> bool {
+        matches!(self, Self::Absent)
+    }
+
+    /// Checks whether the param value is explicitly set to `null`.
+    pub const fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Converts a reference to this value to a `Maybe` of a reference.
+    pub const fn as_ref(&self) -> Maybe<&T> {
+        match self {
+            Self::Absent => Maybe::Absent,
+            Self::Null => Maybe::Null,
+            Self::Set(value) => Maybe::Set(value),
+        }
+    }
+
+    /// Converts this value to an `Option`, mapping both missing and `null` values to `None`.
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Self::Absent | Self::Null => None,
+            Self::Set(value) => Some(value),
+        }
+    }
+}
+
+/// Deserializer decorator for [`Maybe`] params. Returns [`Maybe::Absent`] if the param value is missing,
+/// [`Maybe::Null`] if it's `null`, and otherwise delegates to the wrapped deserializer.
+///
+/// Similarly to [`Optional`](super::Optional), empty strings and `"null"` are coerced to `null`
+/// if the wrapped deserializer doesn't expect string values.
+///
+/// # Serialization
+///
+/// Since there's no way to express a missing value in JSON, both [`Maybe::Absent`] and [`Maybe::Null`]
+/// are serialized as `null`.
+#[derive(Debug)]
+pub struct Tristate<De>(pub De);
+
+impl<T, De: DeserializeParam<T>> DeserializeParam<Maybe<T>> for Tristate<De> {
+    const EXPECTING: BasicTypes = De::EXPECTING;
+
+    fn describe(&self, description: &mut TypeDescription) {
+        self.0.describe(description);
+    }
+
+    fn deserialize_param(
+        &self,
+        ctx: DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+    ) -> Result<Maybe<T>, ErrorWithOrigin> {
+        match ctx.current_value().map(|val| &val.inner) {
+            None => Ok(Maybe::Absent),
+            Some(Value::Null) => Ok(Maybe::Null),
+            Some(_) => self.0.deserialize_param(ctx, param).map(Maybe::Set),
+        }
+    }
+
+    fn serialize_param(&self, param: &Maybe<T>) -> serde_json::Value {
+        match param {
+            Maybe::Absent | Maybe::Null => serde_json::Value::Null,
+            Maybe::Set(value) => self.0.serialize_param(value),
+        }
+    }
+}
+
+impl<T: WellKnown> WellKnown for Maybe<T> {
+    type Deserializer = Tristate<T::Deserializer>;
+    const DE: Self::Deserializer = Tristate(T::DE);
+}
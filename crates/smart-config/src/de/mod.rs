@@ -19,6 +19,7 @@
 //! If you have a custom type defined locally which you want to use in configs, the easiest solution
 //! would be to implement `WellKnown` (+ maybe `WellKnownOption`) for it.
 //! Alternatively, it's possible to specify a custom deserializer using `#[config(with = _)]` attribute.
+//! Deserializers for params without this attribute can also be overridden in runtime using [`CustomDeserializers`].
 //!
 //! ## Universal deserializers
 //!
@@ -27,6 +28,28 @@
 //! the input `Value` to be present; otherwise, they'll fail with a "missing value" error. As such,
 //! for [`Option`]al types, it's necessary to wrap a deserializer in the [`Optional`] decorator.
 //!
+//! ## Value coercion
+//!
+//! Many config sources (e.g., env variables) can only provide string values. To support such sources, string values
+//! are coerced to the type expected by the param [deserializer](DeserializeParam::EXPECTING) before deserialization.
+//! By default, coercion applies to values from all sources, including hierarchical ones like JSON or YAML:
+//!
+//! - If a param expects a Boolean value, strings `true` / `false` are coerced to Boolean values.
+//!   If [`DeserializerOptions::coerce_bool_strings`] is set, `1` / `0`, `yes` / `no` and `on` / `off`
+//!   (case-insensitive) are coerced as well.
+//! - If a param expects an integer or a float, numeric strings like `"8080"` or `"0.5"` are coerced to numbers.
+//!   Non-numeric strings are not coerced and lead to a deserialization error. Coercion can be disabled for values
+//!   from JSON, YAML and TOML files via [`DeserializerOptions::deny_numeric_strings`].
+//! - If a param doesn't accept strings, an empty string or `"null"` is coerced to `null`.
+//!
+//! Coercion is skipped if the param accepts multiple primitive types, e.g. both strings and integers,
+//! since it would be ambiguous in this case.
+//!
+//! ## Missing vs `null` values
+//!
+//! `Option`al params do not distinguish between missing and `null` values. If this distinction is important
+//! (e.g., for toggleable subsystems), a [`Maybe`] param can be used instead.
+//!
 //! ## Durations and byte sizes
 //!
 //! [`Duration`]s and [`ByteSize`]s can be deserialized in two ways:
@@ -35,6 +58,12 @@
 //!   in a single-key object like `{ "mb": 4 }`. See [`WithUnit`] for more details.
 //! - Alternatively, [`TimeUnit`](crate::metadata::TimeUnit) and [`SizeUnit`](crate::metadata::SizeUnit) can be used
 //!   on `Duration`s and `ByteSize`s, respectively.
+//! - [`WithDefaultUnit`] combines both approaches: it accepts all inputs supported by `WithUnit`, and interprets
+//!   bare integers in the specified unit.
+//!
+//! `WithUnit` treats `kb` / `mb` / `gb` units as binary ones (i.e., as aliases of `KiB` / `MiB` / `GiB`).
+//! If SI (decimal) units need to be supported, use [`SiOrBinarySize`], which distinguishes between SI
+//! and binary units.
 //!
 //! ## Secrets
 //!
@@ -49,33 +78,36 @@
 //! [`Duration`]: std::time::Duration
 //! [`ByteSize`]: crate::ByteSize
 
-use std::any;
+use std::{any, iter};
 
 use serde::de::Error as DeError;
 
 use self::deserializer::ValueDeserializer;
 pub use self::{
-    deserializer::DeserializerOptions,
+    deserializer::{CustomDeserializers, DeserializerOptions, UnknownKeys},
     macros::Serde,
+    maybe::{Maybe, Tristate},
     param::{
         CustomKnownOption, DeserializeParam, Optional, OrString, Qualified, Serde, WellKnown,
         WellKnownOption, WithDefault,
     },
     repeated::{Delimited, Entries, NamedEntries, Repeated, ToEntries},
     secret::{FromSecretString, Secret},
-    units::WithUnit,
+    units::{SiOrBinarySize, WithDefaultUnit, WithUnit},
 };
 use crate::{
-    error::{ErrorWithOrigin, LocationInConfig, LowLevelError},
+    error::{ErrorKind, ErrorWithOrigin, LocationInConfig, LowLevelError},
     metadata::{BasicTypes, ConfigMetadata, ParamMetadata},
+    utils::{levenshtein_distance, tracing},
     value::{Pointer, StrValue, Value, ValueOrigin, WithOrigin},
-    DescribeConfig, DeserializeConfigError, ParseError, ParseErrorCategory, ParseErrors,
+    DescribeConfig, DeserializeConfigError, ParseError, ParseErrorCategory, ParseErrors, Severity,
 };
 
 #[doc(hidden)]
 pub mod _private;
 mod deserializer;
 mod macros;
+mod maybe;
 mod param;
 #[cfg(feature = "primitive-types")]
 mod primitive_types_impl;
@@ -211,18 +243,40 @@ impl<'a> DeserializeContext<'a> {
 
     /// Pushes a deserialization error into the context.
     pub fn push_error(&mut self, err: ErrorWithOrigin) {
-        self.push_generic_error(err, None);
+        self.push_generic_error(err, None, Severity::Error);
     }
 
     #[cold]
-    fn push_generic_error(&mut self, err: ErrorWithOrigin, validation: Option<String>) {
-        let (inner, category) = match err.inner {
-            LowLevelError::Json { err, category } => (err, category),
+    fn push_generic_error(
+        &mut self,
+        err: ErrorWithOrigin,
+        validation: Option<String>,
+        severity: Severity,
+    ) {
+        let (inner, category, kind, note) = match err.inner {
+            LowLevelError::Json {
+                err,
+                category,
+                kind,
+                note,
+            } => (err, category, kind, note),
             LowLevelError::InvalidArray
             | LowLevelError::InvalidObject
             | LowLevelError::Validation => return,
         };
 
+        let suggestions = if matches!(category, ParseErrorCategory::MissingField) {
+            self.suggest_param_names()
+        } else {
+            vec![]
+        };
+
+        let type_mismatch = if matches!(category, ParseErrorCategory::Generic) {
+            self.detect_type_mismatch(kind)
+        } else {
+            None
+        };
+
         let mut origin = err.origin;
         if matches!(origin.as_ref(), ValueOrigin::Unknown) {
             if let Some(val) = self.current_value() {
@@ -233,19 +287,107 @@ impl<'a> DeserializeContext<'a> {
         self.errors.push(ParseError {
             inner,
             category,
+            kind,
             path: self.path.clone(),
             origin,
             config: self.current_config,
             location_in_config: self.location_in_config,
             validation,
+            suggestions,
+            note,
+            type_mismatch,
+            severity,
+        });
+    }
+
+    /// Checks whether the error is caused by the param value having a type not expected by the param.
+    /// This is the case if the value type cannot be coerced to an expected type, or if the value is a string
+    /// (which is coercible to Booleans and numbers) that doesn't parse as any of the expected scalar types.
+    fn detect_type_mismatch(&self, kind: ErrorKind) -> Option<(BasicTypes, BasicTypes)> {
+        if !matches!(kind, ErrorKind::InvalidType | ErrorKind::InvalidValue) {
+            return None;
+        }
+        let LocationInConfig::Param(idx) = self.location_in_config?;
+        let param = self.current_config.params.get(idx)?;
+        // Errors for param items (e.g., array elements) are not checked.
+        let (_, last_segment) = Pointer(&self.path).split_last()?;
+        if last_segment != param.name {
+            return None;
+        }
+        let expected = param.expecting;
+        let value = self.current_value()?;
+        let actual = value.inner.basic_type()?;
+        if expected.contains(actual) {
+            return None;
+        }
+
+        // Strings are parsed into Booleans and (unless disabled) numbers.
+        let coerces_to_bool = expected.contains(BasicTypes::BOOL);
+        let coerces_to_number = expected.contains(BasicTypes::INTEGER)
+            && self.de_options.coerces_numeric_strings(&value.origin);
+        let is_mismatch = if actual == BasicTypes::STRING && (coerces_to_bool || coerces_to_number)
+        {
+            // Secret strings are not inspected.
+            let s = value.inner.as_plain_str()?;
+            let parses_as_bool = coerces_to_bool && deserializer::parse_lenient_bool(s).is_some();
+            let parses_as_number = coerces_to_number && s.trim().parse::<f64>().is_ok();
+            !parses_as_bool && !parses_as_number
+        } else {
+            matches!(kind, ErrorKind::InvalidType)
+        };
+        is_mismatch.then_some((expected, actual))
+    }
+
+    /// Suggests the missing param name if the enclosing config object contains unknown keys similar to it.
+    fn suggest_param_names(&self) -> Vec<&'static str> {
+        /// Max edit distance between an unknown key and the param name for the param to be suggested.
+        const MAX_DISTANCE: usize = 2;
+
+        let Some(LocationInConfig::Param(idx)) = self.location_in_config else {
+            return vec![];
+        };
+        let Some(param) = self.current_config.params.get(idx) else {
+            return vec![];
+        };
+        let Some((parent_path, _)) = Pointer(&self.path).split_last() else {
+            return vec![];
+        };
+        let Some(Value::Object(parent)) = self.root_value.get(parent_path).map(|val| &val.inner)
+        else {
+            return vec![];
+        };
+
+        let config = self.current_config;
+        let is_known_key = |key: &str| {
+            let param_names = config.params.iter().flat_map(|param| {
+                iter::once(param.name).chain(param.aliases.iter().map(|(name, _)| *name))
+            });
+            let config_names = config.nested_configs.iter().flat_map(|nested| {
+                iter::once(nested.name).chain(nested.aliases.iter().map(|(name, _)| *name))
+            });
+            param_names.chain(config_names).any(|name| name == key)
+        };
+        let param_names = iter::once(param.name).chain(param.aliases.iter().map(|(name, _)| *name));
+        let param_names: Vec<_> = param_names.collect();
+
+        let has_similar_key = parent.keys().any(|key| {
+            !is_known_key(key)
+                && param_names
+                    .iter()
+                    .any(|name| levenshtein_distance(key, name) <= MAX_DISTANCE)
         });
+        if has_similar_key {
+            vec![param.name]
+        } else {
+            vec![]
+        }
     }
 
-    #[tracing::instrument(
+    #[cfg_attr(feature = "tracing", tracing::instrument(
         level = "trace",
         skip_all,
         fields(path = self.path, config = ?self.current_config.ty)
-    )]
+    ))]
     pub(crate) fn deserialize_any_config(
         mut self,
     ) -> Result<Box<dyn any::Any>, DeserializeConfigError> {
@@ -259,23 +401,36 @@ impl<'a> DeserializeContext<'a> {
                 return Err(DeserializeConfigError::new());
             }
         }
-        let config = (self.current_config.deserializer)(self.borrow())?;
+        let mut config = (self.current_config.deserializer)(self.borrow())?;
 
         let mut has_errors = false;
         for &validation in self.current_config.validations {
             let _span = tracing::trace_span!("validation", %validation).entered();
             if let Err(err) = validation.validate(config.as_ref()) {
                 tracing::info!(%validation, origin = %err.origin, "config validation failed: {}", err.inner);
-                self.push_generic_error(err, Some(validation.to_string()));
-                has_errors = true;
+                let severity = validation.severity();
+                self.push_generic_error(err, Some(validation.to_string()), severity);
+                has_errors |= severity == Severity::Error;
             }
         }
 
         if has_errors {
-            Err(DeserializeConfigError::new())
-        } else {
-            Ok(config)
+            return Err(DeserializeConfigError::new());
+        }
+
+        if let Some(post_parse) = self.current_config.post_parse {
+            let _span = tracing::trace_span!("post_parse").entered();
+            if let Err(err) = post_parse(config.as_mut()) {
+                tracing::info!(origin = %err.origin, "config post-parse transform failed: {}", err.inner);
+                self.push_generic_error(
+                    err,
+                    Some("post-parse transform".to_owned()),
+                    Severity::Error,
+                );
+                return Err(DeserializeConfigError::new());
+            }
         }
+        Ok(config)
     }
 
     /// Caller is responsible to downcast the config to the correct type.
@@ -302,6 +457,7 @@ impl<'a> DeserializeContext<'a> {
                     .errors
                     .iter()
                     .skip(error_count)
+                    .filter(|err| err.severity == Severity::Error)
                     .all(|err| matches!(err.category, ParseErrorCategory::MissingField));
                 if only_missing_field_errors {
                     tracing::trace!(
@@ -351,12 +507,12 @@ impl DeserializeContext<'_> {
         self.for_nested_config(index).deserialize_config_opt()
     }
 
-    #[tracing::instrument(
+    #[cfg_attr(feature = "tracing", tracing::instrument(
         level = "trace",
         name = "deserialize_param",
         skip_all,
         fields(path = self.path, config = ?self.current_config.ty, param)
-    )]
+    ))]
     pub(crate) fn deserialize_any_param(
         &mut self,
         index: usize,
@@ -364,10 +520,23 @@ impl DeserializeContext<'_> {
         let (mut child_ctx, param) = self.for_param(index);
         tracing::Span::current().record("param", param.rust_field_name);
 
+        if let (Some(allowed_origin), Some(val)) = (param.allowed_origin, child_ctx.current_value())
+        {
+            let origin_kind = val.origin.kind();
+            if origin_kind != allowed_origin {
+                let err = DeError::custom(format_args!(
+                    "param can only be set from {allowed_origin}, but its value is obtained from {origin_kind}"
+                ));
+                child_ctx.push_error(ErrorWithOrigin::json(err, val.origin.clone()));
+                return Err(DeserializeConfigError::new());
+            }
+        }
+
         // Coerce value to the expected type.
+        let de_options = child_ctx.de_options;
         let maybe_coerced = child_ctx
             .current_value()
-            .and_then(|val| val.coerce_value_type(param.expecting));
+            .and_then(|val| val.coerce_value_type(param.expecting, de_options));
         let mut child_ctx = if let Some(coerced) = &maybe_coerced {
             child_ctx.patched(coerced)
         } else {
@@ -404,8 +573,15 @@ impl DeserializeContext<'_> {
 }
 
 impl WithOrigin {
-    #[tracing::instrument(level = "trace", skip(self))]
-    fn coerce_value_type(&self, expecting: BasicTypes) -> Option<Self> {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip(self, options))
+    )]
+    fn coerce_value_type(
+        &self,
+        expecting: BasicTypes,
+        options: &DeserializerOptions,
+    ) -> Option<Self> {
         let Value::String(StrValue::Plain(str)) = &self.inner else {
             return None; // we only know how to coerce strings so far
         };
@@ -420,6 +596,12 @@ impl WithOrigin {
         match expecting {
             // We intentionally use exact comparisons; if a type supports multiple primitive representations,
             // we do nothing.
+            BasicTypes::BOOL if options.coerce_bool_strings => {
+                if let Some(bool_value) = deserializer::parse_lenient_bool(str) {
+                    return Some(Self::new(bool_value.into(), self.origin.clone()));
+                }
+                tracing::info!(%expecting, "failed coercing value: not a Boolean-like string");
+            }
             BasicTypes::BOOL => match str.parse::<bool>() {
                 Ok(bool_value) => {
                     return Some(Self::new(bool_value.into(), self.origin.clone()));
@@ -428,6 +610,11 @@ impl WithOrigin {
                     tracing::info!(%expecting, "failed coercing value: {err}");
                 }
             },
+            BasicTypes::INTEGER | BasicTypes::FLOAT
+                if !options.coerces_numeric_strings(&self.origin) =>
+            {
+                tracing::info!(%expecting, "numeric string coercion is disabled for the value origin");
+            }
             BasicTypes::INTEGER | BasicTypes::FLOAT => match str.parse::<serde_json::Number>() {
                 Ok(number) => {
                     return Some(Self::new(number.into(), self.origin.clone()));
@@ -35,6 +35,7 @@ use crate::{
 /// - [`SizeUnit`](crate::metadata::SizeUnit) similarly deserializes [`ByteSize`](crate::ByteSize)
 /// - [`WithUnit`](super::WithUnit) deserializes `Duration`s / `ByteSize`s as an integer + unit of measurement
 ///   (either in a string or object form).
+/// - [`WithDefaultUnit`](super::WithDefaultUnit) extends `WithUnit` by accepting bare integers in the specified unit.
 ///
 /// ## Decorators
 ///
@@ -42,14 +42,15 @@ impl<De> Repeated<De> {
 
         if let Some(expected_len) = expected_len {
             if items.len() != expected_len {
-                let err = DeError::invalid_length(items.len(), &expected_len.to_string().as_str());
-                return Err(deserializer.enrich_err(err));
+                let err: ErrorWithOrigin =
+                    DeError::invalid_length(items.len(), &expected_len.to_string().as_str());
+                return Err(err.set_origin_if_unset(deserializer.origin()));
             }
         }
 
         let mut has_errors = false;
         let items = items.iter().enumerate().filter_map(|(i, item)| {
-            let coerced = item.coerce_value_type(De::EXPECTING);
+            let coerced = item.coerce_value_type(De::EXPECTING, ctx.de_options);
             let mut child_ctx = ctx.child(&i.to_string(), ctx.location_in_config);
             let mut child_ctx = child_ctx.patched(coerced.as_ref().unwrap_or(item));
             match self.0.deserialize_param(child_ctx.borrow(), param) {
@@ -249,6 +250,10 @@ where
     }
 
     /// Converts this to a [`NamedEntries`] instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either of the provided names is empty, or if the names coincide.
     pub const fn named(
         self,
         keys_name: &'static str,
@@ -310,7 +315,7 @@ fn parse_key_or_value<T, De: DeserializeParam<T>>(
     de: &De,
     val: &WithOrigin,
 ) -> Option<T> {
-    let coerced = val.coerce_value_type(De::EXPECTING);
+    let coerced = val.coerce_value_type(De::EXPECTING, ctx.de_options);
     let mut child_ctx = ctx.child(key_path, ctx.location_in_config);
     let mut child_ctx = child_ctx.patched(coerced.as_ref().unwrap_or(val));
     match de.deserialize_param(child_ctx.borrow(), param) {
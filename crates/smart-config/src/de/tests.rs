@@ -2,25 +2,29 @@ use std::{
     collections::{HashMap, HashSet},
     net::{Ipv4Addr, Ipv6Addr},
     num::NonZeroUsize,
+    path::Path,
     time::Duration,
 };
 
 use assert_matches::assert_matches;
-use serde::Deserialize;
+use serde::{de::Error as _, Deserialize};
 
 use super::deserializer::ValueDeserializer;
 use crate::{
     config,
-    de::DeserializerOptions,
-    metadata::SizeUnit,
+    de::{self, DeserializerOptions},
+    metadata::{BasicTypes, ParamMetadata, SizeUnit},
+    testing,
     testonly::{
         extract_env_var_name, extract_json_name, test_config_roundtrip, test_deserialize,
         test_deserialize_missing, wrap_into_value, ComposedConfig, CompoundConfig,
-        ConfigWithComplexTypes, ConfigWithNesting, DefaultingConfig, DefaultingEnumConfig,
-        EnumConfig, MapOrString, NestedConfig, RenamedEnumConfig, SimpleEnum, TestParam,
+        ConfigWithComplexTypes, ConfigWithNesting, ConfigWithValidations, DefaultingConfig,
+        DefaultingEnumConfig, EnumConfig, MapOrString, NestedConfig, RenamedEnumConfig, SimpleEnum,
+        TestParam, TristateConfig,
     },
     value::{Pointer, Value, ValueOrigin},
-    ByteSize, DescribeConfig, Environment, ParseError,
+    ByteSize, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig, Environment,
+    ErrorKind, ErrorWithOrigin, Json, ParseError, ParseErrors,
 };
 
 #[test]
@@ -316,6 +320,367 @@ fn parsing_compound_config_with_multiple_errors() {
     );
 }
 
+#[test]
+fn deduplicating_errors() {
+    let json = config!("value": "what?", "nested.other_int": "??");
+    let errors = || test_deserialize::<ConfigWithNesting>(json.inner()).unwrap_err();
+    let mut all_errors = errors()
+        .into_iter()
+        .chain(errors())
+        .collect::<Result<(), ParseErrors>>()
+        .unwrap_err();
+    assert_eq!(all_errors.len(), 6);
+    all_errors.dedup();
+    assert_eq!(all_errors.len(), 3, "{all_errors:#?}");
+    let paths: Vec<_> = all_errors.iter().map(ParseError::path).collect();
+    let expected_errors = errors();
+    let expected_paths: Vec<_> = expected_errors.iter().map(ParseError::path).collect();
+    assert_eq!(paths, expected_paths);
+
+    // Errors with different origins must not be deduplicated.
+    let env = Environment::from_iter("", [("value", "what?")]);
+    let env = wrap_into_value(env);
+    let mut all_errors = errors()
+        .into_iter()
+        .chain(test_deserialize::<ConfigWithNesting>(&env).unwrap_err())
+        .collect::<Result<(), ParseErrors>>()
+        .unwrap_err();
+    let value_errors = all_errors.iter().filter(|err| err.path() == "value");
+    assert_eq!(value_errors.count(), 2);
+    all_errors.dedup();
+    let value_errors = all_errors.iter().filter(|err| err.path() == "value");
+    assert_eq!(value_errors.count(), 2, "{all_errors:#?}");
+}
+
+#[test]
+fn sorting_errors_by_path() {
+    let json = config!("value": "what?", "nested.other_int": "??", "nested.renamed": "???");
+    let mut errors = test_deserialize::<ConfigWithNesting>(json.inner()).unwrap_err();
+    errors.sort_by_path();
+    let paths: Vec<_> = errors.iter().map(ParseError::path).collect();
+    assert_eq!(paths, ["nested.other_int", "nested.renamed", "value"]);
+
+    // The sort must be stable.
+    let env = Environment::from_iter("", [("value", "what?")]);
+    let env = wrap_into_value(env);
+    let mut all_errors = test_deserialize::<ConfigWithNesting>(&env)
+        .unwrap_err()
+        .into_iter()
+        .chain(errors)
+        .collect::<Result<(), ParseErrors>>()
+        .unwrap_err();
+    all_errors.sort_by_path();
+    let paths: Vec<_> = all_errors.iter().map(ParseError::path).collect();
+    assert_eq!(
+        paths,
+        [
+            "nested.other_int",
+            "nested.renamed",
+            "nested.renamed",
+            "value",
+            "value"
+        ]
+    );
+    let value_origins: Vec<_> = all_errors
+        .iter()
+        .filter(|err| err.path() == "value")
+        .map(|err| err.origin().to_string())
+        .collect();
+    assert!(
+        value_origins[0].contains("env variable"),
+        "{value_origins:?}"
+    );
+    assert!(value_origins[1].contains("JSON"), "{value_origins:?}");
+}
+
+#[test]
+fn merging_errors() {
+    let json = config!("value": "what?");
+    let mut errors = ParseErrors::default();
+    assert!(!errors.has_errors());
+    errors.merge(test_deserialize::<ConfigWithNesting>(json.inner()).unwrap_err());
+    let json = config!("renamed": "??");
+    let nested_errors = test_deserialize::<NestedConfig>(json.inner()).unwrap_err();
+    errors.extend(nested_errors);
+
+    let paths: Vec<_> = errors.iter().map(ParseError::path).collect();
+    assert_eq!(paths, ["value", "nested.renamed", "renamed"]);
+    let configs: Vec<_> = errors.grouped_by_config().into_keys().collect();
+    assert_eq!(configs, ["ConfigWithNesting", "NestedConfig"]);
+}
+
+#[test]
+fn filtering_errors() {
+    let json = config!("value": "what?");
+    let errors = test_deserialize::<ConfigWithNesting>(json.inner()).unwrap_err();
+    let codes: Vec<_> = errors.iter().map(ParseError::code).collect();
+    assert_eq!(codes, ["invalid_value", "missing_field"]);
+
+    let errors = errors
+        .filter(|err| {
+            err.code() != "missing_field" || err.config().ty != NestedConfig::DESCRIPTION.ty
+        })
+        .unwrap_err();
+    let paths: Vec<_> = errors.iter().map(ParseError::path).collect();
+    assert_eq!(paths, ["value"]);
+
+    errors.filter(|_| false).unwrap();
+}
+
+#[test]
+fn coercing_numeric_strings() {
+    let json = config!("int": "42", "float": "0.5");
+    let config = test_deserialize::<DefaultingConfig>(json.inner()).unwrap();
+    assert_eq!(config.int, 42);
+    assert_eq!(config.float, Some(0.5));
+
+    let json = config!("int": "42!", "float": "what");
+    let errors = test_deserialize::<DefaultingConfig>(json.inner()).unwrap_err();
+    let paths: Vec<_> = errors.iter().map(ParseError::path).collect();
+    assert_eq!(paths, ["int", "float"]);
+    for err in errors.iter() {
+        assert_eq!(err.code(), "invalid_value", "{err}");
+    }
+}
+
+#[test]
+fn denying_numeric_strings() {
+    let mut tester = testing::Tester::<DefaultingConfig>::default();
+    tester.deny_numeric_strings();
+    let json = config!("int": "42", "float": 0.5);
+    let errors = tester.test(json).unwrap_err();
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    let err = errors.first();
+    assert_eq!(err.path(), "int");
+    assert_eq!(err.code(), "type_mismatch");
+    assert_eq!(err.kind(), ErrorKind::InvalidType);
+    assert_eq!(err.expected_types(), Some(BasicTypes::INTEGER));
+
+    let config = tester.test(config!("int": 42)).unwrap();
+    assert_eq!(config.int, 42);
+
+    // Env vars are still coerced.
+    let env = Environment::from_iter("", [("INT", "42"), ("FLOAT", "0.5")]);
+    let config = tester.test(env).unwrap();
+    assert_eq!(config.int, 42);
+    assert_eq!(config.float, Some(0.5));
+}
+
+#[test]
+fn coercing_bool_strings() {
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct TestConfig {
+        flag: bool,
+        opt: Option<bool>,
+    }
+
+    // Only `true` / `false` are accepted by default.
+    let json = config!("flag": "true", "opt": "on");
+    let errors = testing::test::<TestConfig>(json.clone()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors.first().path(), "opt");
+
+    let mut tester = testing::Tester::<TestConfig>::default();
+    tester.coerce_bool_strings();
+    let samples = [
+        ("TRUE", "Off"),
+        ("1", "0"),
+        ("yes", "no"),
+        ("On", "OFF"),
+        (" true ", "false"),
+    ];
+    for (truthy, falsy) in samples {
+        let json = config!("flag": truthy, "opt": falsy);
+        let config = tester.test(json).unwrap();
+        assert!(config.flag, "{truthy}");
+        assert_eq!(config.opt, Some(false), "{falsy}");
+    }
+    let config = tester.test(json).unwrap();
+    assert!(config.flag);
+    assert_eq!(config.opt, Some(true));
+
+    let json = config!("flag": "enabled");
+    let errors = tester.test(json).unwrap_err();
+    let err = errors.first();
+    assert_eq!(err.path(), "flag");
+    let message = err.inner().to_string();
+    assert!(
+        message.contains("cannot parse value 'enabled' as boolean"),
+        "{message}"
+    );
+    assert!(message.contains("`on` / `off`"), "{message}");
+}
+
+#[test]
+fn converting_errors_to_anyhow() {
+    let json = config!("value": "what?");
+    let errors = test_deserialize::<ConfigWithNesting>(json.inner()).unwrap_err();
+    let displayed = errors.to_string();
+    let err = errors.into_anyhow();
+    assert_eq!(err.to_string(), "2 errors parsing configs");
+    let errors = err.downcast::<ParseErrors>().unwrap();
+    assert_eq!(errors.to_string(), displayed);
+
+    let err = errors.into_iter().next().unwrap().into_anyhow();
+    let chain: Vec<_> = err.chain().map(ToString::to_string).collect();
+    assert_eq!(chain.len(), 4, "{chain:#?}");
+    assert_eq!(chain[0], "error parsing config `ConfigWithNesting`");
+    assert!(
+        chain[1].starts_with("param `value` at `value` [origin: "),
+        "{chain:#?}"
+    );
+    assert_eq!(chain[2], "expected integer, got string");
+    assert!(chain[3].contains("invalid digit"), "{chain:#?}");
+    assert!(err.root_cause().is::<serde_json::Error>());
+
+    let json = config!("value": 1);
+    let errors = test_deserialize::<ConfigWithNesting>(json.inner()).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    let err = errors.into_anyhow();
+    let chain: Vec<_> = err.chain().map(ToString::to_string).collect();
+    assert_eq!(chain[0], "error parsing config `NestedConfig`");
+    assert_eq!(chain[1], "param `renamed` at `nested.renamed`");
+    assert!(chain[2].contains("missing field"), "{chain:#?}");
+}
+
+#[test]
+fn grouping_errors_by_config() {
+    let json = config!("value": "what?", "nested.other_int": "??");
+    let errors = test_deserialize::<ConfigWithNesting>(json.inner()).unwrap_err();
+    assert_eq!(errors.len(), 3, "{errors:#?}");
+
+    let groups = errors.grouped_by_config();
+    let config_names: Vec<_> = groups.keys().copied().collect();
+    assert_eq!(config_names, ["ConfigWithNesting", "NestedConfig"]);
+    let paths: Vec<_> = groups["ConfigWithNesting"]
+        .iter()
+        .map(|err| err.path())
+        .collect();
+    assert_eq!(paths, ["value"]);
+    let paths: Vec<_> = groups["NestedConfig"]
+        .iter()
+        .map(|err| err.path())
+        .collect();
+    let expected_paths: Vec<_> = errors
+        .iter()
+        .filter(|err| err.config().ty == NestedConfig::DESCRIPTION.ty)
+        .map(ParseError::path)
+        .collect();
+    assert_eq!(paths, expected_paths);
+    assert_eq!(paths.len(), 2);
+}
+
+#[test]
+fn serializing_errors_to_json() {
+    let json = config!("other_int": "what?");
+    let errors = test_deserialize::<NestedConfig>(json.inner()).unwrap_err();
+    assert_eq!(errors.len(), 2, "{errors:#?}");
+
+    let serde_json::Value::Array(items) = errors.to_json() else {
+        panic!("unexpected JSON");
+    };
+    assert_eq!(items.len(), 2);
+    let missing_field = items
+        .iter()
+        .find(|item| item["category"] == "missing_field")
+        .unwrap();
+    assert_eq!(missing_field["path"], "renamed");
+    assert_eq!(missing_field["config"], "NestedConfig");
+    assert_eq!(missing_field["param"], "renamed");
+    assert_eq!(missing_field["code"], "missing_field");
+    assert!(
+        missing_field["message"]
+            .as_str()
+            .unwrap()
+            .contains("missing field"),
+        "{missing_field:#?}"
+    );
+
+    let generic = items
+        .iter()
+        .find(|item| item["category"] == "generic")
+        .unwrap();
+    assert_eq!(generic["path"], "other_int");
+    assert_eq!(generic["param"], "other_int");
+    assert!(
+        generic["origin"].as_str().unwrap().contains("other_int"),
+        "{generic:#?}"
+    );
+}
+
+#[test]
+fn displaying_errors_with_relative_paths() {
+    let object = serde_json::json!({ "other_int": "what?" });
+    let json = Json::new(
+        "/workspace/configs/app.json",
+        object.as_object().unwrap().clone(),
+    );
+    let errors = test_deserialize::<NestedConfig>(json.inner()).unwrap_err();
+    assert_eq!(errors.len(), 2, "{errors:#?}");
+
+    let absolute = errors.to_string();
+    assert!(
+        absolute.contains("JSON file '/workspace/configs/app.json' -> path 'other_int'"),
+        "{absolute}"
+    );
+    let relative = errors
+        .display_relative_to(Path::new("/workspace"))
+        .to_string();
+    assert!(
+        relative.contains("JSON file 'configs/app.json' -> path 'other_int'"),
+        "{relative}"
+    );
+    assert!(!relative.contains("/workspace"), "{relative}");
+    assert_eq!(relative.lines().count(), absolute.lines().count());
+
+    // Files outside the base dir are not affected.
+    let unrelated = errors.display_relative_to(Path::new("/other")).to_string();
+    assert_eq!(unrelated, absolute);
+}
+
+#[test]
+fn error_codes() {
+    let json = config!("other_int": "what?", "map": 42);
+    let errors = test_deserialize::<NestedConfig>(json.inner()).unwrap_err();
+    let codes: HashMap<_, _> = errors.iter().map(|err| (err.path(), err.code())).collect();
+    assert_eq!(
+        codes,
+        HashMap::from([
+            ("renamed", "missing_field"),
+            ("other_int", "invalid_value"),
+            ("map", "type_mismatch"),
+        ])
+    );
+
+    let json = config!("renamed": "fourth");
+    let errors = test_deserialize::<NestedConfig>(json.inner()).unwrap_err();
+    assert_eq!(errors.first().code(), "unknown_variant");
+
+    let json = config!("renamed": "first", "other_int": -1);
+    let errors = test_deserialize::<NestedConfig>(json.inner()).unwrap_err();
+    assert_eq!(errors.first().code(), "invalid_value");
+
+    let json = config!("len": 3, "secret": "test");
+    let errors = testing::test::<ConfigWithValidations>(json).unwrap_err();
+    assert_eq!(errors.first().code(), "validation_failed");
+
+    let mut err = ParseError::generic(String::new(), &NestedConfig::DESCRIPTION);
+    assert_eq!(err.code(), "custom");
+    // Codes must not depend on the error message
+    err.inner = serde_json::Error::custom("invalid type: whatever");
+    assert_eq!(err.code(), "custom");
+
+    let json = config!("array": [1, 2, 3]);
+    let errors = test_deserialize::<ConfigWithComplexTypes>(json.inner()).unwrap_err();
+    let err = errors.iter().find(|err| err.path() == "array").unwrap();
+    assert_eq!(err.code(), "invalid_array");
+    let json = config!("array": [1, 0]);
+    let errors = test_deserialize::<ConfigWithComplexTypes>(json.inner()).unwrap_err();
+    let err = errors.iter().find(|err| err.path() == "array.1").unwrap();
+    assert_eq!(err.code(), "invalid_value");
+}
+
 #[test]
 fn parsing_defaulting_config_from_missing_value() {
     let config: DefaultingConfig = test_deserialize_missing().unwrap();
@@ -336,6 +701,30 @@ fn parsing_defaulting_config_with_null_override() {
     );
 }
 
+#[test]
+fn parsing_tristate_params() {
+    let config: TristateConfig = test_deserialize_missing().unwrap();
+    assert_eq!(config, TristateConfig::default());
+    assert!(config.port.is_absent());
+
+    let json = config!("port": 8080, "timeout": ());
+    let config: TristateConfig = test_deserialize(json.inner()).unwrap();
+    assert_eq!(config.port, de::Maybe::Set(8080));
+    assert_eq!(config.timeout, de::Maybe::Null);
+
+    // Empty string should be coerced to `null` since `u16` doesn't expect string values
+    let env = Environment::from_iter("APP_", [("APP_PORT", ""), ("APP_TIMEOUT", "3s")]);
+    let config: TristateConfig = testing::test(env).unwrap();
+    assert_eq!(config.port, de::Maybe::Null);
+    assert_eq!(config.timeout, de::Maybe::Set(Duration::from_secs(3)));
+
+    let json = config!("port": "what?");
+    let err = test_deserialize::<TristateConfig>(json.inner()).unwrap_err();
+    assert_eq!(err.len(), 1);
+    let err = err.first();
+    assert_eq!(err.path(), "port");
+}
+
 #[test]
 fn parsing_defaulting_enum_config_from_missing_value() {
     let config: DefaultingEnumConfig = test_deserialize_missing().unwrap();
@@ -359,6 +748,22 @@ fn parsing_defaulting_enum_config_from_missing_tag() {
     assert!(inner.contains("unknown variant"), "{inner}");
 }
 
+#[test]
+fn parsing_defaulting_enum_config_in_repository() {
+    let schema = ConfigSchema::new(&DefaultingEnumConfig::DESCRIPTION, "test");
+    let repo = ConfigRepository::new(&schema).with(config!("test.int": 5));
+    let config: DefaultingEnumConfig = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config, DefaultingEnumConfig::Second { int: 5 });
+
+    // Without a default variant, a missing tag is an error.
+    let schema = ConfigSchema::new(&EnumConfig::DESCRIPTION, "test");
+    let repo = ConfigRepository::new(&schema).with(config!("test.flag": false));
+    let errors = repo.single::<EnumConfig>().unwrap().parse().unwrap_err();
+    let err = errors.first();
+    assert_eq!(err.code(), "missing_field");
+    assert_eq!(err.path(), "test.type");
+}
+
 #[test]
 fn type_mismatch_parsing_error() {
     let env = Environment::from_iter("", [("renamed", "first"), ("other_int", "what")]);
@@ -385,6 +790,25 @@ fn missing_parameter_parsing_error() {
     assert_eq!(err.param().unwrap().name, "renamed");
 }
 
+#[test]
+fn missing_parameter_error_with_suggestions() {
+    let json = config!("renamd": "first", "other_int": 12);
+    let errors = test_deserialize::<NestedConfig>(json.inner()).unwrap_err();
+
+    let err = errors.first();
+    assert_eq!(err.param().unwrap().name, "renamed");
+    assert_eq!(err.suggestions(), ["renamed"]);
+    let err = err.to_string();
+    assert!(err.ends_with("did you mean `renamed`?"), "{err}");
+
+    // Unrelated keys should not produce suggestions.
+    let json = config!("unrelated": "first", "other_int": 12);
+    let errors = test_deserialize::<NestedConfig>(json.inner()).unwrap_err();
+    let err = errors.first();
+    assert!(err.suggestions().is_empty(), "{err:?}");
+    assert!(!err.to_string().contains("did you mean"), "{err}");
+}
+
 #[test]
 fn missing_nested_config_parsing_error() {
     let json = config!("value": 123);
@@ -714,6 +1138,134 @@ fn parsing_complex_types_errors() {
     assert_matches!(err.origin(), ValueOrigin::Path { path, .. } if path == "assumed");
 }
 
+#[test]
+fn using_custom_deserializers() {
+    /// Deserializes integers and multiplies them by 10.
+    #[derive(Debug)]
+    struct Scaled;
+
+    impl de::DeserializeParam<u64> for Scaled {
+        const EXPECTING: BasicTypes = BasicTypes::INTEGER;
+
+        fn deserialize_param(
+            &self,
+            ctx: de::DeserializeContext<'_>,
+            param: &'static ParamMetadata,
+        ) -> Result<u64, ErrorWithOrigin> {
+            let de = ctx.current_value_deserializer(param.name)?;
+            Ok(u64::deserialize(de)? * 10)
+        }
+
+        fn serialize_param(&self, param: &u64) -> serde_json::Value {
+            (param / 10).into()
+        }
+    }
+
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct ConfigWithCustomTypes {
+        int: u64,
+        #[config(with = de::Serde![int])]
+        explicit: u64,
+        #[config(default_t = 5)]
+        defaulted: u64,
+        #[config(validate(..=100, "must not exceed 100"))]
+        validated: u64,
+        optional: Option<u64>,
+        other: u32,
+    }
+
+    let json = config!(
+        "int": 1,
+        "explicit": 2,
+        "validated": 3,
+        "optional": 4,
+        "other": "5",
+    );
+    let schema = ConfigSchema::new(&ConfigWithCustomTypes::DESCRIPTION, "");
+    let mut repo = ConfigRepository::new(&schema).with(json);
+    repo.deserializer_options()
+        .custom_deserializers
+        .insert::<u64, _>(Scaled);
+    let config: ConfigWithCustomTypes = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.int, 10);
+    assert_eq!(config.explicit, 2);
+    assert_eq!(config.defaulted, 5);
+    assert_eq!(config.validated, 30);
+    assert_eq!(config.optional, Some(4));
+    assert_eq!(config.other, 5);
+
+    let json = config!("int": 1, "explicit": 2, "validated": 11, "other": 5);
+    let mut repo = ConfigRepository::new(&schema).with(json);
+    repo.deserializer_options()
+        .custom_deserializers
+        .insert::<u64, _>(Scaled);
+    let err = repo
+        .single::<ConfigWithCustomTypes>()
+        .unwrap()
+        .parse()
+        .unwrap_err();
+    assert_eq!(err.first().path(), "validated");
+    assert_eq!(err.first().validation(), Some("must not exceed 100"));
+
+    assert!(repo
+        .deserializer_options()
+        .custom_deserializers
+        .remove::<u64>());
+    let config: ConfigWithCustomTypes = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.validated, 11);
+}
+
+#[test]
+fn type_mismatch_errors_mention_expected_types() {
+    let json = config!("array": [2, 3], "assumed": true);
+    let errors = test_deserialize::<ConfigWithComplexTypes>(json.inner()).unwrap_err();
+    let err = errors.first();
+    assert_eq!(err.code(), "type_mismatch");
+    assert_eq!(
+        err.expected_types(),
+        Some(BasicTypes::INTEGER.or(BasicTypes::FLOAT))
+    );
+    let err = err.to_string();
+    assert!(
+        err.contains("(expected integer | float, got Boolean)"),
+        "{err}"
+    );
+
+    // Unparsable strings are invalid values, but the expected type is still reported.
+    let json = config!("int": "what");
+    let errors = test_deserialize::<DefaultingConfig>(json.inner()).unwrap_err();
+    let err = errors.first();
+    assert_eq!(err.code(), "invalid_value");
+    assert_eq!(err.expected_types(), Some(BasicTypes::INTEGER));
+    let err = err.to_string();
+    assert!(err.contains("(expected integer, got string)"), "{err}");
+
+    // A numeric string that is out of range is an invalid value rather than a type mismatch.
+    let json = config!("int": "-1");
+    let errors = test_deserialize::<DefaultingConfig>(json.inner()).unwrap_err();
+    let err = errors.first();
+    assert_eq!(err.code(), "invalid_value");
+    assert_eq!(err.expected_types(), None);
+    let err = err.to_string();
+    assert!(!err.contains("(expected"), "{err}");
+
+    let json = config!("int": [1]);
+    let errors = test_deserialize::<DefaultingConfig>(json.inner()).unwrap_err();
+    let err = errors.first();
+    assert_eq!(err.code(), "type_mismatch");
+    assert_eq!(err.expected_types(), Some(BasicTypes::INTEGER));
+    let err = err.to_string();
+    assert!(err.contains("(expected integer, got array)"), "{err}");
+
+    // Errors for array items should not be enriched.
+    let json = config!("array": "-4,what");
+    let errors = test_deserialize::<ConfigWithComplexTypes>(json.inner()).unwrap_err();
+    for err in errors.iter() {
+        assert_eq!(err.expected_types(), None, "{err}");
+    }
+}
+
 #[test]
 fn error_from_custom_deserializer() {
     let json = config!("array": [2, 3], "with_custom_deserializer": "very long string");
@@ -767,3 +1319,55 @@ fn multiple_errors_for_composed_deserializers() {
         err.path() == "map_of_ints.what" && err.inner().to_string().starts_with("invalid type")
     }));
 }
+
+#[derive(Debug, DescribeConfig, DeserializeConfig)]
+#[config(crate = crate, post_parse = Self::normalize)]
+#[config(validate(Self::validate_host, "host must not be empty"))]
+struct ConfigWithPostParse {
+    host: String,
+    #[config(default)]
+    path: String,
+}
+
+impl ConfigWithPostParse {
+    fn validate_host(&self) -> bool {
+        !self.host.is_empty()
+    }
+
+    fn normalize(&mut self) -> Result<(), ErrorWithOrigin> {
+        if self.host.contains(' ') {
+            return Err(ErrorWithOrigin::custom("host contains spaces"));
+        }
+        self.host.make_ascii_lowercase();
+        if let Some(rest) = self.path.strip_prefix('~') {
+            self.path = format!("/home/user{rest}");
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn post_parse_transform() {
+    let json = config!("host": "LocalHost", "path": "~/data");
+    let config: ConfigWithPostParse = testing::test(json).unwrap();
+    assert_eq!(config.host, "localhost");
+    assert_eq!(config.path, "/home/user/data");
+
+    let json = config!("host": "local host");
+    let errors = testing::test::<ConfigWithPostParse>(json).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    let err = errors.first();
+    assert_eq!(err.validation(), Some("post-parse transform"));
+    assert!(err.param().is_none());
+    assert_eq!(err.code(), "validation_failed");
+    assert!(
+        err.inner().to_string().contains("host contains spaces"),
+        "{err}"
+    );
+
+    // Transform doesn't run if validations fail
+    let json = config!("host": "");
+    let errors = testing::test::<ConfigWithPostParse>(json).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors.first().validation(), Some("host must not be empty"));
+}
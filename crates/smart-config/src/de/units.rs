@@ -9,7 +9,7 @@ use serde::{
 
 use crate::{
     de::{CustomKnownOption, DeserializeContext, DeserializeParam, Optional, WellKnown},
-    error::ErrorWithOrigin,
+    error::{ErrorKind, ErrorWithOrigin, LowLevelError},
     metadata::{BasicTypes, ParamMetadata, SizeUnit, TimeUnit, TypeDescription, TypeSuffixes},
     value::Value,
     ByteSize,
@@ -81,6 +81,17 @@ impl DeserializeParam<Duration> for TimeUnit {
     }
 }
 
+impl SizeUnit {
+    fn into_byte_size(self, raw_value: u64) -> Result<ByteSize, serde_json::Error> {
+        ByteSize::checked(raw_value, self).ok_or_else(|| {
+            DeError::custom(format!(
+                "{raw_value} {unit} does not fit into `u64`",
+                unit = self.plural()
+            ))
+        })
+    }
+}
+
 /// Supports deserializing a [`ByteSize`] from a number, with `self` being the unit of measurement.
 ///
 /// # Examples
@@ -117,13 +128,8 @@ impl DeserializeParam<ByteSize> for SizeUnit {
     ) -> Result<ByteSize, ErrorWithOrigin> {
         let deserializer = ctx.current_value_deserializer(param.name)?;
         let raw_value = u64::deserialize(deserializer)?;
-        ByteSize::checked(raw_value, *self).ok_or_else(|| {
-            let err = DeError::custom(format!(
-                "{raw_value} {unit} does not fit into `u64`",
-                unit = self.plural()
-            ));
-            deserializer.enrich_err(err)
-        })
+        self.into_byte_size(raw_value)
+            .map_err(|err| deserializer.enrich_err(err))
     }
 
     fn serialize_param(&self, param: &ByteSize) -> serde_json::Value {
@@ -148,6 +154,10 @@ impl DeserializeParam<ByteSize> for SizeUnit {
 /// from a param name suffixed with a unit. For example, a value `latency_ms: 500` for parameter `latency`
 /// will be recognized as 500 ms.
 ///
+/// Byte size units are case-insensitive and correspond to [`SizeUnit`] variants, which are binary. That is,
+/// `kb`, `mb` and `gb` are treated as aliases of `KiB`, `MiB` and `GiB` respectively, rather than as SI (decimal) units.
+/// Use [`SiOrBinarySize`] to distinguish between SI and binary units.
+///
 /// # Examples
 ///
 /// ```
@@ -198,7 +208,7 @@ impl WithUnit {
         let raw = if let Value::String(s) = deserializer.value() {
             s.expose()
                 .parse::<Raw>()
-                .map_err(|err| deserializer.enrich_err(err))?
+                .map_err(|err| err.set_origin_if_unset(deserializer.origin()))?
         } else {
             deserializer.deserialize_enum("Raw", Raw::VARIANTS, EnumVisitor(PhantomData::<Raw>))?
         };
@@ -219,7 +229,7 @@ impl WithUnit {
             Some(
                 s.expose()
                     .parse::<Raw>()
-                    .map_err(|err| deserializer.enrich_err(err))?,
+                    .map_err(|err| err.set_origin_if_unset(deserializer.origin()))?,
             )
         } else {
             deserializer.deserialize_enum(
@@ -238,8 +248,10 @@ impl WithUnit {
 }
 
 /// Helper trait allowing to unify enum parsing for durations and byte sizes.
-trait EnumWithUnit: FromStr<Err = serde_json::Error> {
+trait EnumWithUnit: FromStr<Err = ErrorWithOrigin> {
     const VARIANTS: &'static [&'static str];
+    /// Human-readable list of supported units used in error messages.
+    const SUPPORTED_UNITS: &'static str;
 
     fn extract_variant(unit: &str) -> Option<fn(u64) -> Self>;
 
@@ -316,6 +328,9 @@ macro_rules! impl_enum_with_unit {
 }
 
 impl EnumWithUnit for RawDuration {
+    const SUPPORTED_UNITS: &'static str =
+        "ms, s, min, h, d, w (or their full names, e.g. `seconds`)";
+
     impl_enum_with_unit!(
         "milliseconds" | "millis" | "ms" => Self::Millis,
         "seconds" | "second" | "secs" | "sec" | "s" => Self::Seconds,
@@ -331,7 +346,7 @@ impl RawDuration {
 }
 
 impl FromStr for RawDuration {
-    type Err = serde_json::Error;
+    type Err = ErrorWithOrigin;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let unit_start = s
@@ -428,6 +443,119 @@ impl DeserializeParam<Option<Duration>> for WithUnit {
     }
 }
 
+/// Deserializer extending [`WithUnit`] so that it accepts bare integers in the wrapped default unit of measurement.
+/// Integers can be supplied either as numbers, or as strings consisting only of decimal digits (e.g., from env vars).
+///
+/// Unknown units are reported with a custom error listing the supported units.
+/// Serialization is performed in the same way as for [`WithUnit`], i.e. in the string + unit form.
+/// `WithDefaultUnit` can be used both for [`Duration`]s (with a [`TimeUnit`]) and for [`ByteSize`]s (with a [`SizeUnit`]).
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// # use smart_config::{de::WithDefaultUnit, metadata::TimeUnit, testing, DescribeConfig, DeserializeConfig};
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(with = WithDefaultUnit(TimeUnit::Seconds))]
+///     timeout: Duration,
+/// }
+///
+/// let source = smart_config::config!("timeout": "5min");
+/// let config: TestConfig = testing::test(source)?;
+/// assert_eq!(config.timeout, Duration::from_secs(300));
+///
+/// // Bare integers are interpreted as seconds.
+/// let source = smart_config::config!("timeout": 30);
+/// let config: TestConfig = testing::test(source)?;
+/// assert_eq!(config.timeout, Duration::from_secs(30));
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WithDefaultUnit<U>(pub U);
+
+impl<U> WithDefaultUnit<U> {
+    const EXPECTED_TYPES: BasicTypes = BasicTypes::INTEGER.or(WithUnit::EXPECTED_TYPES);
+
+    fn deserialize<Raw, T>(
+        ctx: &DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+        from_int: impl FnOnce(u64) -> Result<T, serde_json::Error>,
+    ) -> Result<T, ErrorWithOrigin>
+    where
+        Raw: EnumWithUnit + TryInto<T, Error = serde_json::Error>,
+    {
+        let deserializer = ctx.current_value_deserializer(param.name)?;
+        let raw_int = match deserializer.value() {
+            Value::Number(_) => Some(u64::deserialize(deserializer)?),
+            Value::String(s)
+                if !s.expose().is_empty() && s.expose().bytes().all(|ch| ch.is_ascii_digit()) =>
+            {
+                let int = s
+                    .expose()
+                    .parse::<u64>()
+                    .map_err(|err| deserializer.enrich_err(DeError::custom(err)))?;
+                Some(int)
+            }
+            _ => None,
+        };
+
+        if let Some(raw_int) = raw_int {
+            from_int(raw_int).map_err(|err| deserializer.enrich_err(err))
+        } else {
+            WithUnit::deserialize::<Raw, T>(ctx, param).map_err(|err| {
+                let is_unknown_unit = matches!(
+                    &err.inner,
+                    LowLevelError::Json {
+                        kind: ErrorKind::UnknownVariant,
+                        ..
+                    }
+                );
+                if !is_unknown_unit {
+                    return err;
+                }
+
+                let unit = match deserializer.value() {
+                    Value::String(s) => s
+                        .expose()
+                        .trim_start_matches(|ch: char| ch.is_ascii_digit())
+                        .trim(),
+                    Value::Object(map) => map.keys().next().map_or("", String::as_str),
+                    _ => "",
+                };
+                let message = format!(
+                    "unknown unit of measurement `{unit}`, supported units are: {}",
+                    Raw::SUPPORTED_UNITS
+                );
+                ErrorWithOrigin::custom(message).set_origin_if_unset(&err.origin)
+            })
+        }
+    }
+}
+
+impl DeserializeParam<Duration> for WithDefaultUnit<TimeUnit> {
+    const EXPECTING: BasicTypes = Self::EXPECTED_TYPES;
+
+    fn describe(&self, description: &mut TypeDescription) {
+        description
+            .set_details("duration with unit, or object with single unit key, or integer")
+            .set_unit(self.0.into())
+            .set_suffixes(TypeSuffixes::DurationUnits);
+    }
+
+    fn deserialize_param(
+        &self,
+        ctx: DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+    ) -> Result<Duration, ErrorWithOrigin> {
+        Self::deserialize::<RawDuration, _>(&ctx, param, |raw| self.0.into_duration(raw))
+    }
+
+    fn serialize_param(&self, param: &Duration) -> serde_json::Value {
+        WithUnit.serialize_param(param)
+    }
+}
+
 impl WellKnown for Duration {
     type Deserializer = WithUnit;
     const DE: Self::Deserializer = WithUnit;
@@ -448,6 +576,9 @@ enum RawByteSize {
 }
 
 impl EnumWithUnit for RawByteSize {
+    const SUPPORTED_UNITS: &'static str =
+        "B, KiB, MiB, GiB (case-insensitive, or their full names, e.g. `megabytes`)";
+
     impl_enum_with_unit!(
         "bytes" | "b" => Self::Bytes,
         "kilobytes" | "kb" | "kib" => Self::Kilobytes,
@@ -471,7 +602,7 @@ impl<'de> Deserialize<'de> for RawByteSize {
 }
 
 impl FromStr for RawByteSize {
-    type Err = serde_json::Error;
+    type Err = ErrorWithOrigin;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let unit_start = s
@@ -497,12 +628,7 @@ impl TryFrom<RawByteSize> for ByteSize {
             RawByteSize::Megabytes(val) => (SizeUnit::MiB, val),
             RawByteSize::Gigabytes(val) => (SizeUnit::GiB, val),
         };
-        ByteSize::checked(raw_value, unit).ok_or_else(|| {
-            DeError::custom(format!(
-                "{raw_value} {unit} does not fit into `u64`",
-                unit = unit.plural()
-            ))
-        })
+        unit.into_byte_size(raw_value)
     }
 }
 
@@ -550,6 +676,173 @@ impl DeserializeParam<Option<ByteSize>> for WithUnit {
     }
 }
 
+impl DeserializeParam<ByteSize> for WithDefaultUnit<SizeUnit> {
+    const EXPECTING: BasicTypes = Self::EXPECTED_TYPES;
+
+    fn describe(&self, description: &mut TypeDescription) {
+        description
+            .set_details("size with unit, or object with single unit key, or integer")
+            .set_unit(self.0.into())
+            .set_suffixes(TypeSuffixes::SizeUnits);
+    }
+
+    fn deserialize_param(
+        &self,
+        ctx: DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+    ) -> Result<ByteSize, ErrorWithOrigin> {
+        Self::deserialize::<RawByteSize, _>(&ctx, param, |raw| self.0.into_byte_size(raw))
+    }
+
+    fn serialize_param(&self, param: &ByteSize) -> serde_json::Value {
+        WithUnit.serialize_param(param)
+    }
+}
+
+/// Byte size unit recognized by [`SiOrBinarySize`], together with the number of bytes in it.
+const SI_OR_BINARY_SIZE_UNITS: &[(&str, u64)] = &[
+    ("B", 1),
+    ("kB", 1_000),
+    ("MB", 1_000_000),
+    ("GB", 1_000_000_000),
+    ("KiB", 1 << 10),
+    ("MiB", 1 << 20),
+    ("GiB", 1 << 30),
+];
+
+/// Deserializer for byte sizes distinguishing between SI (decimal) and binary units of measurement.
+///
+/// Values can be deserialized from:
+///
+/// - Integers, which are interpreted as bytes. Integers can be supplied either as numbers, or as strings consisting
+///   only of decimal digits (e.g., from env vars).
+/// - Strings consisting of an integer, optional whitespace and a unit, such as "16MiB" or "1 GB". Units
+///   are case-sensitive: `kB`, `MB` and `GB` are SI units (i.e., 10<sup>3</sup>, 10<sup>6</sup> and 10<sup>9</sup> bytes),
+///   and `KiB`, `MiB` and `GiB` are binary units (i.e., 2<sup>10</sup>, 2<sup>20</sup> and 2<sup>30</sup> bytes).
+///   `B` means bytes.
+///
+/// Units which differ from the supported ones only by case (e.g., `KB` or `mb`) are ambiguous and are rejected
+/// with a descriptive error, as are unknown units.
+///
+/// Unlike [`WithUnit`], this deserializer doesn't support object inputs like `{ "mb": 4 }`, and thus suffixed
+/// param names. It can be used both for `u64` and [`ByteSize`] params; values are serialized as integers.
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::{de::SiOrBinarySize, testing, DescribeConfig, DeserializeConfig};
+/// #[derive(Debug, DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(with = SiOrBinarySize)]
+///     cache_size: u64,
+/// }
+///
+/// let source = smart_config::config!("cache_size": "1 GB");
+/// let config: TestConfig = testing::test(source)?;
+/// assert_eq!(config.cache_size, 1_000_000_000);
+///
+/// let source = smart_config::config!("cache_size": "16MiB");
+/// let config: TestConfig = testing::test(source)?;
+/// assert_eq!(config.cache_size, 16 << 20);
+///
+/// let source = smart_config::config!("cache_size": "1 gb");
+/// let err = testing::test::<TestConfig>(source).unwrap_err();
+/// let err = err.first().inner().to_string();
+/// assert!(err.contains("ambiguous size unit"), "{err}");
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SiOrBinarySize;
+
+impl SiOrBinarySize {
+    fn parse(s: &str) -> Result<u64, ErrorWithOrigin> {
+        const EXPECTING: &str =
+            "integer optionally followed by a size unit, like '32 MB' or '4KiB'";
+
+        let s = s.trim();
+        let unit_start = s.find(|ch: char| !ch.is_ascii_digit()).unwrap_or(s.len());
+        if unit_start == 0 {
+            return Err(DeError::invalid_value(Unexpected::Str(s), &EXPECTING));
+        }
+        let value: u64 = s[..unit_start].parse().map_err(DeError::custom)?;
+        let unit = s[unit_start..].trim_start();
+        if unit.is_empty() {
+            return Ok(value);
+        }
+
+        let bytes_in_unit = SI_OR_BINARY_SIZE_UNITS
+            .iter()
+            .find_map(|&(name, bytes)| (name == unit).then_some(bytes));
+        let Some(bytes_in_unit) = bytes_in_unit else {
+            let is_ambiguous = SI_OR_BINARY_SIZE_UNITS
+                .iter()
+                .any(|(name, _)| name.eq_ignore_ascii_case(unit));
+            return Err(if is_ambiguous {
+                DeError::custom(format!(
+                    "ambiguous size unit `{unit}`; use `kB` / `MB` / `GB` for SI (decimal) units \
+                     or `KiB` / `MiB` / `GiB` for binary units"
+                ))
+            } else {
+                DeError::custom(format!(
+                    "unknown size unit `{unit}`; expected one of `B`, `kB`, `MB`, `GB`, `KiB`, `MiB`, `GiB`"
+                ))
+            });
+        };
+        value
+            .checked_mul(bytes_in_unit)
+            .ok_or_else(|| DeError::custom(format!("{s} does not fit into `u64`")))
+    }
+}
+
+impl DeserializeParam<u64> for SiOrBinarySize {
+    const EXPECTING: BasicTypes = BasicTypes::INTEGER.or(BasicTypes::STRING);
+
+    fn describe(&self, description: &mut TypeDescription) {
+        description
+            .set_details(
+                "size in bytes, or integer with SI (kB, MB, GB) or binary (KiB, MiB, GiB) unit",
+            )
+            .set_unit(SizeUnit::Bytes.into());
+    }
+
+    fn deserialize_param(
+        &self,
+        ctx: DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+    ) -> Result<u64, ErrorWithOrigin> {
+        let deserializer = ctx.current_value_deserializer(param.name)?;
+        if let Value::Number(_) = deserializer.value() {
+            return u64::deserialize(deserializer);
+        }
+        let s = String::deserialize(deserializer)?;
+        Self::parse(&s).map_err(|err| err.set_origin_if_unset(deserializer.origin()))
+    }
+
+    fn serialize_param(&self, param: &u64) -> serde_json::Value {
+        (*param).into()
+    }
+}
+
+impl DeserializeParam<ByteSize> for SiOrBinarySize {
+    const EXPECTING: BasicTypes = <Self as DeserializeParam<u64>>::EXPECTING;
+
+    fn describe(&self, description: &mut TypeDescription) {
+        <Self as DeserializeParam<u64>>::describe(self, description);
+    }
+
+    fn deserialize_param(
+        &self,
+        ctx: DeserializeContext<'_>,
+        param: &'static ParamMetadata,
+    ) -> Result<ByteSize, ErrorWithOrigin> {
+        <Self as DeserializeParam<u64>>::deserialize_param(self, ctx, param).map(ByteSize)
+    }
+
+    fn serialize_param(&self, param: &ByteSize) -> serde_json::Value {
+        param.0.into()
+    }
+}
+
 impl WellKnown for ByteSize {
     type Deserializer = WithUnit;
     const DE: Self::Deserializer = WithUnit;
@@ -578,7 +871,10 @@ impl TypeSuffixes {
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
+
     use super::*;
+    use crate::{config, testing, value::ValueOrigin, DescribeConfig, DeserializeConfig};
 
     #[test]
     fn parsing_time_string() {
@@ -608,13 +904,17 @@ mod tests {
 
     #[test]
     fn parsing_time_string_errors() {
-        let err = "".parse::<RawDuration>().unwrap_err().to_string();
+        let err = "".parse::<RawDuration>().unwrap_err().inner.to_string();
         assert!(err.starts_with("invalid type"), "{err}");
-        let err = "???".parse::<RawDuration>().unwrap_err().to_string();
+        let err = "???".parse::<RawDuration>().unwrap_err().inner.to_string();
         assert!(err.starts_with("invalid type"), "{err}");
-        let err = "10".parse::<RawDuration>().unwrap_err().to_string();
+        let err = "10".parse::<RawDuration>().unwrap_err().inner.to_string();
         assert!(err.starts_with("invalid type"), "{err}");
-        let err = "hours".parse::<RawDuration>().unwrap_err().to_string();
+        let err = "hours"
+            .parse::<RawDuration>()
+            .unwrap_err()
+            .inner
+            .to_string();
         assert!(err.starts_with("invalid type"), "{err}");
 
         let err = "111111111111111111111111111111111111111111s"
@@ -623,7 +923,11 @@ mod tests {
             .to_string();
         assert!(err.contains("too large"), "{err}");
 
-        let err = "10 months".parse::<RawDuration>().unwrap_err().to_string();
+        let err = "10 months"
+            .parse::<RawDuration>()
+            .unwrap_err()
+            .inner
+            .to_string();
         assert!(err.starts_with("unknown variant"), "{err}");
     }
 
@@ -687,6 +991,187 @@ mod tests {
         assert_eq!(val, "1d");
     }
 
+    #[test]
+    fn deserializing_duration_with_default_unit() {
+        #[derive(Debug, DescribeConfig, DeserializeConfig)]
+        #[config(crate = crate)]
+        struct TestConfig {
+            #[config(with = WithDefaultUnit(TimeUnit::Seconds))]
+            timeout: Duration,
+        }
+
+        let samples = [
+            (serde_json::json!(30), Duration::from_secs(30)),
+            (serde_json::json!("30"), Duration::from_secs(30)),
+            (serde_json::json!("1h"), Duration::from_secs(3_600)),
+            (serde_json::json!("500 ms"), Duration::from_millis(500)),
+            (serde_json::json!({ "min": 5 }), Duration::from_secs(300)),
+        ];
+        for (sample, expected) in samples {
+            let json = config!("timeout": sample.clone());
+            let config: TestConfig = testing::test(json).unwrap();
+            assert_eq!(config.timeout, expected, "{sample}");
+
+            let serialized = WithDefaultUnit(TimeUnit::Seconds).serialize_param(&config.timeout);
+            let json = config!("timeout": serialized);
+            let config: TestConfig = testing::test(json).unwrap();
+            assert_eq!(config.timeout, expected, "{sample}");
+        }
+
+        let json = config!("timeout": "10 months");
+        let err = testing::test::<TestConfig>(json).unwrap_err();
+        let err = err.first();
+        assert_eq!(err.path(), "timeout");
+        let inner = err.inner().to_string();
+        assert!(
+            inner.starts_with("unknown unit of measurement `months`"),
+            "{inner}"
+        );
+        assert!(inner.contains("ms, s, min, h, d, w"), "{inner}");
+        assert_matches!(err.origin(), ValueOrigin::Path { path, .. } if path == "timeout");
+
+        let json = config!("timeout": serde_json::json!({ "months": 10 }));
+        let err = testing::test::<TestConfig>(json).unwrap_err();
+        let inner = err.first().inner().to_string();
+        assert!(
+            inner.starts_with("unknown unit of measurement `months`"),
+            "{inner}"
+        );
+
+        let json = config!("timeout": -5);
+        let err = testing::test::<TestConfig>(json).unwrap_err();
+        let inner = err.first().inner().to_string();
+        assert!(inner.contains("invalid value"), "{inner}");
+    }
+
+    #[test]
+    fn deserializing_byte_size_with_default_unit() {
+        #[derive(Debug, DescribeConfig, DeserializeConfig)]
+        #[config(crate = crate)]
+        struct TestConfig {
+            #[config(with = WithDefaultUnit(SizeUnit::Bytes))]
+            cache_size: ByteSize,
+        }
+
+        let samples = [
+            (serde_json::json!(4_096), ByteSize(4_096)),
+            (serde_json::json!("4096"), ByteSize(4_096)),
+            (serde_json::json!("16MiB"), ByteSize(16 << 20)),
+            (serde_json::json!("1 GiB"), ByteSize(1 << 30)),
+            (serde_json::json!({ "kib": 2 }), ByteSize(2 << 10)),
+        ];
+        for (sample, expected) in samples {
+            let json = config!("cache_size": sample.clone());
+            let config: TestConfig = testing::test(json).unwrap();
+            assert_eq!(config.cache_size, expected, "{sample}");
+        }
+
+        let json = config!("cache_size": "16 TiB");
+        let err = testing::test::<TestConfig>(json).unwrap_err();
+        let inner = err.first().inner().to_string();
+        assert!(
+            inner.starts_with("unknown unit of measurement `TiB`"),
+            "{inner}"
+        );
+        assert!(inner.contains("B, KiB, MiB, GiB"), "{inner}");
+
+        let json = config!("cache_size": "MiB");
+        let err = testing::test::<TestConfig>(json).unwrap_err();
+        let inner = err.first().inner().to_string();
+        assert!(inner.contains("value with unit"), "{inner}");
+    }
+
+    #[test]
+    fn parsing_si_or_binary_size() {
+        let samples = [
+            ("0", 0),
+            ("4096", 4_096),
+            ("16 B", 16),
+            ("3kB", 3_000),
+            ("1 MB", 1_000_000),
+            ("2 GB", 2_000_000_000),
+            ("3KiB", 3 << 10),
+            ("  16   MiB ", 16 << 20),
+            ("2GiB", 2 << 30),
+        ];
+        for (sample, expected) in samples {
+            assert_eq!(SiOrBinarySize::parse(sample).unwrap(), expected, "{sample}");
+        }
+
+        for ambiguous in ["1 KB", "1 kb", "16mb", "2 gib", "1 b"] {
+            let err = SiOrBinarySize::parse(ambiguous)
+                .unwrap_err()
+                .inner
+                .to_string();
+            assert!(err.contains("ambiguous size unit"), "{err}");
+        }
+        for unknown in ["1 TiB", "5 bytes", "1 MBs"] {
+            let err = SiOrBinarySize::parse(unknown)
+                .unwrap_err()
+                .inner
+                .to_string();
+            assert!(err.starts_with("unknown size unit"), "{err}");
+        }
+
+        let err = SiOrBinarySize::parse("MiB").unwrap_err().inner.to_string();
+        assert!(err.contains("integer optionally followed"), "{err}");
+        let err = SiOrBinarySize::parse("100000000000 GiB")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("does not fit"), "{err}");
+    }
+
+    #[test]
+    fn deserializing_si_or_binary_size() {
+        #[derive(Debug, DescribeConfig, DeserializeConfig)]
+        #[config(crate = crate)]
+        struct TestConfig {
+            #[config(with = SiOrBinarySize)]
+            cache_size: u64,
+            #[config(with = SiOrBinarySize, default_t = ByteSize(1_000))]
+            buffer_size: ByteSize,
+        }
+
+        let buffer_size = TestConfig::DESCRIPTION
+            .params
+            .iter()
+            .find(|param| param.name == "buffer_size")
+            .unwrap();
+        assert_eq!(
+            buffer_size.expecting,
+            BasicTypes::INTEGER.or(BasicTypes::STRING)
+        );
+
+        let json = config!("cache_size": 4_096, "buffer_size": "1 GB");
+        let config: TestConfig = testing::test(json).unwrap();
+        assert_eq!(config.cache_size, 4_096);
+        assert_eq!(config.buffer_size, ByteSize(1_000_000_000));
+
+        let json = config!("cache_size": "4096");
+        let config: TestConfig = testing::test(json).unwrap();
+        assert_eq!(config.cache_size, 4_096);
+        assert_eq!(config.buffer_size, ByteSize(1_000));
+
+        let json = config!("cache_size": "16 MB");
+        let config: TestConfig = testing::test(json).unwrap();
+        assert_eq!(config.cache_size, 16_000_000);
+
+        let json = config!("cache_size": "16 Mb");
+        let err = testing::test::<TestConfig>(json).unwrap_err();
+        let err = err.first();
+        assert_eq!(err.path(), "cache_size");
+        let inner = err.inner().to_string();
+        assert!(inner.contains("ambiguous size unit `Mb`"), "{inner}");
+
+        let json = config!("cache_size": true);
+        let err = testing::test::<TestConfig>(json).unwrap_err();
+        let inner = err.first().inner().to_string();
+        assert!(
+            inner.contains("expected integer optionally followed"),
+            "{inner}"
+        );
+    }
+
     #[test]
     fn serializing_with_byte_size() {
         let val = WithUnit.serialize_param(&ByteSize(0));
@@ -1,11 +1,16 @@
 //! Config deserialization errors.
 
-use std::{fmt, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt,
+    path::Path,
+    sync::Arc,
+};
 
 use serde::{de, de::Error};
 
 use crate::{
-    metadata::{ConfigMetadata, ParamMetadata},
+    metadata::{BasicTypes, ConfigMetadata, ParamMetadata},
     value::{ValueOrigin, WithOrigin},
 };
 
@@ -20,7 +25,7 @@ impl DeserializeConfigError {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub(crate) enum LocationInConfig {
     Param(usize),
 }
@@ -33,6 +38,56 @@ pub enum ParseErrorCategory {
     Generic,
     /// Missing field (parameter / config) error.
     MissingField,
+    /// Unknown key in a source.
+    UnknownKey,
+}
+
+impl ParseErrorCategory {
+    /// Returns a stable `snake_case` identifier of this category.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Generic => "generic",
+            Self::MissingField => "missing_field",
+            Self::UnknownKey => "unknown_key",
+        }
+    }
+}
+
+/// Kind of a JSON deserialization error recorded when the error is created (e.g., via a [`de::Error`] constructor
+/// like [`invalid_type()`](de::Error::invalid_type())). Used to determine the [error code](ParseError::code()).
+///
+/// The kind is available via [`ParseError::kind()`]. More kinds may be added in the future, so consumers
+/// should treat unknown kinds as [`Self::Custom`], or use the string [error code](ParseError::code()) instead.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Custom error, e.g. one produced by [`de::Error::custom()`].
+    #[default]
+    Custom,
+    /// Value has an unexpected type.
+    InvalidType,
+    /// Value has the expected type, but is invalid.
+    InvalidValue,
+    /// Array (or another collection) has an unexpected number of items.
+    InvalidLength,
+    /// Unknown enum variant.
+    UnknownVariant,
+    /// Missing field.
+    MissingField,
+    /// Unknown variant key in an [externally tagged](crate::ConfigSchema::accept_external_tag()) enum config.
+    UnknownTag,
+}
+
+/// Severity of a [`ParseError`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Severity {
+    /// Error preventing the config from being parsed.
+    #[default]
+    Error,
+    /// Warning that doesn't prevent the config from being parsed; e.g., a failed
+    /// [validation](crate::validation::Validate::severity()) which is explicitly marked as tolerable.
+    Warning,
 }
 
 /// Low-level deserialization error.
@@ -43,6 +98,10 @@ pub enum LowLevelError {
     Json {
         err: serde_json::Error,
         category: ParseErrorCategory,
+        /// Kind of the error recorded on its creation.
+        kind: ErrorKind,
+        /// Remediation hint set via [`ErrorWithOrigin::with_note()`].
+        note: Option<String>,
     },
     #[doc(hidden)] // implementation detail
     InvalidArray,
@@ -57,6 +116,8 @@ impl From<serde_json::Error> for LowLevelError {
         Self::Json {
             err,
             category: ParseErrorCategory::Generic,
+            kind: ErrorKind::Custom,
+            note: None,
         }
     }
 }
@@ -80,10 +141,37 @@ impl ErrorWithOrigin {
         Self::new(err.into(), origin)
     }
 
+    pub(crate) fn json_with_kind(
+        err: serde_json::Error,
+        kind: ErrorKind,
+        origin: Arc<ValueOrigin>,
+    ) -> Self {
+        let err = LowLevelError::Json {
+            err,
+            category: ParseErrorCategory::Generic,
+            kind,
+            note: None,
+        };
+        Self::new(err, origin)
+    }
+
     /// Creates a custom error.
     pub fn custom(message: impl fmt::Display) -> Self {
         Self::json(de::Error::custom(message), Arc::default())
     }
+
+    /// Attaches a remediation hint to this error. The note is propagated to the [`ParseError`] produced from this error,
+    /// and is available via [`ParseError::note()`].
+    ///
+    /// Notes are only supported for errors originating from JSON deserialization logic (incl. [custom errors](Self::custom()));
+    /// for other errors, this method is a no-op.
+    #[must_use]
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        if let LowLevelError::Json { note: note_mut, .. } = &mut self.inner {
+            *note_mut = Some(note.into());
+        }
+        self
+    }
 }
 
 impl de::Error for ErrorWithOrigin {
@@ -91,10 +179,44 @@ impl de::Error for ErrorWithOrigin {
         Self::json(de::Error::custom(msg), Arc::default())
     }
 
+    fn invalid_type(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        Self::json_with_kind(
+            de::Error::invalid_type(unexp, exp),
+            ErrorKind::InvalidType,
+            Arc::default(),
+        )
+    }
+
+    fn invalid_value(unexp: de::Unexpected<'_>, exp: &dyn de::Expected) -> Self {
+        Self::json_with_kind(
+            de::Error::invalid_value(unexp, exp),
+            ErrorKind::InvalidValue,
+            Arc::default(),
+        )
+    }
+
+    fn invalid_length(len: usize, exp: &dyn de::Expected) -> Self {
+        Self::json_with_kind(
+            de::Error::invalid_length(len, exp),
+            ErrorKind::InvalidLength,
+            Arc::default(),
+        )
+    }
+
+    fn unknown_variant(variant: &str, expected: &'static [&'static str]) -> Self {
+        Self::json_with_kind(
+            de::Error::unknown_variant(variant, expected),
+            ErrorKind::UnknownVariant,
+            Arc::default(),
+        )
+    }
+
     fn missing_field(field: &'static str) -> Self {
         let err = LowLevelError::Json {
             err: de::Error::missing_field(field),
             category: ParseErrorCategory::MissingField,
+            kind: ErrorKind::MissingField,
+            note: None,
         };
         Self::new(err, Arc::default())
     }
@@ -121,11 +243,17 @@ impl std::error::Error for ErrorWithOrigin {
 pub struct ParseError {
     pub(crate) inner: serde_json::Error,
     pub(crate) category: ParseErrorCategory,
+    pub(crate) kind: ErrorKind,
     pub(crate) path: String,
     pub(crate) origin: Arc<ValueOrigin>,
     pub(crate) config: &'static ConfigMetadata,
     pub(crate) location_in_config: Option<LocationInConfig>,
     pub(crate) validation: Option<String>,
+    pub(crate) suggestions: Vec<&'static str>,
+    pub(crate) note: Option<String>,
+    /// Expected and actual basic types of the param value if this error is caused by a type mismatch.
+    pub(crate) type_mismatch: Option<(BasicTypes, BasicTypes)>,
+    pub(crate) severity: Severity,
 }
 
 impl fmt::Debug for ParseError {
@@ -138,12 +266,26 @@ impl fmt::Debug for ParseError {
             .field("config.ty", &self.config.ty)
             .field("location_in_config", &self.location_in_config)
             .field("validation", &self.validation)
+            .field("suggestions", &self.suggestions)
+            .field("note", &self.note)
+            .field("type_mismatch", &self.type_mismatch)
+            .field("severity", &self.severity)
             .finish_non_exhaustive()
     }
 }
 
 impl fmt::Display for ParseError {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with_origin(formatter, &self.origin)
+    }
+}
+
+impl ParseError {
+    fn fmt_with_origin(
+        &self,
+        formatter: &mut fmt::Formatter<'_>,
+        origin: &ValueOrigin,
+    ) -> fmt::Result {
         let field = self.location_in_config.and_then(|location| {
             Some(match location {
                 LocationInConfig::Param(idx) => {
@@ -154,10 +296,10 @@ impl fmt::Display for ParseError {
         });
         let field = field.as_deref().unwrap_or("");
 
-        let origin = if matches!(self.origin(), ValueOrigin::Unknown) {
+        let origin = if matches!(origin, ValueOrigin::Unknown) {
             String::new()
         } else {
-            format!(" [origin: {}]", self.origin)
+            format!(" [origin: {origin}]")
         };
 
         let failed_action = if let Some(validation) = &self.validation {
@@ -166,13 +308,32 @@ impl fmt::Display for ParseError {
             "parsing".to_owned()
         };
 
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
         write!(
             formatter,
-            "error {failed_action} {field}`{config}` at `{path}`{origin}: {err}",
+            "{severity} {failed_action} {field}`{config}` at `{path}`{origin}: {err}",
             err = self.inner,
             config = self.config.ty.name_in_code(),
             path = self.path
-        )
+        )?;
+        if let Some((expected, actual)) = self.type_mismatch {
+            write!(formatter, " (expected {expected}, got {actual})")?;
+        }
+
+        if let Some((last, rest)) = self.suggestions.split_last() {
+            formatter.write_str("; did you mean ")?;
+            for suggestion in rest {
+                write!(formatter, "`{suggestion}`, ")?;
+            }
+            write!(formatter, "`{last}`?")?;
+        }
+        if let Some(note) = &self.note {
+            write!(formatter, "\n  help: {note}")?;
+        }
+        Ok(())
     }
 }
 
@@ -187,11 +348,16 @@ impl ParseError {
         Self {
             inner: serde_json::Error::custom("unspecified error deserializing configuration"),
             category: ParseErrorCategory::Generic,
+            kind: ErrorKind::Custom,
             path,
             origin: Arc::default(),
             config,
             location_in_config: None,
             validation: None,
+            suggestions: vec![],
+            note: None,
+            type_mismatch: None,
+            severity: Severity::Error,
         }
     }
 
@@ -200,6 +366,80 @@ impl ParseError {
         &self.inner
     }
 
+    /// Converts this error into an [`anyhow::Error`]. Unlike converting via `?` (which would only retain
+    /// the formatted message), the error context is represented as a chain, so it's possible to inspect
+    /// (e.g., via [`anyhow::Error::chain()`]) or format it using standard `anyhow` tools. From the outermost to the innermost,
+    /// the chain consists of:
+    ///
+    /// 1. Severity and the config name in code, e.g. ``error parsing config `TestConfig` ``.
+    /// 2. Path to the failing param / config, and the value origin if known.
+    /// 3. Validation description, if the error was produced by a validation.
+    /// 4. Hints, if any: expected vs actual types, suggestions and the note.
+    /// 5. The [wrapped](Self::inner()) error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{config, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig};
+    /// #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    /// struct TestConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    /// let repo = ConfigRepository::new(&schema).with(config!("test.port": "??"));
+    /// let errors = repo.single::<TestConfig>()?.parse().unwrap_err();
+    /// let err = errors.into_iter().next().unwrap().into_anyhow();
+    /// assert_eq!(err.to_string(), "error parsing config `TestConfig`");
+    /// let chain: Vec<_> = err.chain().map(ToString::to_string).collect();
+    /// assert!(chain[1].starts_with("param `port` at `test.port` [origin: "));
+    /// assert_eq!(chain[2], "expected integer, got string");
+    /// assert!(err.root_cause().is::<serde_json::Error>());
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn into_anyhow(self) -> anyhow::Error {
+        let mut hints = vec![];
+        if let Some((expected, actual)) = self.type_mismatch {
+            hints.push(format!("expected {expected}, got {actual}"));
+        }
+        if !self.suggestions.is_empty() {
+            let suggestions: Vec<_> = self
+                .suggestions
+                .iter()
+                .map(|suggestion| format!("`{suggestion}`"))
+                .collect();
+            hints.push(format!("did you mean {}?", suggestions.join(", ")));
+        }
+        if let Some(note) = &self.note {
+            hints.push(format!("help: {note}"));
+        }
+
+        let location = match self.param() {
+            Some(param) => format!("param `{}` at `{}`", param.name, self.path),
+            None => format!("`{}`", self.path),
+        };
+        let origin = if matches!(self.origin.as_ref(), ValueOrigin::Unknown) {
+            String::new()
+        } else {
+            format!(" [origin: {}]", self.origin)
+        };
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let config = self.config.ty.name_in_code();
+
+        let mut err = anyhow::Error::new(self.inner);
+        if !hints.is_empty() {
+            err = err.context(hints.join("; "));
+        }
+        if let Some(validation) = self.validation {
+            err = err.context(format!("validating '{validation}'"));
+        }
+        err.context(format!("{location}{origin}"))
+            .context(format!("{severity} parsing config `{config}`"))
+    }
+
     #[doc(hidden)]
     pub fn category(&self) -> ParseErrorCategory {
         self.category
@@ -220,6 +460,39 @@ impl ParseError {
         self.validation.as_deref()
     }
 
+    /// Returns the remediation hint for this error, if any. The note is rendered in the [`Display`](fmt::Display)
+    /// implementation on a separate line after the main error message.
+    ///
+    /// Notes can be attached by [validations](crate::validation) and custom deserializers
+    /// via [`ErrorWithOrigin::with_note()`], or directly via [`Self::with_note()`].
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    /// Attaches a remediation hint to this error, replacing the existing one (if any).
+    #[must_use]
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.note = Some(note.into());
+        self
+    }
+
+    /// Returns the basic types expected by the failing param if this error is caused by a type mismatch,
+    /// e.g. a string provided for an integer param.
+    pub fn expected_types(&self) -> Option<BasicTypes> {
+        self.type_mismatch.map(|(expected, _)| expected)
+    }
+
+    /// Returns the severity of this error.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns canonical param names similar to keys unknown to the failing config. Only non-empty
+    /// for missing field errors, in which case the missing param may have been misspelled.
+    pub fn suggestions(&self) -> &[&'static str] {
+        &self.suggestions
+    }
+
     /// Returns metadata for the failing config.
     pub fn config(&self) -> &'static ConfigMetadata {
         self.config
@@ -231,6 +504,50 @@ impl ParseError {
         let LocationInConfig::Param(idx) = self.location_in_config?;
         self.config.params.get(idx)
     }
+
+    /// Returns the kind of the underlying deserialization error recorded on its creation. Unlike [`Self::code()`],
+    /// the kind doesn't take into account the error category or validation; e.g., a missing config has
+    /// the [`ErrorKind::Custom`] kind, but the `missing_field` code.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns a stable machine-readable code for this error, which can be used to match on error kinds
+    /// without parsing the human-readable message. The following codes are currently returned:
+    ///
+    /// | Code | Description |
+    /// |:-----|:------------|
+    /// | `missing_field` | Required param or config is missing |
+    /// | `validation_failed` | Param or config [validation](crate::validation) has failed |
+    /// | `type_mismatch` | Value has an unexpected type, e.g. a string instead of an integer |
+    /// | `invalid_value` | Value has the expected type (or can be coerced to it), but is invalid, e.g. an out-of-range integer or an unparsable integer string |
+    /// | `invalid_array` | Array (or another collection) has an unexpected number of items |
+    /// | `unknown_variant` | Unknown enum variant or unit of measurement |
+    /// | `unknown_tag` | Unknown variant key in an [externally tagged](crate::ConfigSchema::accept_external_tag()) enum config |
+    /// | `unknown_key` | Key in a source not corresponding to any param or config; see [`UnknownKeys`](crate::de::UnknownKeys) |
+    /// | `custom` | All other errors, e.g. ones produced by custom deserializers |
+    ///
+    /// More codes may be added in the future, so consumers should treat unknown codes as `custom`.
+    pub fn code(&self) -> &'static str {
+        match self.category {
+            ParseErrorCategory::MissingField => return "missing_field",
+            ParseErrorCategory::UnknownKey => return "unknown_key",
+            ParseErrorCategory::Generic => { /* continue classification */ }
+        }
+        if self.validation.is_some() {
+            return "validation_failed";
+        }
+
+        match self.kind {
+            ErrorKind::InvalidType => "type_mismatch",
+            ErrorKind::InvalidValue => "invalid_value",
+            ErrorKind::InvalidLength => "invalid_array",
+            ErrorKind::UnknownVariant => "unknown_variant",
+            ErrorKind::MissingField => "missing_field",
+            ErrorKind::UnknownTag => "unknown_tag",
+            ErrorKind::Custom => "custom",
+        }
+    }
 }
 
 /// Collection of [`ParseError`]s returned from [`ConfigParser::parse()`](crate::ConfigParser::parse()).
@@ -249,7 +566,26 @@ impl ParseErrors {
         self.errors.iter()
     }
 
-    /// Returns the number of contained errors.
+    /// Iterates over the contained errors with [`Severity::Error`].
+    pub fn errors(&self) -> impl Iterator<Item = &ParseError> + '_ {
+        self.errors
+            .iter()
+            .filter(|err| err.severity == Severity::Error)
+    }
+
+    /// Iterates over the contained errors with [`Severity::Warning`].
+    pub fn warnings(&self) -> impl Iterator<Item = &ParseError> + '_ {
+        self.errors
+            .iter()
+            .filter(|err| err.severity == Severity::Warning)
+    }
+
+    /// Checks whether this collection contains at least one error with [`Severity::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    /// Returns the number of contained errors (including warnings).
     #[allow(clippy::len_without_is_empty)] // is_empty should always return false
     pub fn len(&self) -> usize {
         self.errors.len()
@@ -261,9 +597,109 @@ impl ParseErrors {
         self.errors.first().expect("no errors")
     }
 
+    /// Appends all errors from `other` to this collection. This is useful to aggregate errors from multiple
+    /// [`ConfigParser`](crate::ConfigParser)s, e.g. to report them all at once on application startup.
+    pub fn merge(&mut self, other: Self) {
+        self.errors.extend(other.errors);
+    }
+
+    /// Filters errors by the specified predicate, preserving their order. This can be used to drop
+    /// acceptable errors before reporting, e.g. all [`missing_field`](ParseError::code()) errors for a specific config.
+    /// Unlike `Vec::retain()`, this method consumes the collection since it cannot be left empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns the retained errors, or `Ok(())` if all errors were dropped.
+    pub fn filter(self, predicate: impl FnMut(&ParseError) -> bool) -> Result<(), Self> {
+        self.errors.into_iter().filter(predicate).collect()
+    }
+
+    /// Converts these errors into an [`anyhow::Error`]. If there's a single error, this is equivalent
+    /// to [`ParseError::into_anyhow()`]. Otherwise, the returned error wraps this collection (so it can be recovered via
+    /// [`anyhow::Error::downcast()`]) with a context describing the number of errors; the full error context
+    /// is available via the [`Display`](fmt::Display) implementation of the wrapped collection.
+    pub fn into_anyhow(mut self) -> anyhow::Error {
+        if self.errors.len() == 1 {
+            if let Some(err) = self.errors.pop() {
+                return err.into_anyhow();
+            }
+        }
+        let len = self.errors.len();
+        anyhow::Error::new(self).context(format!("{len} errors parsing configs"))
+    }
+
     pub(crate) fn truncate(&mut self, len: usize) {
         self.errors.truncate(len);
     }
+
+    /// Removes duplicate errors, keeping the first error among the duplicates. Errors are considered duplicate
+    /// if they have the same path, config, location in config and rendered message (which includes the origin and validation
+    /// description, if any). In particular, errors differing only by origin are retained.
+    pub fn dedup(&mut self) {
+        let mut seen = HashSet::with_capacity(self.errors.len());
+        self.errors.retain(|err| {
+            seen.insert((
+                err.path.clone(),
+                err.config.ty.id(),
+                err.location_in_config,
+                err.to_string(),
+            ))
+        });
+    }
+
+    /// Sorts errors by their path, breaking ties by the config [name in code](crate::metadata::RustType::name_in_code())
+    /// and then by the error message. The sort is stable, i.e., errors equal by all these criteria retain their relative order.
+    ///
+    /// Combined with [`Self::iter()`] + [`Iterator::take()`], this allows outputting errors in a reproducible way.
+    pub fn sort_by_path(&mut self) {
+        self.errors.sort_by_cached_key(|err| {
+            (
+                err.path.clone(),
+                err.config.ty.name_in_code(),
+                err.inner.to_string(),
+            )
+        });
+    }
+
+    /// Groups errors by the failing config, keyed by the config [name in code](crate::metadata::RustType::name_in_code()).
+    /// Within each group, errors are ordered in the same way as in [`Self::iter()`].
+    pub fn grouped_by_config(&self) -> BTreeMap<&'static str, Vec<&ParseError>> {
+        let mut groups = BTreeMap::<_, Vec<_>>::new();
+        for err in &self.errors {
+            groups
+                .entry(err.config.ty.name_in_code())
+                .or_default()
+                .push(err);
+        }
+        groups
+    }
+
+    /// Returns a [`Display`](fmt::Display)able representation of these errors with file paths in origins
+    /// made relative to the specified `base` directory. Origins not backed by files, and files outside `base`,
+    /// are rendered unchanged. Otherwise, the output is identical to the `Display` implementation for `ParseErrors`.
+    ///
+    /// This is useful to make error output portable across machines, e.g. in snapshot tests.
+    pub fn display_relative_to<'a>(&'a self, base: &'a Path) -> impl fmt::Display + 'a {
+        RelativeDisplay { errors: self, base }
+    }
+
+    /// Serializes these errors to JSON, e.g. for structured logging. The output is an array of objects
+    /// with `path`, `origin`, `config`, `param` (`null` if the error doesn't concern a param), `category`,
+    /// [`code`](ParseError::code()) and `message` fields.
+    pub fn to_json(&self) -> serde_json::Value {
+        let errors = self.errors.iter().map(|err| {
+            serde_json::json!({
+                "path": err.path,
+                "origin": err.origin.to_string(),
+                "config": err.config.ty.name_in_code(),
+                "param": err.param().map(|param| param.name),
+                "category": err.category.as_str(),
+                "code": err.code(),
+                "message": err.inner.to_string(),
+            })
+        });
+        serde_json::Value::Array(errors.collect())
+    }
 }
 
 impl IntoIterator for ParseErrors {
@@ -275,6 +711,12 @@ impl IntoIterator for ParseErrors {
     }
 }
 
+impl Extend<ParseError> for ParseErrors {
+    fn extend<I: IntoIterator<Item = ParseError>>(&mut self, iter: I) {
+        self.errors.extend(iter);
+    }
+}
+
 impl fmt::Display for ParseErrors {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         for err in &self.errors {
@@ -286,6 +728,23 @@ impl fmt::Display for ParseErrors {
 
 impl std::error::Error for ParseErrors {}
 
+#[derive(Debug)]
+struct RelativeDisplay<'a> {
+    errors: &'a ParseErrors,
+    base: &'a Path,
+}
+
+impl fmt::Display for RelativeDisplay<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for err in &self.errors.errors {
+            let origin = err.origin.relative_to(self.base);
+            err.fmt_with_origin(formatter, &origin)?;
+            writeln!(formatter)?;
+        }
+        Ok(())
+    }
+}
+
 impl FromIterator<ParseError> for Result<(), ParseErrors> {
     fn from_iter<I: IntoIterator<Item = ParseError>>(iter: I) -> Self {
         let errors: Vec<_> = iter.into_iter().collect();
@@ -17,15 +17,39 @@
 //!
 //! - By design, fallbacks are location-independent. E.g., an [`Env`] fallback will always read from the same env var,
 //!   regardless of where the param containing it is placed (including the case when it has multiple copies!).
-//! - Fallbacks always have lower priority than all other config sources.
+//! - By default, fallbacks have lower priority than all other config sources.
+//!
+//! # Priority
+//!
+//! A fallback can declare [high priority](FallbackPriority::High) via [`FallbackSource::priority()`]
+//! (e.g., by wrapping it in [`HighPriority`]). High-priority fallbacks are still overridden by values from env vars
+//! and other non-file sources, but they take precedence over values from file sources (i.e., ones with
+//! [`OriginKind::File`](crate::value::OriginKind::File) origin), regardless of the order in which sources are added
+//! to a [`ConfigRepository`](crate::ConfigRepository). Thus, the complete ordering from the lowest to the highest priority
+//! is as follows:
+//!
+//! 1. Low-priority fallbacks
+//! 2. File sources, in the order they are added to the repository
+//! 3. High-priority fallbacks
+//! 4. Other sources (env vars etc.), in the order they are added to the repository. Since these sources
+//!    are ordered together with file sources, a value from a file source added after an env source still overrides
+//!    the env value.
 
-use std::{collections::HashMap, env, fmt, sync::Arc};
+#[cfg(feature = "http")]
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    sync::{mpsc, OnceLock},
+    time::Duration,
+};
+use std::{collections::BTreeMap, env, fmt, fs, io, sync::Arc};
 
 use crate::{
     source::Hierarchical,
     testing::MOCK_ENV_VARS,
-    value::{Map, Pointer, Value, ValueOrigin, WithOrigin},
-    ConfigSchema, ConfigSource,
+    utils::tracing,
+    value::{FileFormat, Map, Pointer, Value, ValueOrigin, WithOrigin},
+    ConfigSchema, ConfigSource, Json,
 };
 
 /// Fallback source of a configuration param.
@@ -34,6 +58,68 @@ pub trait FallbackSource: 'static + Send + Sync + fmt::Debug + fmt::Display {
     ///
     /// Implementations should return `None` (vs `Some(Value::Null)` etc.) if the source doesn't have a value.
     fn provide_value(&self) -> Option<WithOrigin>;
+
+    /// Returns the priority of this fallback relative to other config sources. See [module docs](self#priority)
+    /// for details.
+    ///
+    /// The default implementation returns [`FallbackPriority::Low`].
+    fn priority(&self) -> FallbackPriority {
+        FallbackPriority::Low
+    }
+}
+
+/// Priority of a [`FallbackSource`] relative to other config sources.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FallbackPriority {
+    /// Fallback has lower priority than all other config sources.
+    #[default]
+    Low,
+    /// Fallback has higher priority than file sources, but lower priority than other sources, such as env vars.
+    High,
+}
+
+/// Wrapper raising the priority of the wrapped fallback to [`FallbackPriority::High`].
+///
+/// # Examples
+///
+/// ```
+/// use smart_config::{fallback, testing, DescribeConfig, DeserializeConfig};
+///
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(
+///         default_t = "info".into(),
+///         fallback = &fallback::HighPriority(fallback::Env("RUST_LOG"))
+///     )]
+///     log_directives: String,
+/// }
+///
+/// let mut tester = testing::Tester::default();
+/// tester.set_env("RUST_LOG", "warn");
+/// // The fallback overrides the value from a file source.
+/// let input = smart_config::config!("log_directives": "info,my_crate=debug");
+/// let config: TestConfig = tester.test(input)?;
+/// assert_eq!(config.log_directives, "warn");
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct HighPriority<F>(pub F);
+
+impl<F: fmt::Display> fmt::Display for HighPriority<F> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, formatter)
+    }
+}
+
+impl<F: FallbackSource> FallbackSource for HighPriority<F> {
+    fn provide_value(&self) -> Option<WithOrigin> {
+        self.0.provide_value()
+    }
+
+    fn priority(&self) -> FallbackPriority {
+        FallbackPriority::High
+    }
 }
 
 /// Gets a string value from the specified env variable.
@@ -85,20 +171,297 @@ impl Env {
             .with(|cell| cell.borrow().get(self.0).cloned())
             .or_else(|| env::var(self.0).ok())
     }
+
+    /// Returns the origin of values provided by this env var.
+    pub fn origin(&self) -> Arc<ValueOrigin> {
+        Arc::new(ValueOrigin::Path {
+            source: Arc::new(ValueOrigin::EnvVars),
+            path: self.0.into(),
+        })
+    }
+
+    /// Returns the origin for a value combined from multiple env vars, e.g. in a [`Manual`] fallback.
+    /// The origin lists names of all `vars`, which is useful for diagnostics (e.g., if the combined value is invalid).
+    pub fn combined_origin(vars: &[Self]) -> Arc<ValueOrigin> {
+        let var_names: Vec<_> = vars.iter().map(|var| format!("`{}`", var.0)).collect();
+        Arc::new(ValueOrigin::Synthetic {
+            source: Arc::new(ValueOrigin::EnvVars),
+            transform: format!("combined from {}", var_names.join(", ")),
+        })
+    }
 }
 
 impl FallbackSource for Env {
     fn provide_value(&self) -> Option<WithOrigin> {
-        if let Some(value) = self.get_raw() {
-            let origin = ValueOrigin::Path {
-                source: Arc::new(ValueOrigin::EnvVars),
-                path: self.0.into(),
+        let value = self.get_raw()?;
+        Some(WithOrigin::new(value.into(), self.origin()))
+    }
+}
+
+/// Gets the param value from the contents of a file with the specified path, e.g. a secret mounted by Kubernetes.
+/// A single trailing newline (`\n` or `\r\n`) is trimmed from the contents.
+///
+/// The file is read when the [`ConfigRepository`](crate::ConfigRepository) is created. If the file does not exist
+/// or cannot be read, no value is provided.
+///
+/// # Examples
+///
+/// ```
+/// use smart_config::{fallback, DescribeConfig, DeserializeConfig};
+/// use secrecy::SecretString;
+///
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct DatabaseConfig {
+///     #[config(fallback = &fallback::FileContents("/run/secrets/db_password"))]
+///     password: Option<SecretString>,
+/// }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FileContents(pub &'static str);
+
+impl fmt::Display for FileContents {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "contents of file {:?}", self.0)
+    }
+}
+
+impl FileContents {
+    fn read(&self) -> Option<String> {
+        match fs::read_to_string(self.0) {
+            Ok(contents) => Some(contents),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => {
+                tracing::warn!(path = self.0, %err, "failed reading file for fallback");
+                None
+            }
+        }
+    }
+}
+
+impl FallbackSource for FileContents {
+    fn provide_value(&self) -> Option<WithOrigin> {
+        let mut contents = self.read()?;
+        if contents.ends_with('\n') {
+            contents.pop();
+            if contents.ends_with('\r') {
+                contents.pop();
+            }
+        }
+        let origin = ValueOrigin::File {
+            name: self.0.to_owned(),
+            format: FileFormat::Text,
+        };
+        Some(WithOrigin::new(contents.into(), Arc::new(origin)))
+    }
+}
+
+/// Gets the param value from a JSON document served over HTTP, e.g. by a config server of the parent process.
+/// The value is extracted from the response body using a [JSON pointer](https://datatracker.ietf.org/doc/html/rfc6901).
+///
+/// The document is fetched using a blocking GET request when the [`ConfigRepository`](crate::ConfigRepository)
+/// is created; the request has a short [timeout](Self::DEFAULT_TIMEOUT) by default. The document is fetched
+/// once per repository and is shared among all `Http` fallbacks with the same URL. If the request fails
+/// or the response is not valid JSON, a warning is logged and no value is provided. No value is provided
+/// either if the pointer doesn't resolve to a value in the document.
+///
+/// # Examples
+///
+/// ```
+/// # use std::time::Duration;
+/// use smart_config::{fallback, DescribeConfig, DeserializeConfig};
+///
+/// const CONFIG_SERVER: fallback::Http =
+///     fallback::Http::new("http://127.0.0.1:3000/config.json", "/api/port")
+///         .with_timeout(Duration::from_millis(200));
+///
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct ApiConfig {
+///     #[config(default_t = 8080, fallback = &CONFIG_SERVER)]
+///     port: u16,
+/// }
+/// ```
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+#[derive(Debug, Clone, Copy)]
+pub struct Http {
+    url: &'static str,
+    pointer: &'static str,
+    timeout: Duration,
+}
+
+#[cfg(feature = "http")]
+impl fmt::Display for Http {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            formatter,
+            "value at JSON pointer {:?} from {:?}",
+            self.pointer, self.url
+        )
+    }
+}
+
+#[cfg(feature = "http")]
+impl Http {
+    /// Default timeout for HTTP requests.
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+    /// Creates a fallback fetching a JSON document from the specified URL and extracting a value
+    /// at the specified JSON pointer (e.g., `/api/port`).
+    pub const fn new(url: &'static str, pointer: &'static str) -> Self {
+        Self {
+            url,
+            pointer,
+            timeout: Self::DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// Sets the timeout for the HTTP request.
+    #[must_use]
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    fn fetch(&self) -> anyhow::Result<serde_json::Value> {
+        let (runtime, client) = http_runtime()
+            .as_ref()
+            .map_err(|err| anyhow::anyhow!("failed initializing HTTP runtime: {err}"))?;
+        let request = client.get(self.url).timeout(self.timeout);
+        // The request is executed on the shared runtime and awaited via a sync channel, so that fetching
+        // doesn't interfere with the async runtime if the repository is created within one.
+        let (sender, receiver) = mpsc::sync_channel(1);
+        runtime.spawn(async move {
+            let result = async {
+                let response = request.send().await?.error_for_status()?;
+                let body = response.text().await?;
+                Ok(serde_json::from_str(&body)?)
             };
-            Some(WithOrigin::new(value.into(), Arc::new(origin)))
-        } else {
-            None
+            sender.send(result.await).ok();
+        });
+        receiver
+            .recv()
+            .unwrap_or_else(|_| Err(anyhow::anyhow!("HTTP request task panicked")))
+    }
+
+    fn fetch_or_warn(&self) -> Option<Arc<serde_json::Value>> {
+        match self.fetch() {
+            Ok(document) => Some(Arc::new(document)),
+            Err(err) => {
+                tracing::warn!(url = self.url, "failed fetching fallback value: {err:#}");
+                None
+            }
         }
     }
+
+    /// Fetches the document, or gets it from the cache if fallbacks are resolved within [`with_shared_documents()`].
+    fn document(&self) -> Option<Arc<serde_json::Value>> {
+        HTTP_DOCUMENTS.with_borrow_mut(|cache| match cache {
+            Some(cache) => cache
+                .entry(self.url)
+                .or_insert_with(|| self.fetch_or_warn())
+                .clone(),
+            None => self.fetch_or_warn(),
+        })
+    }
+}
+
+#[cfg(feature = "http")]
+impl FallbackSource for Http {
+    fn provide_value(&self) -> Option<WithOrigin> {
+        let document = self.document()?;
+        let value = document.pointer(self.pointer)?.clone();
+        let origin = Arc::new(ValueOrigin::Custom(format!(
+            "JSON pointer {:?} from '{}'",
+            self.pointer, self.url
+        )));
+        Some(Json::map_value(value, &origin, String::new()))
+    }
+}
+
+/// Provides a constant string value for the param. Useful as the lowest-priority fallback, e.g. to express
+/// a default that should be parsed in the same way as user-provided values.
+///
+/// The provided value has a [synthetic](ValueOrigin::Synthetic) origin with the `const fallback` transform.
+/// See [`ConstJson`] for a similar fallback providing a structured value.
+///
+/// # Examples
+///
+/// ```
+/// use smart_config::{fallback, testing, DescribeConfig, DeserializeConfig};
+///
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(fallback = &fallback::Const("info,my_app=debug"))]
+///     log_directives: String,
+/// }
+///
+/// let config: TestConfig = testing::test(smart_config::config!())?;
+/// assert_eq!(config.log_directives, "info,my_app=debug");
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Const(pub &'static str);
+
+impl fmt::Display for Const {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "constant {:?}", self.0)
+    }
+}
+
+impl FallbackSource for Const {
+    fn provide_value(&self) -> Option<WithOrigin> {
+        Some(WithOrigin::new(self.0.to_owned().into(), const_origin()))
+    }
+}
+
+/// Provides a constant value for the param specified as a JSON string. Unlike [`Const`], the value
+/// can be structured (e.g., an array or an object), or have a non-string type.
+///
+/// The JSON string is parsed when the [`ConfigRepository`](crate::ConfigRepository) is created.
+/// If it is invalid, a warning is logged and no value is provided.
+///
+/// # Examples
+///
+/// ```
+/// use smart_config::{fallback, testing, DescribeConfig, DeserializeConfig};
+///
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(fallback = &fallback::ConstJson(r#"["localhost:3000", "localhost:3001"]"#))]
+///     peers: Vec<String>,
+/// }
+///
+/// let config: TestConfig = testing::test(smart_config::config!())?;
+/// assert_eq!(config.peers, ["localhost:3000", "localhost:3001"]);
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConstJson(pub &'static str);
+
+impl fmt::Display for ConstJson {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "constant JSON `{}`", self.0)
+    }
+}
+
+impl FallbackSource for ConstJson {
+    fn provide_value(&self) -> Option<WithOrigin> {
+        let value = match serde_json::from_str(self.0) {
+            Ok(value) => value,
+            Err(err) => {
+                tracing::warn!(json = self.0, %err, "invalid constant JSON for fallback");
+                return None;
+            }
+        };
+        Some(Json::map_value(value, &const_origin(), String::new()))
+    }
+}
+
+fn const_origin() -> Arc<ValueOrigin> {
+    Arc::new(ValueOrigin::Synthetic {
+        source: Arc::default(),
+        transform: "const fallback".to_owned(),
+    })
 }
 
 /// Custom [fallback value provider](FallbackSource).
@@ -120,9 +483,8 @@ impl FallbackSource for Env {
 /// # Examples
 ///
 /// ```
-/// # use std::sync::Arc;
 /// use smart_config::{
-///     fallback, testing, value::{ValueOrigin, WithOrigin},
+///     fallback::{self, Env}, testing, value::WithOrigin,
 ///     DescribeConfig, DeserializeConfig,
 /// };
 ///
@@ -130,9 +492,11 @@ impl FallbackSource for Env {
 /// // the definition like this so that it's more readable.
 /// const COMBINED_VARS: &'static dyn fallback::FallbackSource =
 ///     &fallback::Manual::new("$TEST_ENV - $TEST_NETWORK", || {
-///         let env = fallback::Env("TEST_ENV").get_raw()?;
-///         let network = fallback::Env("TEST_NETWORK").get_raw()?;
-///         let origin = Arc::new(ValueOrigin::EnvVars);
+///         const VARS: [Env; 2] = [Env("TEST_ENV"), Env("TEST_NETWORK")];
+///         let env = VARS[0].get_raw()?;
+///         let network = VARS[1].get_raw()?;
+///         // Origin listing all consulted vars, so that they are mentioned in parsing errors
+///         let origin = Env::combined_origin(&VARS);
 ///         Some(WithOrigin::new(format!("{env} - {network}").into(), origin))
 ///     });
 ///
@@ -149,6 +513,12 @@ impl FallbackSource for Env {
 /// assert_eq!(config.app, "stage - goerli");
 /// # anyhow::Ok(())
 /// ```
+///
+/// ## Using runtime state
+///
+/// Fallbacks are a part of config metadata, which is constructed in compile time. Thus, the getter cannot
+/// be a capturing closure. If the getter needs state computed in runtime (e.g., an env var prefix),
+/// use [`ManualBoxed`] stored in a `static` cell and delegate to it from the getter.
 #[derive(Debug)]
 pub struct Manual {
     description: &'static str,
@@ -177,21 +547,163 @@ impl FallbackSource for Manual {
     }
 }
 
-#[derive(Debug)]
+type BoxedGetter = Box<dyn Fn() -> Option<WithOrigin> + Send + Sync>;
+
+/// Custom [fallback value provider](FallbackSource) with a boxed getter, which can capture state computed in runtime.
+///
+/// Unlike [`Manual`], this provider cannot be created in compile time, so it cannot be referenced in config metadata
+/// directly. Instead, it can be placed in a `static` cell initialized before configs are parsed, and a [`Manual`]
+/// fallback can delegate to it.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::OnceLock;
+/// use smart_config::{
+///     fallback::{self, Env, FallbackSource},
+///     testing, DescribeConfig, DeserializeConfig,
+/// };
+///
+/// static NETWORK: OnceLock<fallback::ManualBoxed> = OnceLock::new();
+///
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(default_t = "mainnet".into())]
+///     #[config(fallback = &fallback::Manual::new("$PREFIX_NETWORK", || {
+///         NETWORK.get()?.provide_value()
+///     }))]
+///     network: String,
+/// }
+///
+/// // The prefix is computed in runtime, e.g. from command-line args.
+/// let prefix = "APP_";
+/// let var = Env(format!("{prefix}NETWORK").leak());
+/// let description = format!("${}", var.0);
+/// NETWORK
+///     .set(fallback::ManualBoxed::new(description, move || var.provide_value()))
+///     .unwrap();
+///
+/// let config: TestConfig = testing::Tester::default()
+///     .set_env("APP_NETWORK", "sepolia")
+///     .test(smart_config::config!())?;
+/// assert_eq!(config.network, "sepolia");
+/// # anyhow::Ok(())
+/// ```
+pub struct ManualBoxed {
+    description: String,
+    getter: BoxedGetter,
+}
+
+impl fmt::Debug for ManualBoxed {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("ManualBoxed")
+            .field("description", &self.description)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ManualBoxed {
+    /// Creates a provider with the specified human-readable description and a getter closure.
+    pub fn new(
+        description: impl Into<String>,
+        getter: impl Fn() -> Option<WithOrigin> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            description: description.into(),
+            getter: Box::new(getter),
+        }
+    }
+}
+
+impl fmt::Display for ManualBoxed {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.description)
+    }
+}
+
+impl FallbackSource for ManualBoxed {
+    fn provide_value(&self) -> Option<WithOrigin> {
+        (self.getter)()
+    }
+}
+
+/// Returns the runtime and the client shared by all [`Http`] fallbacks. The runtime has a single worker thread
+/// and is never shut down.
+#[cfg(feature = "http")]
+fn http_runtime() -> &'static io::Result<(tokio::runtime::Runtime, reqwest::Client)> {
+    static RUNTIME: OnceLock<io::Result<(tokio::runtime::Runtime, reqwest::Client)>> =
+        OnceLock::new();
+    RUNTIME.get_or_init(|| {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("smart-config-http")
+            .enable_all()
+            .build()?;
+        Ok((runtime, reqwest::Client::new()))
+    })
+}
+
+/// Documents fetched by [`Http`] fallbacks keyed by URL; `None` values correspond to failed fetches.
+#[cfg(feature = "http")]
+type HttpDocuments = HashMap<&'static str, Option<Arc<serde_json::Value>>>;
+
+#[cfg(feature = "http")]
+thread_local! {
+    /// Only set within [`with_shared_documents()`].
+    static HTTP_DOCUMENTS: RefCell<Option<HttpDocuments>> = const { RefCell::new(None) };
+}
+
+/// Executes the provided action so that documents fetched by [`Http`] fallbacks are shared among all fallbacks
+/// resolved within it.
+pub(crate) fn with_shared_documents<R>(action: impl FnOnce() -> R) -> R {
+    #[cfg(feature = "http")]
+    struct CacheGuard;
+
+    #[cfg(feature = "http")]
+    impl Drop for CacheGuard {
+        fn drop(&mut self) {
+            HTTP_DOCUMENTS.take();
+        }
+    }
+
+    #[cfg(feature = "http")]
+    let _guard = {
+        HTTP_DOCUMENTS.set(Some(HashMap::new()));
+        CacheGuard
+    };
+    action()
+}
+
+#[derive(Debug, Clone)]
 pub(crate) struct Fallbacks {
-    inner: HashMap<(String, &'static str), WithOrigin>,
+    inner: BTreeMap<(String, &'static str), WithOrigin>,
     origin: Arc<ValueOrigin>,
 }
 
 impl Fallbacks {
-    #[tracing::instrument(level = "debug", name = "Fallbacks::new", skip_all)]
-    pub(crate) fn new(schema: &ConfigSchema) -> Option<Self> {
-        let mut inner = HashMap::new();
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", name = "Fallbacks::new_filtered", skip_all)
+    )]
+    pub(crate) fn new_filtered(
+        schema: &ConfigSchema,
+        priority: FallbackPriority,
+        filter: &dyn Fn(&str) -> bool,
+    ) -> Option<Self> {
+        let mut inner = BTreeMap::new();
         for (prefix, config) in schema.iter_ll() {
+            if !filter(prefix.0) {
+                tracing::trace!(prefix = prefix.0, "skipped fallbacks for config");
+                continue;
+            }
             for param in config.metadata.params {
                 let Some(fallback) = param.fallback else {
                     continue;
                 };
+                if fallback.priority() != priority {
+                    continue;
+                }
                 if let Some(mut val) = fallback.provide_value() {
                     tracing::trace!(
                         prefix = prefix.0,
@@ -201,13 +713,14 @@ impl Fallbacks {
                         "got fallback for param"
                     );
 
-                    let origin = ValueOrigin::Synthetic {
+                    let origin = ValueOrigin::Fallback {
                         source: val.origin.clone(),
-                        transform: format!(
-                            "fallback for `{}.{}`",
+                        param: format!(
+                            "{}.{}",
                             config.metadata.ty.name_in_code(),
                             param.rust_field_name,
                         ),
+                        priority,
                     };
                     val.origin = Arc::new(origin);
                     inner.insert((prefix.0.to_owned(), param.name), val);
@@ -51,6 +51,26 @@
 //! Implements deserialization for basic Ethereum types like [`H256`](primitive_types::H256) (32-byte hash)
 //! and [`U256`](primitive_types::U256) (256-bit unsigned integer).
 //!
+//! ## `http`
+//!
+//! *(Off by default)*
+//!
+//! Provides the [`Http`] configuration source fetching configuration from an HTTP endpoint.
+//! Also provides the [`fallback::Http`] fallback reading individual param values from a JSON document served over HTTP.
+//!
+//! ## `tracing`
+//!
+//! *(On by default)*
+//!
+//! Emits [`tracing`](https://docs.rs/tracing/) spans and events during config parsing.
+//! If disabled, the crate doesn't depend on `tracing`; parsing behavior is otherwise identical.
+//!
+//! ## `toml`
+//!
+//! *(Off by default)*
+//!
+//! Provides the [`Toml`] configuration source.
+//!
 //! # Examples
 //!
 //! ## Basic workflow
@@ -157,6 +177,9 @@
 ///
 /// Specifies a post-deserialization validation for the config. This is useful to check invariants involving multiple params.
 /// Multiple validations are supported by specifying the attribute multiple times.
+/// Config validations run after all params are deserialized. Errors produced by them are attributed to the config as a whole,
+/// i.e., have [`ParseError::param()`] set to `None`, [`ParseError::path()`] equal to the config prefix,
+/// and [`ParseError::validation()`] set to the validation description.
 ///
 /// ## `tag`
 ///
@@ -190,6 +213,44 @@
 /// Derives `Default` according to the default values of params (+ the default variant for enum configs).
 /// To work, all params must have a default value specified.
 ///
+/// ## `deny_unknown`
+///
+/// **Type:** flag
+///
+/// Makes parsing fail if the config object contains [unknown keys](ConfigRepository::unknown_keys()), i.e. keys
+/// not corresponding to any param or sub-config, regardless of the [global policy](de::DeserializerOptions::unknown_keys)
+/// for unknown keys. This is similar to `#[serde(deny_unknown_fields)]`. Unknown keys are reported both when parsing
+/// the config itself, and when parsing any config containing it as a sub-config.
+///
+/// ## `post_parse`
+///
+/// **Type:** path to a function with the `fn(&mut Self) -> Result<(), ErrorWithOrigin>` signature
+///
+/// Specifies a transform applied to the config after it is deserialized and all [validations](#validate) pass,
+/// e.g. to normalize param values (lowercase a hostname, expand `~` in paths, etc.). An error returned by the function
+/// is reported similarly to config validation errors, with [`ParseError::validation()`] set to `post-parse transform`.
+///
+/// ```
+/// # use smart_config::{testing, DescribeConfig, DeserializeConfig, ErrorWithOrigin};
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// #[config(post_parse = Self::normalize)]
+/// struct ServerConfig {
+///     host: String,
+/// }
+///
+/// impl ServerConfig {
+///     fn normalize(&mut self) -> Result<(), ErrorWithOrigin> {
+///         self.host.make_ascii_lowercase();
+///         Ok(())
+///     }
+/// }
+///
+/// let input = smart_config::config!("host": "LocalHost");
+/// let config: ServerConfig = testing::test(input)?;
+/// assert_eq!(config.host, "localhost");
+/// # anyhow::Ok(())
+/// ```
+///
 /// # Variant attributes
 ///
 /// ## `rename`, `alias`
@@ -230,7 +291,8 @@
 /// **Type:** string
 ///
 /// Similar to `alias`, with the difference that the alias is marked as deprecated in the schema docs,
-/// and its usages are logged on the `WARN` level.
+/// and its usages are logged on the `WARN` level. Additionally, parsing a config with a param provided via a deprecated alias
+/// produces a [warning](Severity::Warning) pointing to the canonical param path.
 ///
 /// ## `default`
 ///
@@ -281,6 +343,23 @@
 /// ## `validate`
 ///
 /// Has same semantics as [config validations](#validate), but applies to a specific config parameter.
+/// Errors produced by param validations point to the origin of the param value.
+///
+/// ## `range`
+///
+/// **Type:** range expression, e.g. `1024..=65535` or `0.0..1.0`
+///
+/// Checks that an integer or floating-point param is in the specified range. Unlike the equivalent [`validate`](#validate-1)
+/// attribute, the range is captured in [`ParamMetadata::range()`](metadata::ParamMetadata::range()).
+/// The corresponding [`ParseError::validation()`] is e.g. `"range 1024..=65535"`.
+///
+/// ## `non_empty`
+///
+/// **Type:** flag
+///
+/// Shortcut for `#[config(validate(validation::NotEmpty))]`, i.e., checks that a string or a collection (such as `Vec`
+/// or `HashMap`) param is not empty. Unlike the equivalent [`validate`](#validate-1) attribute, the corresponding
+/// [`ParseError::validation()`] is `"non_empty"`.
 ///
 /// ## `deserialize_if`
 ///
@@ -290,6 +369,50 @@
 /// to `None` in the case [automated null coercion](crate::de::Optional#encoding-nulls) doesn't apply.
 /// See the [`validation`] module for examples of usage.
 ///
+/// ## `since`
+///
+/// **Type:** string
+///
+/// Specifies the version in which the param was introduced, e.g. `since = "0.4.0"`. The version is not interpreted
+/// by the library; it's available via [`ParamMetadata::since()`](metadata::ParamMetadata::since()) and is output in the param help.
+///
+/// ## `unit`
+///
+/// **Type:** string
+///
+/// Specifies a human-readable unit of the param value, e.g. `unit = "seconds"`. The unit is purely informational
+/// (e.g., for generated docs); it's available via [`ParamMetadata::unit()`](metadata::ParamMetadata::unit()).
+/// To influence how a param is deserialized, use [`TimeUnit`](metadata::TimeUnit) / [`SizeUnit`](metadata::SizeUnit) instead.
+///
+/// ## `examples`
+///
+/// **Type:** comma-separated string literals
+///
+/// Specifies example raw values for the param, e.g. `examples("30", "1m")`. The examples are purely informational
+/// and are not type-checked; they're available via [`ParamMetadata::examples()`](metadata::ParamMetadata::examples()).
+/// Unlike [`example`](#example), they can be specified multiple times and do not require the param type to be constructed.
+///
+/// ## `env_only`, `file_only`
+///
+/// **Type:** flag
+///
+/// Restricts the param value to be obtained only from env variables or files, respectively. If the value
+/// is obtained from another [kind of source](value::OriginKind), e.g. if an `env_only` param is set in a JSON file,
+/// parsing will fail with an error. This restriction applies to the value after merging all sources;
+/// for example, an env variable [fallback](#fallback) satisfies the `env_only` restriction.
+///
+/// ## `rest`
+///
+/// **Type:** flag
+///
+/// Marks the param as capturing all entries in the config object not claimed by other params or sub-configs
+/// (including their aliases), similar to `#[serde(flatten)]` applied to a map. The param must accept objects;
+/// a typical choice is `HashMap<String, String>` with `#[config(default)]`. Values for the param from multiple sources
+/// are merged rather than overwritten. Only keys in hierarchical sources (e.g., JSON or YAML files) are captured;
+/// env vars not corresponding to any param are ignored as usual.
+///
+/// A config can have at most one `rest` param; this is checked when the config is inserted into a [`ConfigSchema`].
+///
 /// # Validations
 ///
 /// The following validations are performed by the macro in compile time:
@@ -407,13 +530,26 @@ pub use smart_config_derive::DeserializeConfig;
 /// ```
 pub use smart_config_derive::ExampleConfig;
 
+#[cfg(feature = "toml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+pub use self::source::Toml;
+#[cfg(feature = "http")]
+#[cfg_attr(docsrs, doc(cfg(feature = "http")))]
+pub use self::source::{Http, HttpRequest, HttpSourceError, RetryPolicy};
 pub use self::{
     de::DeserializeConfig,
-    error::{DeserializeConfigError, ErrorWithOrigin, ParseError, ParseErrorCategory, ParseErrors},
-    schema::{ConfigMut, ConfigRef, ConfigSchema},
+    error::{
+        DeserializeConfigError, ErrorKind, ErrorWithOrigin, ParseError, ParseErrorCategory,
+        ParseErrors, Severity,
+    },
+    schema::{
+        AliasConflict, ConfigMut, ConfigRef, ConfigSchema, Deprecation, FrozenSchema, SchemaDiff,
+        SchemaVisitor,
+    },
     source::{
-        ConfigParser, ConfigRepository, ConfigSource, ConfigSourceKind, ConfigSources, Environment,
-        Flat, Hierarchical, Json, Prefixed, SerializerOptions, SourceInfo, Yaml,
+        CliOverrides, ConfigParser, ConfigRepository, ConfigSource, ConfigSourceKind,
+        ConfigSources, Environment, Flat, Hierarchical, Json, MergeStrategy, NestedEnvironment,
+        ParseStats, ParsedConfigs, Prefixed, SerializerOptions, SourceInfo, StripNulls, Yaml,
     },
     types::ByteSize,
 };
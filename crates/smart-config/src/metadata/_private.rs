@@ -7,6 +7,7 @@ use compile_fmt::{clip, clip_ascii, compile_args, compile_panic, Ascii, CompileA
 use super::{ConfigMetadata, NestedConfigMetadata, ParamMetadata};
 use crate::{
     de::DeserializeContext,
+    error::ErrorWithOrigin,
     utils::const_eq,
     visit::{ConfigVisitor, VisitConfig},
     DeserializeConfig, DeserializeConfigError,
@@ -17,6 +18,8 @@ pub type BoxedDeserializer =
 
 pub type BoxedVisitor = fn(&dyn any::Any, &mut dyn ConfigVisitor);
 
+pub type BoxedPostParse = fn(&mut dyn any::Any) -> Result<(), ErrorWithOrigin>;
+
 pub const fn box_config_visitor<T: VisitConfig + 'static>() -> BoxedVisitor {
     |boxed_config, visitor| {
         let config = boxed_config
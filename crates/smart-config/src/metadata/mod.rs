@@ -1,12 +1,13 @@
 //! Configuration metadata.
 
-use std::{any, borrow::Cow, fmt, ops, time::Duration};
+use std::{any, borrow::Cow, fmt, ops, str::FromStr, time::Duration};
 
-use self::_private::{BoxedDeserializer, BoxedVisitor};
+use self::_private::{BoxedDeserializer, BoxedPostParse, BoxedVisitor};
 use crate::{
-    de::{DeserializeParam, _private::ErasedDeserializer},
+    de::{_private::ErasedDeserializer, DeserializeParam},
     fallback::FallbackSource,
     validation::Validate,
+    value::OriginKind,
 };
 
 #[doc(hidden)] // used in the derive macros
@@ -16,11 +17,31 @@ mod tests;
 
 /// Options for a param or config alias.
 #[derive(Debug, Clone, Copy)]
-#[cfg_attr(test, derive(PartialEq))]
 #[non_exhaustive]
 pub struct AliasOptions {
     /// Is this alias deprecated?
     pub is_deprecated: bool,
+    /// Version since which the alias is deprecated, if known.
+    pub deprecated_since: Option<&'static str>,
+    /// Version in which the alias is planned to be removed, if known.
+    pub remove_in: Option<&'static str>,
+    /// Condition for the alias to be active, e.g. a check for a feature flag. If the condition returns `false`,
+    /// the alias is ignored.
+    ///
+    /// The condition is evaluated each time the alias is resolved when parsing config sources
+    /// (i.e., on [`ConfigRepository::with()`](crate::ConfigRepository::with())), not when the alias is inserted into the schema.
+    pub condition: Option<fn() -> bool>,
+}
+
+#[cfg(test)]
+impl PartialEq for AliasOptions {
+    fn eq(&self, other: &Self) -> bool {
+        // Function pointers cannot be meaningfully compared, so we only check condition presence.
+        self.is_deprecated == other.is_deprecated
+            && self.deprecated_since == other.deprecated_since
+            && self.remove_in == other.remove_in
+            && self.condition.is_some() == other.condition.is_some()
+    }
 }
 
 impl Default for AliasOptions {
@@ -34,6 +55,9 @@ impl AliasOptions {
     pub const fn new() -> Self {
         AliasOptions {
             is_deprecated: false,
+            deprecated_since: None,
+            remove_in: None,
+            condition: None,
         }
     }
 
@@ -44,11 +68,60 @@ impl AliasOptions {
         self
     }
 
+    /// Marks the alias as deprecated since the specified version. The version is included into the warning
+    /// produced when the alias is used.
+    #[must_use]
+    pub const fn deprecated_since(mut self, version: &'static str) -> Self {
+        self.is_deprecated = true;
+        self.deprecated_since = Some(version);
+        self
+    }
+
+    /// Marks the alias as deprecated and planned to be removed in the specified version. The version is included
+    /// into the warning produced when the alias is used.
+    #[must_use]
+    pub const fn remove_in(mut self, version: &'static str) -> Self {
+        self.is_deprecated = true;
+        self.remove_in = Some(version);
+        self
+    }
+
+    /// Makes the alias conditional on the specified check, e.g. whether a certain feature flag is set.
+    /// See [`Self::condition`] for details on when the condition is evaluated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::atomic::{AtomicBool, Ordering};
+    /// # use smart_config::metadata::AliasOptions;
+    /// static LEGACY_NAMES: AtomicBool = AtomicBool::new(false);
+    ///
+    /// let options = AliasOptions::new().with_condition(|| LEGACY_NAMES.load(Ordering::Relaxed));
+    /// assert!(!options.is_active());
+    /// LEGACY_NAMES.store(true, Ordering::Relaxed);
+    /// assert!(options.is_active());
+    /// ```
+    #[must_use]
+    pub const fn with_condition(mut self, condition: fn() -> bool) -> Self {
+        self.condition = Some(condition);
+        self
+    }
+
+    /// Checks whether the alias is currently active, i.e. has no [condition](Self::condition) or the condition holds.
+    pub fn is_active(&self) -> bool {
+        self.condition.map_or(true, |condition| condition())
+    }
+
     #[doc(hidden)] // not stable yet
     #[must_use]
     pub fn combine(self, other: Self) -> Self {
         Self {
             is_deprecated: self.is_deprecated || other.is_deprecated,
+            deprecated_since: self.deprecated_since.or(other.deprecated_since),
+            remove_in: self.remove_in.or(other.remove_in),
+            // Function pointers cannot be composed, so the more specific condition wins. The schema separately
+            // keeps track of conditions of all combined aliases.
+            condition: self.condition.or(other.condition),
         }
     }
 }
@@ -60,18 +133,27 @@ pub struct ConfigMetadata {
     pub ty: RustType,
     /// Help regarding the config itself.
     pub help: &'static str,
+    /// Doc comment of the config type preserved verbatim (e.g., with line breaks and Markdown formatting intact).
+    /// Unlike [`Self::help`], lines within a paragraph are not joined.
+    pub doc: &'static str,
     /// Parameters included in the config.
     pub params: &'static [ParamMetadata],
     /// Tag for enumeration configs.
     pub tag: Option<ConfigTag>,
     /// Nested configs included in the config.
     pub nested_configs: &'static [NestedConfigMetadata],
+    /// Whether the config denies [unknown keys](crate::ConfigRepository::unknown_keys()) in its object
+    /// regardless of the [global policy](crate::de::DeserializerOptions::unknown_keys).
+    /// Set via the `#[config(deny_unknown)]` container attribute.
+    pub deny_unknown: bool,
     #[doc(hidden)] // implementation detail
     pub deserializer: BoxedDeserializer,
     #[doc(hidden)] // implementation detail
     pub visitor: BoxedVisitor,
     #[doc(hidden)] // implementation detail
     pub validations: &'static [&'static dyn Validate<dyn any::Any>],
+    #[doc(hidden)] // implementation detail
+    pub post_parse: Option<BoxedPostParse>,
 }
 
 /// Information about a config tag.
@@ -123,9 +205,55 @@ pub struct ParamMetadata {
     pub example_value: Option<fn() -> Box<dyn any::Any>>,
     #[doc(hidden)]
     pub fallback: Option<&'static dyn FallbackSource>,
+    #[doc(hidden)] // use the `since()` getter
+    pub since: Option<&'static str>,
+    #[doc(hidden)] // use the `unit()` getter
+    pub unit: Option<&'static str>,
+    #[doc(hidden)] // use the `examples()` getter
+    pub examples: &'static [&'static str],
+    #[doc(hidden)] // use the `allowed_origin()` getter
+    pub allowed_origin: Option<OriginKind>,
+    #[doc(hidden)] // use the `is_rest()` getter
+    pub is_rest: bool,
+    #[doc(hidden)] // use the `range()` getter
+    pub range: Option<&'static str>,
 }
 
 impl ParamMetadata {
+    /// Returns the version in which the param was introduced, as specified via `#[config(since = ..)]`.
+    pub fn since(&self) -> Option<&'static str> {
+        self.since
+    }
+
+    /// Returns the human-readable unit of the param value (e.g., `seconds` or `bytes`), as specified via `#[config(unit = ..)]`.
+    /// The unit is purely informational; it doesn't influence param deserialization.
+    pub fn unit(&self) -> Option<&'static str> {
+        self.unit
+    }
+
+    /// Returns example raw values for the param, as specified via `#[config(examples(..))]`. The examples are purely
+    /// informational; unlike the [example value](Self::example_value_json()), they are not type-checked.
+    pub fn examples(&self) -> &'static [&'static str] {
+        self.examples
+    }
+
+    /// Returns the only kind of sources the param value can be obtained from, as specified
+    /// via `#[config(env_only)]` / `#[config(file_only)]`.
+    pub fn allowed_origin(&self) -> Option<OriginKind> {
+        self.allowed_origin
+    }
+
+    /// Checks whether this param captures all config entries not claimed by other params, as specified
+    /// via `#[config(rest)]`.
+    pub fn is_rest(&self) -> bool {
+        self.is_rest
+    }
+
+    /// Returns the range of allowed param values (e.g., `1024..=65535`), as specified via `#[config(range = ..)]`.
+    pub fn range(&self) -> Option<&'static str> {
+        self.range
+    }
+
     /// Returns the default value for the param.
     pub fn default_value(&self) -> Option<Box<dyn any::Any>> {
         self.default_value.map(|value_fn| value_fn())
@@ -193,6 +321,21 @@ impl RustType {
 }
 
 /// Set of one or more basic types in the JSON object model.
+///
+/// The set is displayed as a `|`-separated list of types, e.g. `integer | string`, or as `any` if it contains
+/// all types. The same format can be parsed back via the [`FromStr`] implementation.
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::metadata::BasicTypes;
+/// let types = BasicTypes::INTEGER.or(BasicTypes::STRING);
+/// assert_eq!(types.to_string(), "integer | string");
+/// assert_eq!("integer | string".parse::<BasicTypes>()?, types);
+/// assert!(types.contains(BasicTypes::STRING));
+/// assert_eq!("any".parse::<BasicTypes>()?, BasicTypes::ANY);
+/// # anyhow::Ok(())
+/// ```
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct BasicTypes(u8);
 
@@ -267,6 +410,31 @@ impl fmt::Display for BasicTypes {
     }
 }
 
+impl FromStr for BasicTypes {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTED: &str = "any, Boolean, integer, float, string, array, object";
+
+        let mut raw = 0;
+        for part in s.split('|') {
+            let part = part.trim();
+            if part.eq_ignore_ascii_case("any") {
+                raw |= Self::ANY.0;
+                continue;
+            }
+            let (component, _) = Self::COMPONENTS
+                .iter()
+                .find(|(_, name)| part.eq_ignore_ascii_case(name))
+                .ok_or_else(|| {
+                    anyhow::anyhow!("unknown basic type `{part}`, expected one of: {EXPECTED}")
+                })?;
+            raw |= component.0;
+        }
+        Ok(Self(raw))
+    }
+}
+
 impl fmt::Debug for BasicTypes {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Display::fmt(self, formatter)
@@ -486,6 +654,8 @@ pub struct NestedConfigMetadata {
     pub rust_field_name: &'static str,
     /// Tag variant in the enclosing [`ConfigMetadata`] that enables this parameter. `None` means that the parameter is unconditionally enabled.
     pub tag_variant: Option<&'static ConfigVariant>,
+    /// Whether the config may be absent from config sources, i.e., it's wrapped in an `Option` or has a default value.
+    pub is_optional: bool,
     /// Config metadata.
     pub meta: &'static ConfigMetadata,
 }
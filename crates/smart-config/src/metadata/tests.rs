@@ -168,3 +168,35 @@ fn suffixes_for_composed_params() {
     let ty = dur_param.type_description();
     assert_matches!(ty.suffixes, None);
 }
+
+#[test]
+fn basic_types_display_and_parsing() {
+    let samples = [
+        (BasicTypes::BOOL, "Boolean"),
+        (BasicTypes::FLOAT, "integer | float"),
+        (
+            BasicTypes::INTEGER.or(BasicTypes::STRING),
+            "integer | string",
+        ),
+        (BasicTypes::ARRAY.or(BasicTypes::OBJECT), "array | object"),
+        (BasicTypes::ANY, "any"),
+    ];
+    for (types, expected) in samples {
+        assert_eq!(types.to_string(), expected);
+        assert_eq!(expected.parse::<BasicTypes>().unwrap(), types);
+    }
+
+    assert_eq!(
+        "boolean|String".parse::<BasicTypes>().unwrap(),
+        BasicTypes::BOOL.or(BasicTypes::STRING)
+    );
+    assert_eq!("float".parse::<BasicTypes>().unwrap(), BasicTypes::FLOAT);
+    assert!(BasicTypes::FLOAT.contains(BasicTypes::INTEGER));
+    assert!(!BasicTypes::INTEGER.contains(BasicTypes::FLOAT));
+
+    for invalid in ["", "int", "string |"] {
+        let err = invalid.parse::<BasicTypes>().unwrap_err().to_string();
+        assert!(err.starts_with("unknown basic type"), "{err}");
+        assert!(err.contains("expected one of: any, Boolean"), "{err}");
+    }
+}
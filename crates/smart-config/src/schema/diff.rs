@@ -0,0 +1,77 @@
+//! Comparing config schemas.
+
+use std::collections::BTreeMap;
+
+use super::{ConfigRef, ConfigSchema};
+use crate::metadata::{BasicTypes, ParamMetadata};
+
+type ParamsByPath<'a> = BTreeMap<String, (ConfigRef<'a>, &'static ParamMetadata)>;
+
+/// Difference between two [`ConfigSchema`]s returned by [`ConfigSchema::diff()`].
+///
+/// Params are compared by their canonical paths; aliases are ignored. All iterators are ordered by the canonical path.
+#[derive(Debug)]
+pub struct SchemaDiff<'a> {
+    old_params: ParamsByPath<'a>,
+    new_params: ParamsByPath<'a>,
+}
+
+impl<'a> SchemaDiff<'a> {
+    fn params_by_path(schema: &'a ConfigSchema) -> ParamsByPath<'a> {
+        let mut params = BTreeMap::new();
+        for (path, config, param) in schema.iter_params() {
+            params.entry(path).or_insert((config, param));
+        }
+        params
+    }
+
+    pub(super) fn new(old: &'a ConfigSchema, new: &'a ConfigSchema) -> Self {
+        Self {
+            old_params: Self::params_by_path(old),
+            new_params: Self::params_by_path(new),
+        }
+    }
+
+    /// Iterates over params present in the new schema, but not in the old one.
+    pub fn added_params(
+        &self,
+    ) -> impl Iterator<Item = (&str, ConfigRef<'a>, &'static ParamMetadata)> + '_ {
+        Self::difference(&self.new_params, &self.old_params)
+    }
+
+    /// Iterates over params present in the old schema, but not in the new one.
+    pub fn removed_params(
+        &self,
+    ) -> impl Iterator<Item = (&str, ConfigRef<'a>, &'static ParamMetadata)> + '_ {
+        Self::difference(&self.old_params, &self.new_params)
+    }
+
+    fn difference<'s>(
+        lhs: &'s ParamsByPath<'a>,
+        rhs: &'s ParamsByPath<'a>,
+    ) -> impl Iterator<Item = (&'s str, ConfigRef<'a>, &'static ParamMetadata)> + 's {
+        lhs.iter().filter_map(|(path, &(config, param))| {
+            (!rhs.contains_key(path)).then_some((path.as_str(), config, param))
+        })
+    }
+
+    /// Iterates over params present in both schemas for which the expected types differ. Returns the canonical path
+    /// together with the old and new expected types.
+    pub fn changed_types(&self) -> impl Iterator<Item = (&str, BasicTypes, BasicTypes)> + '_ {
+        self.old_params.iter().filter_map(|(path, (_, old_param))| {
+            let (_, new_param) = self.new_params.get(path)?;
+            (old_param.expecting != new_param.expecting).then_some((
+                path.as_str(),
+                old_param.expecting,
+                new_param.expecting,
+            ))
+        })
+    }
+
+    /// Checks whether the schemas have no differences.
+    pub fn is_empty(&self) -> bool {
+        self.added_params().next().is_none()
+            && self.removed_params().next().is_none()
+            && self.changed_types().next().is_none()
+    }
+}
@@ -0,0 +1,88 @@
+//! Exporting config schemas as Graphviz graphs.
+
+use std::{collections::HashMap, fmt::Write as _};
+
+use super::{ConfigRef, ConfigSchema};
+
+impl ConfigSchema {
+    /// Exports configs in this schema and their nesting relationships as a [Graphviz](https://graphviz.org/) `digraph`
+    /// in the DOT format.
+    ///
+    /// - Each config is represented by a box node labeled with the config [name in code](crate::metadata::RustType::name_in_code())
+    ///   and its canonical prefix.
+    /// - Each nested config is linked to its parent with an edge labeled with the nested config name
+    ///   (or `(flattened)` for flattened configs).
+    /// - Aliases for top-level configs are represented by ellipse nodes linked to the aliased config
+    ///   with dotted edges. Deprecated aliases are rendered with dashed outlines.
+    ///
+    /// Nodes are output in the same order as in [`Self::iter()`], so the output is deterministic.
+    pub fn to_dot(&self) -> String {
+        let mut node_ids = HashMap::new();
+        let mut dot = String::from("digraph schema {\n    node [shape=box];\n");
+        for (i, config) in self.iter().enumerate() {
+            node_ids.insert(Self::dot_key(config), i);
+            let name = config.metadata().ty.name_in_code();
+            let prefix = match config.prefix() {
+                "" => "(root)",
+                prefix => prefix,
+            };
+            let label = escape_dot(&format!("{name}\n{prefix}"));
+            writeln!(dot, "    config{i} [label=\"{label}\"];").unwrap();
+        }
+
+        let mut alias_count = 0;
+        for config in self.iter() {
+            let id = node_ids[&Self::dot_key(config)];
+            if let Some((parent, nested)) = config.parent_link() {
+                let parent_id = node_ids[&Self::dot_key(parent)];
+                let label = if nested.name.is_empty() {
+                    "(flattened)"
+                } else {
+                    nested.name
+                };
+                let label = escape_dot(label);
+                writeln!(
+                    dot,
+                    "    config{parent_id} -> config{id} [label=\"{label}\"];"
+                )
+                .unwrap();
+                continue;
+            }
+
+            for (alias, options) in config.aliases() {
+                let label = escape_dot(alias);
+                let style = if options.is_deprecated {
+                    ", style=dashed"
+                } else {
+                    ""
+                };
+                writeln!(
+                    dot,
+                    "    alias{alias_count} [label=\"{label}\", shape=ellipse{style}];"
+                )
+                .unwrap();
+                writeln!(dot, "    alias{alias_count} -> config{id} [style=dotted];").unwrap();
+                alias_count += 1;
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn dot_key(config: ConfigRef<'_>) -> (&str, std::any::TypeId) {
+        (config.prefix(), config.metadata().ty.id())
+    }
+}
+
+fn escape_dot(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
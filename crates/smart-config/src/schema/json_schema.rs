@@ -0,0 +1,110 @@
+//! Exporting config schemas as JSON Schema.
+
+use serde_json::{json, Map, Value as JsonValue};
+
+use super::ConfigSchema;
+use crate::metadata::{BasicTypes, ParamMetadata};
+
+impl ConfigSchema {
+    /// Exports this schema as a [JSON Schema](https://json-schema.org/) describing the canonical (hierarchical)
+    /// config layout.
+    ///
+    /// - Each config prefix is represented by a nested `object` schema.
+    /// - Each param is represented by a schema with `type` derived from [expected types](crate::metadata::BasicTypes)
+    ///   (omitted if the param accepts any value), `description` taken from the param help, and `default` if the param
+    ///   has a default value.
+    /// - The [version the param was introduced in](crate::metadata::ParamMetadata::since()) is recorded
+    ///   in the `x-since` annotation.
+    ///
+    /// Params are output in the same order as in [`Self::iter()`] (as long as `serde_json` preserves insertion order).
+    pub fn to_json_schema(&self) -> JsonValue {
+        let mut root = object_schema();
+        for config in self.iter() {
+            let path: Vec<_> = config
+                .prefix()
+                .split('.')
+                .filter(|segment| !segment.is_empty())
+                .collect();
+            let Some(target) = object_at_path(&mut root, &path) else {
+                continue; // should not happen since the schema is checked for conflicts on insertion
+            };
+            if !config.metadata().help.is_empty() && !target.contains_key("description") {
+                target.insert("description".into(), config.metadata().help.into());
+            }
+            let Some(JsonValue::Object(properties)) = target.get_mut("properties") else {
+                continue;
+            };
+            for param in config.metadata().params {
+                properties.insert(param.name.to_owned(), param_schema(param));
+            }
+        }
+        root["$schema"] = "https://json-schema.org/draft/2020-12/schema".into();
+        root
+    }
+}
+
+fn object_schema() -> JsonValue {
+    json!({ "type": "object", "properties": {} })
+}
+
+fn object_at_path<'a>(
+    schema: &'a mut JsonValue,
+    path: &[&str],
+) -> Option<&'a mut Map<String, JsonValue>> {
+    let JsonValue::Object(object) = schema else {
+        return None;
+    };
+    let Some((first, rest)) = path.split_first() else {
+        return Some(object);
+    };
+    let JsonValue::Object(properties) = object.get_mut("properties")? else {
+        return None;
+    };
+    let child = properties.entry(*first).or_insert_with(object_schema);
+    object_at_path(child, rest)
+}
+
+fn param_schema(param: &ParamMetadata) -> JsonValue {
+    let mut schema = Map::new();
+    if param.expecting != BasicTypes::ANY {
+        let types = json_types(param.expecting);
+        let ty = if types.len() == 1 {
+            types[0].into()
+        } else {
+            types.into()
+        };
+        schema.insert("type".into(), ty);
+    }
+    if !param.help.is_empty() {
+        schema.insert("description".into(), param.help.into());
+    }
+    if let Some(default) = param.default_value_json() {
+        schema.insert("default".into(), default);
+    }
+    if let Some(since) = param.since() {
+        schema.insert("x-since".into(), since.into());
+    }
+    schema.into()
+}
+
+fn json_types(expecting: BasicTypes) -> Vec<&'static str> {
+    let mut types = vec![];
+    if expecting.contains(BasicTypes::BOOL) {
+        types.push("boolean");
+    }
+    if expecting.contains(BasicTypes::FLOAT) {
+        types.push("number");
+    } else if expecting.contains(BasicTypes::INTEGER) {
+        types.push("integer");
+    }
+    if expecting.contains(BasicTypes::STRING) {
+        types.push("string");
+    }
+    if expecting.contains(BasicTypes::ARRAY) {
+        types.push("array");
+    }
+    if expecting.contains(BasicTypes::OBJECT) {
+        types.push("object");
+    }
+    types
+}
@@ -0,0 +1,78 @@
+//! Exporting config schemas as Markdown reference docs.
+
+use std::fmt::Write as _;
+
+use super::ConfigSchema;
+
+impl ConfigSchema {
+    /// Exports params in this schema as Markdown reference docs.
+    ///
+    /// Each config with params is rendered as a section headed by its canonical prefix, followed by a table
+    /// with a row per param. The table lists the canonical param path, [expected types](crate::metadata::BasicTypes),
+    /// the default value (if any), the [version the param was introduced in](crate::metadata::ParamMetadata::since())
+    /// and the param help. Deprecated aliases are listed in the help cell.
+    ///
+    /// Sections are output in the same order as in [`Self::iter()`], so the output is deterministic.
+    pub fn to_markdown(&self) -> String {
+        let mut md = String::new();
+        for config in self.iter() {
+            let metadata = config.metadata();
+            if metadata.params.is_empty() {
+                continue;
+            }
+
+            let prefix = match config.prefix() {
+                "" => "(root)",
+                prefix => prefix,
+            };
+            writeln!(md, "## `{prefix}`\n").unwrap();
+            if !metadata.help.is_empty() {
+                writeln!(md, "{}\n", metadata.help).unwrap();
+            }
+
+            md.push_str("| Param | Type | Default | Since | Description |\n");
+            md.push_str("|-------|------|---------|-------|-------------|\n");
+            for param in metadata.params {
+                let path = if config.prefix().is_empty() {
+                    param.name.to_owned()
+                } else {
+                    format!("{}.{}", config.prefix(), param.name)
+                };
+                let default = param
+                    .default_value_json()
+                    .map_or_else(String::new, |value| format!("`{value}`"));
+                let since = param.since().unwrap_or("");
+
+                let mut help = param.help.to_owned();
+                let deprecated: Vec<_> = param
+                    .aliases
+                    .iter()
+                    .filter(|(_, options)| options.is_deprecated)
+                    .map(|(alias, _)| format!("`{alias}`"))
+                    .collect();
+                if !deprecated.is_empty() {
+                    if !help.is_empty() {
+                        help.push(' ');
+                    }
+                    write!(help, "Deprecated aliases: {}.", deprecated.join(", ")).unwrap();
+                }
+
+                writeln!(
+                    md,
+                    "| `{path}` | {types} | {default} | {since} | {help} |",
+                    types = param.expecting,
+                    default = escape_cell(&default),
+                    since = escape_cell(since),
+                    help = escape_cell(&help)
+                )
+                .unwrap();
+            }
+            md.push('\n');
+        }
+        md
+    }
+}
+
+fn escape_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', "<br>")
+}
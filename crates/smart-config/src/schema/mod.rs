@@ -3,25 +3,38 @@
 use std::{
     any,
     borrow::Cow,
-    collections::{BTreeMap, BTreeSet, HashMap},
-    iter,
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fmt::Write as _,
+    hash, iter, ops, ptr,
+    sync::Arc,
 };
 
 use anyhow::Context;
 
 use self::mount::{MountingPoint, MountingPoints};
+pub use self::{diff::SchemaDiff, visit::SchemaVisitor};
+use serde::de::Error as DeError;
+
 use crate::{
+    error::{ErrorKind, LocationInConfig, ParseErrorCategory},
     metadata::{
         AliasOptions, BasicTypes, ConfigMetadata, ConfigVariant, NestedConfigMetadata,
-        ParamMetadata,
+        ParamMetadata, TypeSuffixes,
     },
+    source::Json,
     utils::EnumVariant,
-    value::Pointer,
+    value::{Pointer, Value, ValueOrigin, WithOrigin},
+    ConfigRepository, ConfigSource, Environment, ParseError, ParseErrors, Severity,
 };
 
+mod diff;
+mod dot;
+mod json_schema;
+mod markdown;
 mod mount;
 #[cfg(test)]
 mod tests;
+mod visit;
 
 #[derive(Debug, Clone, Copy)]
 struct ParentLink {
@@ -35,7 +48,92 @@ pub(crate) struct ConfigData {
     parent_link: Option<ParentLink>,
     pub(crate) is_top_level: bool,
     pub(crate) coerce_serde_enums: bool,
-    all_paths: Vec<(Cow<'static, str>, AliasOptions)>,
+    all_paths: Vec<(Cow<'static, str>, MountOptions)>,
+    deprecation: Option<&'static str>,
+}
+
+/// Suffixes that may be claimed by a child of a config object; see [`ClaimedKeys`].
+#[derive(Debug, Clone, Copy)]
+enum ClaimedSuffixes {
+    Any,
+    Param {
+        array_indices: bool,
+        suffixes: Option<TypeSuffixes>,
+    },
+}
+
+/// Keys in a config object claimed by params or sub-configs (including via aliases). Precomputed for a config object
+/// so that checking a key doesn't require resolving params.
+#[derive(Debug)]
+pub(crate) struct ClaimedKeys<'a> {
+    children: HashMap<&'a str, ClaimedSuffixes>,
+}
+
+impl ClaimedKeys<'_> {
+    /// Checks whether a key is claimed. Keys that may be nested into a param or a sub-config, like `timeout_ms`
+    /// for the `timeout` param, are considered claimed. For params, this only applies to suffixes supported
+    /// by the param type (i.e., units of measurement, or item indices for array params).
+    pub(crate) fn contains(&self, key: &str) -> bool {
+        if self.children.contains_key(key) {
+            return true;
+        }
+        key.match_indices('_').any(|(pos, _)| {
+            let (child, suffix) = (&key[..pos], &key[pos + 1..]);
+            if suffix.is_empty() {
+                return false;
+            }
+            match self.children.get(child) {
+                None => false,
+                Some(ClaimedSuffixes::Any) => true,
+                Some(ClaimedSuffixes::Param {
+                    array_indices,
+                    suffixes,
+                }) => {
+                    (*array_indices && suffix.parse::<usize>().is_ok())
+                        || suffixes.is_some_and(|suffixes| suffixes.contains(suffix))
+                }
+            }
+        })
+    }
+}
+
+/// [`AliasOptions`] for a path at which a config or param is mounted, combined from the options of the param alias (if any)
+/// and of the aliases of all enclosing configs. Since function pointers cannot be composed, conditions of all combined
+/// aliases are retained separately; the path is active only if all of them hold.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MountOptions {
+    pub(crate) alias: AliasOptions,
+    conditions: Vec<fn() -> bool>,
+}
+
+impl From<AliasOptions> for MountOptions {
+    fn from(alias: AliasOptions) -> Self {
+        Self {
+            alias,
+            conditions: alias.condition.into_iter().collect(),
+        }
+    }
+}
+
+impl MountOptions {
+    /// Combines these options with the options of a path for the enclosing config.
+    fn combine(self, parent: &Self) -> Self {
+        let mut conditions = self.conditions;
+        conditions.extend_from_slice(&parent.conditions);
+        Self {
+            alias: self.alias.combine(parent.alias),
+            conditions,
+        }
+    }
+
+    /// Checks whether all alias conditions hold.
+    pub(crate) fn is_active(&self) -> bool {
+        self.conditions.iter().all(|condition| condition())
+    }
+
+    pub(crate) fn is_conditional(&self) -> bool {
+        !self.conditions.is_empty()
+    }
 }
 
 impl ConfigData {
@@ -43,17 +141,17 @@ impl ConfigData {
         Pointer(self.all_paths[0].0.as_ref())
     }
 
-    pub(crate) fn aliases(&self) -> impl Iterator<Item = (&str, AliasOptions)> + '_ {
+    pub(crate) fn aliases(&self) -> impl Iterator<Item = (&str, &MountOptions)> + '_ {
         self.all_paths
             .iter()
             .skip(1)
-            .map(|(path, options)| (path.as_ref(), *options))
+            .map(|(path, options)| (path.as_ref(), options))
     }
 
     pub(crate) fn all_paths_for_param(
         &self,
         param: &'static ParamMetadata,
-    ) -> impl Iterator<Item = (String, AliasOptions)> + '_ {
+    ) -> impl Iterator<Item = (String, MountOptions)> + '_ {
         self.all_paths_for_child(param.name, param.aliases, param.tag_variant)
     }
 
@@ -62,7 +160,17 @@ impl ConfigData {
         name: &'static str,
         aliases: &'static [(&'static str, AliasOptions)],
         tag_variant: Option<&'static ConfigVariant>,
-    ) -> impl Iterator<Item = (String, AliasOptions)> + '_ {
+    ) -> impl Iterator<Item = (String, MountOptions)> + '_ {
+        self.resolved_paths_for_child(name, aliases, tag_variant)
+            .map(|(resolved, options)| (resolved.path, options))
+    }
+
+    fn resolved_paths_for_child(
+        &self,
+        name: &'static str,
+        aliases: &'static [(&'static str, AliasOptions)],
+        tag_variant: Option<&'static ConfigVariant>,
+    ) -> impl Iterator<Item = (ResolvedPath, MountOptions)> + '_ {
         let local_names =
             iter::once((name, AliasOptions::default())).chain(aliases.iter().copied());
 
@@ -80,7 +188,7 @@ impl ConfigData {
                             return None;
                         }
                         let full_path = Pointer(&variant_name).join(name_or_path);
-                        Some((Cow::Owned(full_path), options))
+                        Some((Cow::Owned(full_path), options, true))
                     })
             });
             Some(paths)
@@ -89,23 +197,74 @@ impl ConfigData {
         };
         let enum_names = enum_names.into_iter().flatten();
         let local_names = local_names
-            .map(|(name, options)| (Cow::Borrowed(name), options))
+            .map(|(name, options)| (Cow::Borrowed(name), options, false))
             .chain(enum_names);
 
         self.all_paths
             .iter()
-            .flat_map(move |(alias, config_options)| {
-                local_names
-                    .clone()
-                    .filter_map(move |(name_or_path, options)| {
-                        let full_path = Pointer(alias).join_path(Pointer(&name_or_path))?;
-                        Some((full_path, options.combine(*config_options)))
-                    })
+            .enumerate()
+            .flat_map(move |(config_idx, (alias, config_options))| {
+                local_names.clone().enumerate().filter_map(
+                    move |(local_idx, (name_or_path, options, is_enum_coerced))| {
+                        let path = Pointer(alias).join_path(Pointer(&name_or_path))?;
+                        let options = MountOptions::from(options).combine(config_options);
+                        let deprecated = options.alias.is_deprecated;
+                        let kind = if is_enum_coerced {
+                            ResolvedPathKind::EnumCoerced { deprecated }
+                        } else if config_idx == 0 && local_idx == 0 && !deprecated {
+                            ResolvedPathKind::Canonical
+                        } else {
+                            ResolvedPathKind::Alias { deprecated }
+                        };
+                        Some((ResolvedPath { path, kind }, options))
+                    },
+                )
             })
     }
 }
 
+/// Kind of a [`ResolvedPath`], i.e., the reason the path is recognized for a param.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ResolvedPathKind {
+    /// Canonical path to the param, i.e., the canonical param name appended to the canonical config prefix.
+    Canonical,
+    /// Alias for the param, the containing config, or both.
+    Alias {
+        /// Whether the alias is deprecated.
+        deprecated: bool,
+    },
+    /// Path produced by [coercing serde enums](ConfigSchema::coerce_serde_enums()).
+    EnumCoerced {
+        /// Whether the path is produced from a deprecated alias.
+        deprecated: bool,
+    },
+}
+
+impl ResolvedPathKind {
+    /// Checks whether this path is deprecated.
+    pub fn is_deprecated(self) -> bool {
+        match self {
+            Self::Canonical => false,
+            Self::Alias { deprecated } | Self::EnumCoerced { deprecated } => deprecated,
+        }
+    }
+}
+
+/// Absolute path to a param together with the reason it is recognized. Returned by [`ConfigRef::resolved_paths()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ResolvedPath {
+    /// Absolute path to the param.
+    pub path: String,
+    /// Kind of the path.
+    pub kind: ResolvedPathKind,
+}
+
 /// Reference to a specific configuration inside [`ConfigSchema`].
+///
+/// References are compared and hashed by their [unique key](Self::key()). References are equal only if they point
+/// to the same schema.
 #[derive(Debug, Clone, Copy)]
 pub struct ConfigRef<'a> {
     schema: &'a ConfigSchema,
@@ -113,17 +272,41 @@ pub struct ConfigRef<'a> {
     pub(crate) data: &'a ConfigData,
 }
 
+impl PartialEq for ConfigRef<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(self.schema, other.schema) && self.key() == other.key()
+    }
+}
+
+impl Eq for ConfigRef<'_> {}
+
+impl hash::Hash for ConfigRef<'_> {
+    fn hash<H: hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
 impl<'a> ConfigRef<'a> {
     /// Gets the config prefix.
     pub fn prefix(&self) -> &'a str {
         self.prefix
     }
 
+    /// Returns the unique key of this config in the schema, i.e., its type + canonical prefix.
+    pub fn key(&self) -> (any::TypeId, &'a str) {
+        (self.data.metadata.ty.id(), self.prefix)
+    }
+
     /// Gets the config metadata.
     pub fn metadata(&self) -> &'static ConfigMetadata {
         self.data.metadata
     }
 
+    /// Gets the doc comment of the config type, preserved verbatim. See [`ConfigMetadata::doc`] for details.
+    pub fn doc(&self) -> &'static str {
+        self.data.metadata.doc
+    }
+
     /// Checks whether this config is top-level (i.e., was included into the schema directly, rather than as a sub-config).
     pub fn is_top_level(&self) -> bool {
         self.data.parent_link.is_none()
@@ -147,9 +330,63 @@ impl<'a> ConfigRef<'a> {
         Some((parent_ref, link.this_ref))
     }
 
+    /// Returns the variant of the enclosing enum config that this config corresponds to. `None` if this config
+    /// is top-level, or if the parent config is not an enum config.
+    pub fn tag_variant(&self) -> Option<&'static ConfigVariant> {
+        self.data.parent_link?.this_ref.tag_variant
+    }
+
     /// Iterates over all aliases for this config.
     pub fn aliases(&self) -> impl Iterator<Item = (&'a str, AliasOptions)> + '_ {
-        self.data.aliases()
+        self.data
+            .aliases()
+            .map(|(alias, options)| (alias, options.alias))
+    }
+
+    /// Returns the deprecation message for this config, if it was [marked as deprecated](ConfigMut::mark_deprecated()).
+    pub fn deprecation(&self) -> Option<&'a str> {
+        self.data.deprecation
+    }
+
+    /// Gets a param in this config by its Rust field name.
+    pub fn param(&self, rust_field_name: &str) -> Option<&'static ParamMetadata> {
+        self.metadata()
+            .params
+            .iter()
+            .find(|param| param.rust_field_name == rust_field_name)
+    }
+
+    /// Gets a param in this config by its name or one of its aliases. Only local names are considered,
+    /// i.e., path aliases (ones starting with `.`) are ignored.
+    pub fn param_by_name(&self, name: &str) -> Option<&'static ParamMetadata> {
+        self.metadata().params.iter().find(|param| {
+            param.name == name || param.aliases.iter().any(|(alias, _)| *alias == name)
+        })
+    }
+
+    /// Returns the default value of the specified param (if any) converted to the [`WithOrigin`] value tree.
+    /// The root value has a [synthetic](ValueOrigin::Synthetic) origin with the `default` transform; nested values
+    /// (object entries and array items) have [path](ValueOrigin::Path) origins relative to it.
+    /// For the result to make sense, the param must be a part of this config.
+    pub fn default_for(&self, param: &'static ParamMetadata) -> Option<WithOrigin> {
+        let default = param.default_value_json()?;
+        let origin = Arc::new(ValueOrigin::Synthetic {
+            source: Arc::default(),
+            transform: "default".to_owned(),
+        });
+        Some(Json::map_value(default, &origin, String::new()))
+    }
+
+    /// Returns a prioritized list of absolute paths to the specified param (higher-priority paths first)
+    /// together with the reason each path is recognized. The first returned path is always the canonical one.
+    /// For the result to make sense, the param must be a part of this config.
+    pub fn resolved_paths(
+        &self,
+        param: &'static ParamMetadata,
+    ) -> impl Iterator<Item = ResolvedPath> + '_ {
+        self.data
+            .resolved_paths_for_child(param.name, param.aliases, param.tag_variant)
+            .map(|(resolved, _)| resolved)
     }
 
     /// Returns a prioritized list of absolute paths to the specified param (higher-priority paths first).
@@ -159,10 +396,41 @@ impl<'a> ConfigRef<'a> {
         &self,
         param: &'static ParamMetadata,
     ) -> impl Iterator<Item = (String, AliasOptions)> + '_ {
-        self.data.all_paths_for_param(param)
+        self.data
+            .all_paths_for_param(param)
+            .map(|(path, options)| (path, options.alias))
     }
 }
 
+/// Param path claimed by multiple configs, at least one via an alias. Returned by [`ConfigSchema::conflicting_aliases()`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct AliasConflict<'a> {
+    /// Absolute path claimed by the conflicting params.
+    pub path: String,
+    /// First conflicting param together with the config it belongs to. If one of the params has `path` as its canonical path,
+    /// it is the first one.
+    pub first: (ConfigRef<'a>, &'static ParamMetadata),
+    /// Second conflicting param together with the config it belongs to.
+    pub second: (ConfigRef<'a>, &'static ParamMetadata),
+}
+
+/// Deprecated param alias. Returned by [`ConfigSchema::deprecations()`].
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct Deprecation<'a> {
+    /// Absolute deprecated path to the param.
+    pub alias: String,
+    /// Absolute canonical path to the param that should be used instead of the alias.
+    pub canonical_path: String,
+    /// Config owning the param.
+    pub config: ConfigRef<'a>,
+    /// Param metadata.
+    pub param: &'static ParamMetadata,
+    /// Alias options, e.g. the version since which the alias is deprecated.
+    pub options: AliasOptions,
+}
+
 /// Mutable reference to a specific configuration inside [`ConfigSchema`].
 #[derive(Debug)]
 pub struct ConfigMut<'a> {
@@ -181,6 +449,7 @@ impl ConfigMut<'_> {
     pub fn aliases(&self) -> impl Iterator<Item = (&str, AliasOptions)> + '_ {
         let data = &self.schema.configs[self.prefix.as_str()].inner[&self.type_id];
         data.aliases()
+            .map(|(alias, options)| (alias, options.alias))
     }
 
     /// Pushes an additional alias for the config.
@@ -190,7 +459,17 @@ impl ConfigMut<'_> {
     /// Returns an error if adding a config leads to violations of fundamental invariants
     /// (same as for [`ConfigSchema::insert()`]).
     pub fn push_alias(self, alias: &'static str) -> anyhow::Result<Self> {
-        self.push_alias_inner(alias, AliasOptions::new())
+        self.push_alias_inner(alias.into(), AliasOptions::new())
+    }
+
+    /// Same as [`Self::push_alias()`], but accepts an alias computed in runtime (e.g., based on a tenant name).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if adding a config leads to violations of fundamental invariants
+    /// (same as for [`ConfigSchema::insert()`]).
+    pub fn push_alias_owned(self, alias: String) -> anyhow::Result<Self> {
+        self.push_alias_inner(alias.into(), AliasOptions::new())
     }
 
     /// Same as [`Self::push_alias()`], but also marks the alias as deprecated.
@@ -200,17 +479,86 @@ impl ConfigMut<'_> {
     /// Returns an error if adding a config leads to violations of fundamental invariants
     /// (same as for [`ConfigSchema::insert()`]).
     pub fn push_deprecated_alias(self, alias: &'static str) -> anyhow::Result<Self> {
-        self.push_alias_inner(
-            alias,
-            AliasOptions {
-                is_deprecated: true,
+        self.push_alias_inner(alias.into(), AliasOptions::new().deprecated())
+    }
+
+    /// Same as [`Self::push_alias()`], but with the specified alias options. This allows specifying
+    /// the deprecation details, e.g., `AliasOptions::new().deprecated_since("0.2.0").remove_in("0.4.0")`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if adding a config leads to violations of fundamental invariants
+    /// (same as for [`ConfigSchema::insert()`]).
+    pub fn push_alias_with_options(
+        self,
+        alias: &'static str,
+        options: AliasOptions,
+    ) -> anyhow::Result<Self> {
+        self.push_alias_inner(alias.into(), options)
+    }
+
+    /// Marks the config as deprecated with the specified message (e.g., describing the replacement).
+    /// If the config receives any values from sources, parsing it will produce a [warning](crate::Severity::Warning)
+    /// pointing to the config prefix.
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)] // false positive
+    pub fn mark_deprecated(self, message: &'static str) -> Self {
+        let data = self
+            .schema
+            .configs
+            .get_mut(self.prefix.as_str())
+            .and_then(|configs| configs.inner.get_mut(&self.type_id))
+            .expect("internal error: config is not in the schema");
+        data.deprecation = Some(message);
+        self
+    }
+
+    /// Overrides [serde enum coercion](ConfigSchema::coerce_serde_enums()) for this config and its nested configs,
+    /// regardless of the schema-wide setting. Path aliases produced by coercion are regenerated for all affected configs.
+    /// This allows mixing serde-style and native enum configs in a single schema.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config is not top-level, or if regenerating aliases leads to violations
+    /// of fundamental invariants (same as for [`ConfigSchema::insert()`]). In this case, the schema is not changed.
+    #[allow(clippy::missing_panics_doc)] // false positive
+    pub fn coerce_serde_enums(self, coerce: bool) -> anyhow::Result<Self> {
+        let data = &self.schema.configs[self.prefix.as_str()].inner[&self.type_id];
+        anyhow::ensure!(
+            data.is_top_level,
+            "Config `{}` at `{}` is not top-level; serde enum coercion can only be overridden for top-level configs",
+            data.metadata.ty.name_in_code(),
+            self.prefix
+        );
+        if data.coerce_serde_enums == coerce {
+            return Ok(self); // no-op
+        }
+
+        let mut rebuilt = self.schema.rebuild(
+            |config| Some(Cow::Owned(config.prefix().to_owned())),
+            |alias| Some(alias.clone()),
+            |config| {
+                if config.prefix() == self.prefix && config.metadata().ty.id() == self.type_id {
+                    coerce
+                } else {
+                    config.data.coerce_serde_enums
+                }
             },
-        )
+        )?;
+        if let Some(path) = self.schema.catch_all {
+            rebuilt.set_catch_all(path)?;
+        }
+        *self.schema = rebuilt;
+        Ok(self)
     }
 
-    fn push_alias_inner(self, alias: &'static str, options: AliasOptions) -> anyhow::Result<Self> {
+    fn push_alias_inner(
+        self,
+        alias: Cow<'static, str>,
+        options: AliasOptions,
+    ) -> anyhow::Result<Self> {
         let mut patched = PatchedSchema::new(self.schema);
-        patched.insert_alias(self.prefix.clone(), self.type_id, Pointer(alias), options)?;
+        patched.insert_alias(self.prefix.clone(), self.type_id, alias, options.into())?;
         patched.commit();
         Ok(self)
     }
@@ -219,18 +567,21 @@ impl ConfigMut<'_> {
 #[derive(Debug, Clone, Default)]
 struct ConfigsForPrefix {
     inner: HashMap<any::TypeId, ConfigData>,
-    by_depth: BTreeSet<(usize, any::TypeId)>,
+    /// Iteration order for configs. Type names are used to make the order stable across builds; type IDs
+    /// only break ties between types with the same name.
+    by_depth: BTreeSet<(usize, &'static str, any::TypeId)>,
 }
 
 impl ConfigsForPrefix {
     fn by_depth(&self) -> impl Iterator<Item = &ConfigData> + '_ {
-        self.by_depth.iter().map(|(_, ty)| &self.inner[ty])
+        self.by_depth.iter().map(|(_, _, ty)| &self.inner[ty])
     }
 
     fn insert(&mut self, ty: any::TypeId, depth: Option<usize>, data: ConfigData) {
+        let type_name = data.metadata.ty.name_in_code();
         self.inner.insert(ty, data);
         if let Some(depth) = depth {
-            self.by_depth.insert((depth, ty));
+            self.by_depth.insert((depth, type_name, ty));
         }
     }
 
@@ -249,6 +600,20 @@ pub struct ConfigSchema {
     configs: BTreeMap<Cow<'static, str>, ConfigsForPrefix>,
     mounting_points: MountingPoints,
     coerce_serde_enums: bool,
+    accept_external_tag: bool,
+    catch_all: Option<&'static str>,
+    kv_delimiter: Option<char>,
+    unset_marker: UnsetMarker,
+}
+
+/// Key of the [unset marker](crate::ConfigRepository#unsetting-values) object; `None` if unset markers are disabled.
+#[derive(Debug, Clone, Copy)]
+struct UnsetMarker(Option<&'static str>);
+
+impl Default for UnsetMarker {
+    fn default() -> Self {
+        Self(Some("$unset"))
+    }
 }
 
 impl ConfigSchema {
@@ -269,16 +634,183 @@ impl ConfigSchema {
     /// (`snake_cased` tag + param name) will be added for the param. Tag aliases and param aliases will result
     /// in additional path aliases, as expected. For example, if `param` has alias `alias` and the tag has alias `AliasTag`,
     /// then the param will have `.alias_tag.param`, `.alias_tag.alias` and `.some_tag.alias` aliases.
+    ///
+    /// Besides aliases, the tag value is inferred during source preprocessing if it's missing. Thus, coercion
+    /// allows parsing enum configs from the externally tagged `serde` representation, like `{ "some_tag": { "param": 42 } }`.
+    /// If the enum config object contains keys for multiple variants, or the variant key doesn't correspond to an object,
+    /// or the key doesn't match any variant, the tag is not inferred; this will usually lead to a missing tag error
+    /// during parsing (unless the enum config has a default variant). Use [`Self::accept_external_tag()`] to get
+    /// a dedicated error for unknown variant keys instead.
     pub fn coerce_serde_enums(&mut self, coerce: bool) -> &mut Self {
         self.coerce_serde_enums = coerce;
         self
     }
 
-    /// Iterates over all configs with their canonical prefixes.
+    /// Switches accepting the externally tagged `serde` representation for enum configs, like
+    /// `{ "SomeTag": { "param": 42 } }`. If enabled, an enum config object without the tag that contains a single key
+    /// not claimed by params or nested configs is treated as an externally tagged enum, provided that the key value
+    /// is an object. The key is matched against variant names and aliases (both as-is and `snake_cased`);
+    /// if it matches, the tag is set to the matched variant, and the contents of the key value are moved
+    /// to the enum config object.
+    ///
+    /// If the key doesn't match any variant, parsing the config will fail with an `unknown_tag` [error](crate::ParseError::code()),
+    /// even if the enum config has a default variant.
+    ///
+    /// This can be used together with [serde enum coercion](Self::coerce_serde_enums()), in which case coercion
+    /// takes precedence for matching `snake_cased` variant keys.
+    pub fn accept_external_tag(&mut self, accept: bool) -> &mut Self {
+        self.accept_external_tag = accept;
+        self
+    }
+
+    pub(crate) fn accepts_external_tag(&self) -> bool {
+        self.accept_external_tag
+    }
+
+    /// Sets the delimiter used to split keys in [flat](crate::Flat) sources (e.g., [`Environment`](crate::Environment))
+    /// into nested paths. By default, keys are split on `_`, and a key is matched against all params whose canonical paths
+    /// coincide with the key after replacing `.`s with `_`s (e.g., `test_param_value` may correspond to `test.param_value`
+    /// or `test_param.value`).
+    ///
+    /// If a custom delimiter is set, keys are split exactly on this delimiter, so that e.g. with `/` as the delimiter,
+    /// `test/param_value` is nested as `test.param_value`, and `_`s do not delimit path segments. Leading, trailing
+    /// and repeated delimiters are ignored. A numeric last segment is treated as an index for an array param,
+    /// so that `test/array/0` provides the first element of the array param at `test.array`.
+    ///
+    /// Only ingestion of flat sources is affected; canonical param paths are still dot-separated.
+    /// Setting `_` as the delimiter restores the default behavior. See also [`NestedEnvironment`](crate::NestedEnvironment),
+    /// which splits env var names on a separator on the source level.
+    pub fn kv_delimiter(&mut self, delimiter: char) -> &mut Self {
+        self.kv_delimiter = (delimiter != '_').then_some(delimiter);
+        self
+    }
+
+    pub(crate) fn custom_kv_delimiter(&self) -> Option<char> {
+        self.kv_delimiter
+    }
+
+    /// Sets the key of [unset markers](crate::ConfigRepository#unsetting-values) in sources, or disables unset markers
+    /// if `key` is `None`. The default key is `$unset`, i.e., `{ "$unset": true }` is treated as an unset marker.
+    ///
+    /// An unset marker is an object with a single entry, so it may be ambiguous with legitimate values of object params
+    /// (e.g., a map param with Boolean values). If this is a concern, change the marker key to one that cannot occur
+    /// in values, or disable markers altogether.
+    pub fn unset_marker(&mut self, key: Option<&'static str>) -> &mut Self {
+        self.unset_marker = UnsetMarker(key);
+        self
+    }
+
+    pub(crate) fn unset_marker_key(&self) -> Option<&'static str> {
+        self.unset_marker.0
+    }
+
+    /// Designates a catch-all param at the specified canonical `path`. All top-level keys in hierarchical sources
+    /// that are not claimed by any config or param in the schema (including via aliases) will be moved into the object
+    /// at this path, preserving their origins. This allows capturing e.g. plugin config sections to be processed later.
+    ///
+    /// The catch-all param must accept objects; a good choice for its type is `HashMap<String, serde_json::Value>`
+    /// deserialized with [`Serde![object]`](crate::de::Serde!). Unlike with other object params, values for
+    /// the catch-all param from multiple sources are merged rather than overwritten.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `path` does not correspond to a canonical param accepting objects.
+    pub fn set_catch_all(&mut self, path: &'static str) -> anyhow::Result<&mut Self> {
+        match self.mounting_points.get(path) {
+            Some(MountingPoint::Param {
+                is_canonical: true,
+                expecting,
+            }) => {
+                anyhow::ensure!(
+                    expecting.contains(BasicTypes::OBJECT),
+                    "Catch-all param at `{path}` must accept objects; it expects {expecting}"
+                );
+            }
+            _ => anyhow::bail!("No canonical param is mounted at `{path}`"),
+        }
+        self.catch_all = Some(path);
+        Ok(self)
+    }
+
+    pub(crate) fn catch_all(&self) -> Option<Pointer<'static>> {
+        self.catch_all.map(Pointer)
+    }
+
+    /// Checks whether a top-level key is claimed by a config or a param in this schema.
+    pub(crate) fn is_known_top_level_key(&self, key: &str) -> bool {
+        self.mounting_points.contains_path_or_descendants(key)
+    }
+
+    /// Checks whether a param (possibly via an alias) or a config is mounted at `path` or at one of its descendants.
+    pub(crate) fn contains_path_or_descendants(&self, path: Pointer<'_>) -> bool {
+        self.mounting_points.contains_path_or_descendants(path.0)
+    }
+
+    /// Checks whether a param (possibly via an alias) is mounted at `path`.
+    pub(crate) fn contains_param(&self, path: Pointer<'_>) -> bool {
+        matches!(
+            self.mounting_points.get(path.0),
+            Some(MountingPoint::Param { .. })
+        )
+    }
+
+    /// Returns basic types expected by the param at the canonical `path`, or `None` if there's no such param.
+    pub(crate) fn param_expecting(&self, path: Pointer<'_>) -> Option<BasicTypes> {
+        match self.mounting_points.get(path.0)? {
+            MountingPoint::Param { expecting, .. } => Some(*expecting),
+            MountingPoint::Config => None,
+        }
+    }
+
+    /// Checks whether a config is mounted at the canonical `prefix`.
+    pub(crate) fn contains_config_at(&self, prefix: Pointer<'_>) -> bool {
+        self.configs.contains_key(prefix.0)
+    }
+
+    /// Returns keys in the config object at `prefix` claimed by params or sub-configs (including via aliases).
+    pub(crate) fn claimed_keys<'s>(&'s self, prefix: Pointer<'s>) -> ClaimedKeys<'s> {
+        let mut children = HashMap::new();
+        for child in self.mounting_points.child_names(prefix.0) {
+            if children.contains_key(child) {
+                continue;
+            }
+            let child_path = prefix.join(child);
+            let suffixes = match self.mounting_points.get(&child_path) {
+                Some(MountingPoint::Param { expecting, .. }) => ClaimedSuffixes::Param {
+                    array_indices: expecting.contains(BasicTypes::ARRAY)
+                        && !expecting.contains(BasicTypes::OBJECT),
+                    suffixes: self
+                        .param_at(&child_path)
+                        .and_then(|(_, param)| param.type_description().suffixes()),
+                },
+                // Sub-configs may claim any suffix (e.g., `nested_param` for the `param` param in the `nested` config).
+                Some(MountingPoint::Config) | None => ClaimedSuffixes::Any,
+            };
+            children.insert(child, suffixes);
+        }
+        ClaimedKeys { children }
+    }
+
+    /// Checks whether the specified path corresponds to a [`rest`](crate::DescribeConfig#rest) param.
+    pub(crate) fn is_rest_param(&self, at: Pointer<'_>) -> bool {
+        let Some((prefix, name)) = at.split_last() else {
+            return false;
+        };
+        self.configs.get(prefix.0).is_some_and(|configs| {
+            configs.inner.values().any(|data| {
+                data.metadata
+                    .params
+                    .iter()
+                    .any(|param| param.is_rest && param.name == name)
+            })
+        })
+    }
+
+    /// Iterates over all configs with their canonical prefixes. The iteration order is deterministic (same as for [`Self::iter()`]).
     pub(crate) fn iter_ll(&self) -> impl Iterator<Item = (Pointer<'_>, &ConfigData)> + '_ {
         self.configs
             .iter()
-            .flat_map(|(prefix, data)| data.inner.values().map(move |data| (Pointer(prefix), data)))
+            .flat_map(|(prefix, data)| data.by_depth().map(move |data| (Pointer(prefix), data)))
     }
 
     pub(crate) fn contains_canonical_param(&self, at: Pointer<'_>) -> bool {
@@ -308,6 +840,17 @@ impl ConfigSchema {
             })
     }
 
+    /// Returns the number of configs in this schema. Like [`Self::iter()`], this counts each unique (config type, location)
+    /// pair, i.e., every mount of a config including nested ones.
+    pub fn len(&self) -> usize {
+        self.configs.values().map(|data| data.inner.len()).sum()
+    }
+
+    /// Checks whether this schema contains no configs.
+    pub fn is_empty(&self) -> bool {
+        self.configs.values().all(|data| data.inner.is_empty())
+    }
+
     /// Iterates over all configs contained in this schema. A unique key for a config is its type + location;
     /// i.e., multiple returned refs may have the same config type xor same location (never both).
     pub fn iter(&self) -> impl Iterator<Item = ConfigRef<'_>> + '_ {
@@ -320,6 +863,24 @@ impl ConfigSchema {
         })
     }
 
+    /// Iterates over all params in this schema together with their absolute canonical paths and the containing configs.
+    /// Params are sorted by the path.
+    pub fn iter_params(
+        &self,
+    ) -> impl Iterator<Item = (String, ConfigRef<'_>, &'static ParamMetadata)> + '_ {
+        let mut params: Vec<_> = self
+            .iter()
+            .flat_map(|config| {
+                config.metadata().params.iter().map(move |param| {
+                    let path = Pointer(config.prefix()).join(param.name);
+                    (path, config, param)
+                })
+            })
+            .collect();
+        params.sort_by(|(path, ..), (other_path, ..)| path.cmp(other_path));
+        params.into_iter()
+    }
+
     /// Lists all prefixes for the specified config. This does not include aliases.
     pub fn locate(&self, metadata: &'static ConfigMetadata) -> impl Iterator<Item = &str> + '_ {
         let config_type_id = metadata.ty.id();
@@ -330,6 +891,449 @@ impl ConfigSchema {
         })
     }
 
+    /// Lists all canonical absolute paths of the specified `param` across all prefixes where its config is mounted,
+    /// ordered by the prefix. This does not include aliases. If `param` is not a param of the config, no paths are returned.
+    pub fn canonical_paths(
+        &self,
+        metadata: &'static ConfigMetadata,
+        param: &'static ParamMetadata,
+    ) -> impl Iterator<Item = String> + '_ {
+        let is_config_param = metadata
+            .params
+            .iter()
+            .any(|config_param| std::ptr::eq(config_param, param));
+        let prefixes = self.locate(metadata).filter(move |_| is_config_param);
+        prefixes.map(|prefix| Pointer(prefix).join(param.name))
+    }
+
+    /// Returns references to all mounts of the specified config, ordered by the canonical prefix. This does not include aliases.
+    /// Unlike [`Self::single()`], this method doesn't fail if the config is mounted at multiple locations.
+    pub fn get_all(
+        &self,
+        metadata: &'static ConfigMetadata,
+    ) -> impl Iterator<Item = ConfigRef<'_>> + '_ {
+        let config_type_id = metadata.ty.id();
+        self.configs.iter().filter_map(move |(prefix, data)| {
+            Some(ConfigRef {
+                schema: self,
+                prefix: prefix.as_ref(),
+                data: data.inner.get(&config_type_id)?,
+            })
+        })
+    }
+
+    /// Lists config types mounted at more than one location together with their canonical prefixes.
+    /// This is mostly useful as a debugging aid; multi-mounted configs may be intentional, but more often
+    /// than not they are a result of an accidental duplicate registration.
+    ///
+    /// Configs are ordered by their first prefix; prefixes are sorted.
+    pub fn find_multi_mounted(&self) -> Vec<(&'static ConfigMetadata, Vec<&str>)> {
+        let mut indices_by_type = HashMap::new();
+        let mut configs = vec![];
+        for config in self.iter() {
+            let idx = *indices_by_type
+                .entry(config.metadata().ty.id())
+                .or_insert_with(|| {
+                    configs.push((config.metadata(), vec![]));
+                    configs.len() - 1
+                });
+            configs[idx].1.push(config.prefix());
+        }
+        configs.retain(|(_, prefixes)| prefixes.len() > 1);
+        configs
+    }
+
+    /// Lists params that cannot receive a value via their canonical path, together with these paths.
+    /// A param is unreachable if its canonical mounting point is not a canonical param mount (e.g., it's overridden
+    /// by a param alias or a config mount), or if any ancestor of the path is not a config mount.
+    ///
+    /// [`Self::insert()`] rejects mounts leading to unreachable params, so this should always return an empty list;
+    /// it's still useful as a lint in tests for large schemas.
+    ///
+    /// Params are sorted by the path.
+    pub fn unreachable_params(&self) -> Vec<(String, &'static ParamMetadata)> {
+        self.iter_params()
+            .filter(|(path, ..)| !self.is_canonically_reachable(path))
+            .map(|(path, _, param)| (path, param))
+            .collect()
+    }
+
+    fn is_canonically_reachable(&self, path: &str) -> bool {
+        let is_canonical = matches!(
+            self.mounting_points.get(path),
+            Some(MountingPoint::Param {
+                is_canonical: true,
+                ..
+            })
+        );
+        is_canonical
+            && Pointer(path)
+                .with_ancestors()
+                .filter(|ancestor| ancestor.0 != path)
+                .all(|ancestor| {
+                    matches!(
+                        self.mounting_points.get(ancestor.0),
+                        Some(MountingPoint::Config)
+                    )
+                })
+    }
+
+    /// Checks that all params without a default value in this schema are present in the provided `source`
+    /// (or are provided by [fallbacks](crate::fallback)). This is a dry run that doesn't deserialize param values;
+    /// it's useful to fail fast on incomplete configuration, e.g. in a CLI command checking configuration.
+    ///
+    /// Params and nested configs specific to an enum variant are only checked if the variant is active,
+    /// i.e., the tag param value (or the default variant if the tag is missing) corresponds to the variant.
+    /// Likewise, params in optional nested configs (i.e., ones wrapped in an `Option` or having a default value)
+    /// are only checked if the config object is present.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MissingField`](crate::ParseErrorCategory::MissingField) errors for all missing params, ordered by
+    /// the canonical param path.
+    pub fn validate_complete(&self, source: impl ConfigSource) -> Result<(), ParseErrors> {
+        let repo = ConfigRepository::new(self).with(source);
+        let merged = repo.merged();
+        let mut missing_params: Vec<_> = self
+            .iter()
+            .filter(|&config| {
+                Self::is_config_active(merged, config) && Self::is_config_present(merged, config)
+            })
+            .flat_map(|config| {
+                let params = config.metadata().params.iter().enumerate();
+                params.filter_map(move |(idx, param)| {
+                    if param.default_value.is_some()
+                        || !Self::is_variant_active(merged, config, param.tag_variant)
+                    {
+                        return None;
+                    }
+                    let path = Pointer(config.prefix()).join(param.name);
+                    merged
+                        .get(Pointer(&path))
+                        .is_none()
+                        .then_some((path, config, idx, param))
+                })
+            })
+            .collect();
+        missing_params.sort_by(|(path, ..), (other_path, ..)| path.cmp(other_path));
+
+        let errors: Vec<_> = missing_params
+            .into_iter()
+            .map(|(path, config, idx, param)| ParseError {
+                inner: DeError::missing_field(param.name),
+                category: ParseErrorCategory::MissingField,
+                kind: ErrorKind::MissingField,
+                path,
+                origin: Arc::default(),
+                config: config.metadata(),
+                location_in_config: Some(LocationInConfig::Param(idx)),
+                validation: None,
+                suggestions: vec![],
+                note: None,
+                type_mismatch: None,
+                severity: Severity::Error,
+            })
+            .collect();
+        errors.into_iter().collect()
+    }
+
+    /// Checks whether all optional configs on the path to `config` (including `config` itself) are present.
+    fn is_config_present(merged: &WithOrigin, mut config: ConfigRef<'_>) -> bool {
+        while let Some((parent, nested)) = config.parent_link() {
+            if nested.is_optional && merged.get(Pointer(config.prefix())).is_none() {
+                return false;
+            }
+            config = parent;
+        }
+        true
+    }
+
+    pub(crate) fn is_config_active(merged: &WithOrigin, mut config: ConfigRef<'_>) -> bool {
+        while let Some((parent, nested)) = config.parent_link() {
+            if !Self::is_variant_active(merged, parent, nested.tag_variant) {
+                return false;
+            }
+            config = parent;
+        }
+        true
+    }
+
+    fn is_variant_active(
+        merged: &WithOrigin,
+        config: ConfigRef<'_>,
+        variant: Option<&'static ConfigVariant>,
+    ) -> bool {
+        let (Some(variant), Some(tag)) = (variant, &config.metadata().tag) else {
+            return true;
+        };
+        let tag_path = Pointer(config.prefix()).join(tag.param.name);
+        match merged.get(Pointer(&tag_path)).map(|val| &val.inner) {
+            Some(Value::String(s)) => {
+                let s = s.expose();
+                variant.name == s || variant.aliases.contains(&s)
+            }
+            Some(_) => false,
+            None => tag
+                .default_variant
+                .is_some_and(|default| std::ptr::eq(default, variant)),
+        }
+    }
+
+    /// Generates a documented `.env` template for all params in this schema. Each param is represented by a block
+    /// with its doc comment followed by `VAR=value`, where `VAR` is the canonical env variable name (i.e., the name
+    /// mapped to the canonical param path by an unprefixed [`Environment`]), and `value` is the default param value.
+    ///
+    /// - Required params (i.e., ones without a default value) are marked as such, and have an empty value.
+    /// - Params with a `null` default (e.g., optional params) are commented out.
+    /// - [Secret](crate::metadata::TypeDescription::contains_secrets()) params are marked as such and are commented out;
+    ///   their default values are never included into the template.
+    /// - Params with an array / object default use the `__JSON` suffix, which is recognized by [`Environment::coerce_json()`].
+    /// - String values are quoted and escaped if necessary, so that they are parsed back verbatim.
+    ///
+    /// Aliases are not included into the template.
+    ///
+    /// [`Environment`]: crate::Environment
+    /// [`Environment::coerce_json()`]: crate::Environment::coerce_json()
+    pub fn to_env_template(&self) -> String {
+        let mut template = String::new();
+        for config in self.iter() {
+            for param in config.metadata().params {
+                let path = Pointer(config.prefix()).join(param.name);
+                let mut var_name = Environment::var_name("", &path);
+                if !template.is_empty() {
+                    template.push('\n');
+                }
+                for line in param.help.lines() {
+                    let line = line.trim_end();
+                    if line.is_empty() {
+                        template.push_str("#\n");
+                    } else {
+                        writeln!(template, "# {line}").unwrap();
+                    }
+                }
+
+                let Some(default) = param.default_value_json() else {
+                    template.push_str("# Required.\n");
+                    writeln!(template, "{var_name}=").unwrap();
+                    continue;
+                };
+                if param.type_description().contains_secrets() {
+                    template.push_str("# Secret.\n");
+                    writeln!(template, "# {var_name}=").unwrap();
+                    continue;
+                }
+                let value = match default {
+                    serde_json::Value::Null => {
+                        writeln!(template, "# {var_name}=").unwrap();
+                        continue;
+                    }
+                    serde_json::Value::String(s) => Environment::dotenv_value(&s).into_owned(),
+                    value @ (serde_json::Value::Array(_) | serde_json::Value::Object(_)) => {
+                        var_name.push_str("__JSON");
+                        value.to_string()
+                    }
+                    value => value.to_string(),
+                };
+                writeln!(template, "{var_name}={value}").unwrap();
+            }
+        }
+        template
+    }
+
+    /// Compares this schema (considered the old version) with `other` (the new version). This can be used
+    /// to check that an upgraded app remains compatible with existing configuration.
+    pub fn diff<'a>(&'a self, other: &'a ConfigSchema) -> SchemaDiff<'a> {
+        SchemaDiff::new(self, other)
+    }
+
+    /// Finds param paths claimed by multiple configs, at least one of which claims the path via an alias.
+    /// Such paths are allowed by the schema as long as the expected param types match, but more often than not
+    /// they are a result of an accidental alias collision.
+    ///
+    /// Conflicts are ordered by the path; a conflict is reported for each pair of conflicting params.
+    pub fn conflicting_aliases(&self) -> Vec<AliasConflict<'_>> {
+        let mut params_by_path = BTreeMap::<_, Vec<_>>::new();
+        for config in self.iter() {
+            for param in config.metadata().params {
+                for (i, (path, _)) in config.data.all_paths_for_param(param).enumerate() {
+                    params_by_path
+                        .entry(path)
+                        .or_default()
+                        .push((config, param, i > 0));
+                }
+            }
+        }
+
+        let mut conflicts = vec![];
+        for (path, mut params) in params_by_path {
+            if !matches!(
+                self.mounting_points.get(&path),
+                Some(MountingPoint::Param { .. })
+            ) {
+                continue;
+            }
+            // Place canonical paths first.
+            params.sort_by_key(|&(_, _, is_alias)| is_alias);
+
+            for (i, &(config, param, is_alias)) in params.iter().enumerate() {
+                for &(other_config, other_param, other_is_alias) in &params[i + 1..] {
+                    let is_same_owner = config.prefix() == other_config.prefix()
+                        && config.metadata().ty == other_config.metadata().ty;
+                    if is_same_owner || !(is_alias || other_is_alias) {
+                        continue;
+                    }
+                    conflicts.push(AliasConflict {
+                        path: path.clone(),
+                        first: (config, param),
+                        second: (other_config, other_param),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Checks this schema for soft consistency issues which are not prevented by [`Self::insert()`], but usually
+    /// indicate a mistake. Currently, this aggregates the following checks:
+    ///
+    /// - [Alias conflicts](Self::conflicting_aliases())
+    /// - [Multi-mounted configs](Self::find_multi_mounted()). Only configs explicitly [inserted](Self::insert())
+    ///   at multiple locations are reported; configs nested in multiple places are fine.
+    /// - [Unreachable params](Self::unreachable_params())
+    ///
+    /// If some of these issues are intentional, use the corresponding methods directly instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error with a report listing all found issues.
+    pub fn assert_valid(&self) -> anyhow::Result<()> {
+        let mut issues = vec![];
+        for conflict in self.conflicting_aliases() {
+            let (first_config, first_param) = conflict.first;
+            let (second_config, second_param) = conflict.second;
+            issues.push(format!(
+                "path `{}` is claimed by `{}.{}` (config at `{}`) and `{}.{}` (config at `{}`)",
+                conflict.path,
+                first_config.metadata().ty.name_in_code(),
+                first_param.rust_field_name,
+                first_config.prefix(),
+                second_config.metadata().ty.name_in_code(),
+                second_param.rust_field_name,
+                second_config.prefix()
+            ));
+        }
+        for (metadata, prefixes) in self.find_multi_mounted() {
+            let top_level_prefixes: Vec<_> = prefixes
+                .into_iter()
+                .filter(|prefix| {
+                    self.get(metadata, prefix)
+                        .is_some_and(|config| config.is_top_level())
+                })
+                .collect();
+            if top_level_prefixes.len() < 2 {
+                continue;
+            }
+            let prefixes = top_level_prefixes
+                .iter()
+                .map(|prefix| format!("`{prefix}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            issues.push(format!(
+                "config `{}` is mounted at multiple locations: {prefixes}",
+                metadata.ty.name_in_code()
+            ));
+        }
+        for (path, param) in self.unreachable_params() {
+            issues.push(format!(
+                "param `{}` cannot receive a value via its canonical path `{path}`",
+                param.rust_field_name
+            ));
+        }
+
+        if issues.is_empty() {
+            return Ok(());
+        }
+        let mut report = format!("found {} issue(s) in config schema:", issues.len());
+        for issue in issues {
+            write!(report, "\n- {issue}").unwrap();
+        }
+        Err(anyhow::Error::msg(report))
+    }
+
+    /// Panics with a detailed report if this schema has [soft consistency issues](Self::assert_valid()).
+    /// Intended to be used in tests.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Self::assert_valid()`] returns an error.
+    #[track_caller]
+    pub fn assert_no_conflicts(&self) {
+        if let Err(err) = self.assert_valid() {
+            panic!("{err}");
+        }
+    }
+
+    /// Lists all deprecated param aliases in this schema together with the canonical paths they map to. This includes
+    /// deprecated aliases of the params themselves, as well as param paths implied by deprecated aliases of the enclosing configs.
+    /// The output can be used e.g. to generate a migration guide.
+    ///
+    /// Deprecations are ordered by the alias path.
+    pub fn deprecations(&self) -> Vec<Deprecation<'_>> {
+        let mut deprecations = vec![];
+        for config in self.iter() {
+            for param in config.metadata().params {
+                let mut all_paths = config.data.all_paths_for_param(param);
+                let Some((canonical_path, _)) = all_paths.next() else {
+                    continue;
+                };
+                for (alias, options) in all_paths {
+                    if !options.alias.is_deprecated {
+                        continue;
+                    }
+                    deprecations.push(Deprecation {
+                        alias,
+                        canonical_path: canonical_path.clone(),
+                        config,
+                        param,
+                        options: options.alias,
+                    });
+                }
+            }
+        }
+        deprecations.sort_by(|lhs, rhs| lhs.alias.cmp(&rhs.alias));
+        deprecations
+    }
+
+    /// Resolves an absolute param path to the param and the config containing it. The path may be either canonical,
+    /// or correspond to one of param aliases (including aliases of configs containing the param). If a path is claimed
+    /// by multiple params, the param with the canonical path has priority.
+    ///
+    /// Returns `None` if the path doesn't correspond to a param, e.g. if it corresponds to a config.
+    pub fn param_at(&self, path: &str) -> Option<(ConfigRef<'_>, &'static ParamMetadata)> {
+        if !matches!(
+            self.mounting_points.get(path),
+            Some(MountingPoint::Param { .. })
+        ) {
+            return None;
+        }
+
+        let mut alias_match = None;
+        for config in self.iter() {
+            for param in config.metadata().params {
+                for (i, (param_path, _)) in config.data.all_paths_for_param(param).enumerate() {
+                    if param_path != path {
+                        continue;
+                    }
+                    if i == 0 {
+                        return Some((config, param));
+                    }
+                    alias_match.get_or_insert((config, param));
+                }
+            }
+        }
+        alias_match
+    }
+
     /// Gets a reference to a config by ist unique key (metadata + canonical prefix).
     pub fn get<'s>(
         &'s self,
@@ -439,17 +1443,306 @@ impl ConfigSchema {
         &mut self,
         metadata: &'static ConfigMetadata,
         prefix: &'static str,
+    ) -> anyhow::Result<ConfigMut<'_>> {
+        self.insert_with_cow_prefix(metadata, prefix.into())
+    }
+
+    /// Inserts a new configuration type at a prefix determined in runtime, e.g. based on a tenant ID.
+    /// Otherwise, this method is equivalent to [`Self::insert()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if adding a config leads to violations of fundamental invariants (same as for [`Self::insert()`]).
+    pub fn insert_at(
+        &mut self,
+        metadata: &'static ConfigMetadata,
+        prefix: String,
+    ) -> anyhow::Result<ConfigMut<'_>> {
+        self.insert_with_cow_prefix(metadata, prefix.into())
+    }
+
+    fn insert_with_cow_prefix(
+        &mut self,
+        metadata: &'static ConfigMetadata,
+        prefix: Cow<'static, str>,
     ) -> anyhow::Result<ConfigMut<'_>> {
         let coerce_serde_enums = self.coerce_serde_enums;
         let mut patched = PatchedSchema::new(self);
-        patched.insert_config(prefix, metadata, coerce_serde_enums)?;
+        patched.insert_config(prefix.clone(), metadata, coerce_serde_enums)?;
         patched.commit();
         Ok(ConfigMut {
             schema: self,
             type_id: metadata.ty.id(),
-            prefix: prefix.to_owned(),
+            prefix: prefix.into_owned(),
         })
     }
+
+    /// Moves a top-level config (together with its nested configs) from the `old` canonical prefix to `new`,
+    /// registering `old` as a [deprecated alias](ConfigMut::push_deprecated_alias()) for the config.
+    /// Other aliases and deprecation messages for the moved config and its nested configs are retained.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config is not mounted at `old` or is not top-level, or if moving the config leads
+    /// to violations of fundamental invariants (same as for [`Self::insert()`]). In this case, the schema is not changed.
+    #[allow(clippy::missing_panics_doc)] // false positive
+    pub fn rename_prefix(
+        &mut self,
+        metadata: &'static ConfigMetadata,
+        old: &str,
+        new: &'static str,
+    ) -> anyhow::Result<()> {
+        let config_name = metadata.ty.name_in_code();
+        let ty = metadata.ty.id();
+        let config = self
+            .get(metadata, old)
+            .with_context(|| format!("Config `{config_name}` is not mounted at `{old}`"))?;
+        anyhow::ensure!(
+            config.is_top_level(),
+            "Config `{config_name}` at `{old}` is not top-level; only top-level configs can be renamed"
+        );
+
+        // Returns the new canonical prefix for the config.
+        let renamed_prefix = |mut config: ConfigRef<'_>| -> Cow<'static, str> {
+            let prefix = config.prefix();
+            while let Some((parent, _)) = config.parent_link() {
+                config = parent;
+            }
+            if config.prefix() != old || config.metadata().ty.id() != ty {
+                return Cow::Owned(prefix.to_owned());
+            }
+
+            let suffix = prefix[old.len()..].trim_start_matches('.');
+            if suffix.is_empty() {
+                Cow::Borrowed(new)
+            } else {
+                Cow::Owned(Pointer(new).join(suffix))
+            }
+        };
+
+        let mut renamed = self.rebuild(
+            |config| Some(renamed_prefix(config)),
+            |alias| Some(alias.clone()),
+            |config| config.data.coerce_serde_enums,
+        )?;
+        let mut patched = PatchedSchema::new(&mut renamed);
+        let options = AliasOptions::new().deprecated();
+        patched.insert_alias(
+            new.to_owned(),
+            ty,
+            Cow::Owned(old.to_owned()),
+            options.into(),
+        )?;
+        patched.commit();
+
+        if let Some(path) = self.catch_all {
+            renamed.set_catch_all(path)?;
+        }
+        *self = renamed;
+        Ok(())
+    }
+
+    /// Extracts configs mounted at or below the specified `prefix` into a separate schema, re-rooting them so that
+    /// `prefix` becomes the root path. This allows delegating parsing or validation of a part of the configuration
+    /// to a sub-component.
+    ///
+    /// Config and param aliases are retained if they point inside `prefix` (in which case they're re-rooted as well);
+    /// other aliases are dropped. The [catch-all path](Self::set_catch_all()) is retained on the same conditions.
+    /// Other schema settings (e.g., the [KV delimiter](Self::kv_delimiter())) are copied as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{config, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig};
+    /// #[derive(DescribeConfig, DeserializeConfig)]
+    /// struct DbConfig {
+    ///     url: String,
+    /// }
+    ///
+    /// #[derive(DescribeConfig, DeserializeConfig)]
+    /// struct AppConfig {
+    ///     #[config(nest)]
+    ///     db: DbConfig,
+    /// }
+    ///
+    /// let schema = ConfigSchema::new(&AppConfig::DESCRIPTION, "app");
+    /// let db_schema = schema.subschema("app.db");
+    /// let paths: Vec<_> = db_schema.iter().map(|config| config.prefix()).collect();
+    /// assert_eq!(paths, [""]);
+    ///
+    /// let repo = ConfigRepository::new(&db_schema).with(config!("url": "postgres://localhost/db"));
+    /// let config: DbConfig = repo.single()?.parse()?;
+    /// assert_eq!(config.url, "postgres://localhost/db");
+    /// # anyhow::Ok(())
+    /// ```
+    #[must_use]
+    #[allow(clippy::missing_panics_doc)] // only panics on internal errors
+    pub fn subschema(&self, prefix: &str) -> Self {
+        fn reroot<'a>(prefix: &str, path: &'a str) -> Option<&'a str> {
+            if prefix.is_empty() {
+                Some(path)
+            } else if path == prefix {
+                Some("")
+            } else {
+                path.strip_prefix(prefix)?.strip_prefix('.')
+            }
+        }
+
+        let reroot_owned = |path: &str| Some(Cow::Owned(reroot(prefix, path)?.to_owned()));
+        // Re-rooting retains relative paths in the subtree, so it cannot introduce conflicts.
+        let mut subschema = self
+            .rebuild(
+                |config| reroot_owned(config.prefix()),
+                |alias| reroot_owned(alias),
+                |config| config.data.coerce_serde_enums,
+            )
+            .expect("internal error: failed re-rooting schema");
+        // The catch-all param belongs to a config in the subtree iff it's a strict descendant of `prefix`.
+        let catch_all = self
+            .catch_all
+            .and_then(|path| reroot(prefix, path))
+            .filter(|path| !path.is_empty());
+        if let Some(path) = catch_all {
+            subschema
+                .set_catch_all(path)
+                .expect("internal error: failed re-rooting catch-all param");
+        }
+        subschema
+    }
+
+    /// Freezes this schema, converting it into an immutable handle that can be cheaply cloned and shared
+    /// among threads.
+    pub fn freeze(self) -> FrozenSchema {
+        FrozenSchema(Arc::new(self))
+    }
+
+    /// Rebuilds this schema from scratch, mapping canonical config prefixes and serde enum coercion flags
+    /// for top-level configs (nested configs inherit the flag from their parent). Explicit aliases and deprecations
+    /// are retained (with aliases mapped using `map_alias`); aliases derived from parent configs are regenerated.
+    /// Configs for which `map_prefix` returns `None` are dropped, as are explicit aliases for which `map_alias`
+    /// returns `None`. The catch-all param is not retained.
+    fn rebuild(
+        &self,
+        map_prefix: impl Fn(ConfigRef<'_>) -> Option<Cow<'static, str>>,
+        map_alias: impl Fn(&Cow<'static, str>) -> Option<Cow<'static, str>>,
+        coerce_serde_enums: impl Fn(ConfigRef<'_>) -> bool,
+    ) -> anyhow::Result<Self> {
+        let mut rebuilt = Self {
+            coerce_serde_enums: self.coerce_serde_enums,
+            accept_external_tag: self.accept_external_tag,
+            kv_delimiter: self.kv_delimiter,
+            unset_marker: self.unset_marker,
+            ..Self::default()
+        };
+        let configs: Vec<_> = self
+            .iter()
+            .filter_map(|config| Some((config, map_prefix(config)?)))
+            .collect();
+
+        // Insert root configs; other configs are inserted recursively.
+        for (config, prefix) in &configs {
+            let is_root = config.is_top_level()
+                || config
+                    .parent_link()
+                    .map_or(true, |(parent, _)| map_prefix(parent).is_none());
+            if !is_root {
+                continue;
+            }
+            let mut patched = PatchedSchema::new(&mut rebuilt);
+            patched.insert_config(
+                prefix.clone(),
+                config.metadata(),
+                coerce_serde_enums(*config),
+            )?;
+            patched.commit();
+        }
+
+        for (config, prefix) in &configs {
+            let config_ty = config.metadata().ty.id();
+            let derived_paths: HashSet<_> = config
+                .parent_link()
+                .into_iter()
+                .flat_map(|(parent, nested)| {
+                    parent
+                        .data
+                        .all_paths_for_child(nested.name, nested.aliases, nested.tag_variant)
+                })
+                .map(|(path, _)| path)
+                .collect();
+
+            for (alias, options) in config.data.all_paths.iter().skip(1) {
+                if derived_paths.contains(alias.as_ref()) {
+                    continue; // Will be regenerated from the parent config aliases
+                }
+                let Some(alias) = map_alias(alias) else {
+                    continue;
+                };
+                let mut patched = PatchedSchema::new(&mut rebuilt);
+                patched.insert_alias(prefix.to_string(), config_ty, alias, options.clone())?;
+                patched.commit();
+            }
+            if let Some(message) = config.data.deprecation {
+                let data = rebuilt
+                    .configs
+                    .get_mut(prefix.as_ref())
+                    .and_then(|configs| configs.inner.get_mut(&config_ty))
+                    .expect("internal error: rebuilt config is not in the schema");
+                data.deprecation = Some(message);
+            }
+        }
+        Ok(rebuilt)
+    }
+}
+
+/// Immutable [`ConfigSchema`] that can be cheaply cloned and shared among threads. Produced by [`ConfigSchema::freeze()`].
+///
+/// The frozen schema dereferences to [`ConfigSchema`], so all read-only schema methods (e.g., [`ConfigSchema::get()`],
+/// [`ConfigSchema::iter()`] or [`ConfigSchema::locate()`]) are available for it, and it can be used to create
+/// a [`ConfigRepository`].
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::{testing, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig};
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(default_t = 3)]
+///     workers: usize,
+/// }
+///
+/// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test").freeze();
+/// let handles: Vec<_> = (0..2).map(|_| {
+///     let schema = schema.clone();
+///     std::thread::spawn(move || {
+///         let repo = ConfigRepository::new(&schema);
+///         repo.single::<TestConfig>().unwrap().parse().unwrap().workers
+///     })
+/// }).collect();
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), 3);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrozenSchema(Arc<ConfigSchema>);
+
+impl ops::Deref for FrozenSchema {
+    type Target = ConfigSchema;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<ConfigSchema> for FrozenSchema {
+    fn as_ref(&self) -> &ConfigSchema {
+        &self.0
+    }
+}
+
+impl From<ConfigSchema> for FrozenSchema {
+    fn from(schema: ConfigSchema) -> Self {
+        schema.freeze()
+    }
 }
 
 /// [`ConfigSchema`] together with a patch that can be atomically committed.
@@ -477,19 +1770,20 @@ impl<'a> PatchedSchema<'a> {
 
     fn insert_config(
         &mut self,
-        prefix: &'static str,
+        prefix: Cow<'static, str>,
         metadata: &'static ConfigMetadata,
         coerce_serde_enums: bool,
     ) -> anyhow::Result<()> {
         self.insert_recursively(
-            prefix.into(),
+            prefix.clone(),
             true,
             ConfigData {
                 metadata,
                 parent_link: None,
                 is_top_level: true,
                 coerce_serde_enums,
-                all_paths: vec![(prefix.into(), AliasOptions::new())],
+                all_paths: vec![(prefix, MountOptions::default())],
+                deprecation: None,
             },
         )
     }
@@ -524,15 +1818,11 @@ impl<'a> PatchedSchema<'a> {
         &mut self,
         prefix: String,
         config_id: any::TypeId,
-        alias: Pointer<'static>,
-        options: AliasOptions,
+        alias: Cow<'static, str>,
+        options: MountOptions,
     ) -> anyhow::Result<()> {
         let config_data = &self.base.configs[prefix.as_str()].inner[&config_id];
-        if config_data
-            .all_paths
-            .iter()
-            .any(|(name, _)| name == alias.0)
-        {
+        if config_data.all_paths.iter().any(|(name, _)| *name == alias) {
             return Ok(()); // shortcut in the no-op case
         }
 
@@ -545,7 +1835,8 @@ impl<'a> PatchedSchema<'a> {
                 parent_link: config_data.parent_link,
                 is_top_level: config_data.is_top_level,
                 coerce_serde_enums: config_data.coerce_serde_enums,
-                all_paths: vec![(alias.0.into(), options)],
+                all_paths: vec![(alias, options)],
+                deprecation: config_data.deprecation,
             },
         )
     }
@@ -570,6 +1861,7 @@ impl<'a> PatchedSchema<'a> {
                 is_top_level: false,
                 coerce_serde_enums: data.coerce_serde_enums,
                 all_paths,
+                deprecation: None,
             };
             (prefix.join(nested.name), config_data)
         })
@@ -582,10 +1874,29 @@ impl<'a> PatchedSchema<'a> {
         mut data: ConfigData,
     ) -> anyhow::Result<()> {
         let config_name = data.metadata.ty.name_in_code();
-        let config_paths = data.all_paths.iter().map(|(name, _)| name.as_ref());
-        let config_paths = iter::once(prefix.as_ref()).chain(config_paths);
+        let mut rest_params = data.metadata.params.iter().filter(|param| param.is_rest);
+        if let Some(rest_param) = rest_params.next() {
+            anyhow::ensure!(
+                rest_param.expecting.contains(BasicTypes::OBJECT),
+                "`rest` param `{name}` [Rust field: `{field}`] from config `{config_name}` must accept objects; it expects {expecting}",
+                name = rest_param.name,
+                field = rest_param.rust_field_name,
+                expecting = rest_param.expecting
+            );
+            anyhow::ensure!(
+                rest_params.next().is_none(),
+                "Config `{config_name}` has multiple `rest` params"
+            );
+        }
+
+        let default_options = MountOptions::default();
+        let config_paths = data
+            .all_paths
+            .iter()
+            .map(|(name, options)| (name.as_ref(), options));
+        let config_paths = iter::once((prefix.as_ref(), &default_options)).chain(config_paths);
 
-        for path in config_paths {
+        for (path, options) in config_paths {
             if let Some(mount) = self.mount(path) {
                 match mount {
                     MountingPoint::Config => { /* OK */ }
@@ -597,15 +1908,17 @@ impl<'a> PatchedSchema<'a> {
                     }
                 }
             }
-            self.patch
-                .mounting_points
-                .insert(path.to_owned(), MountingPoint::Config);
+            self.patch.mounting_points.insert_via_alias(
+                path.to_owned(),
+                MountingPoint::Config,
+                options,
+            );
         }
 
         for param in data.metadata.params {
             let all_paths = data.all_paths_for_param(param);
 
-            for (name_i, (full_name, _)) in all_paths.enumerate() {
+            for (name_i, (full_name, options)) in all_paths.enumerate() {
                 let mut was_canonical = false;
                 if let Some(mount) = self.mount(&full_name) {
                     let prev_expecting = match mount {
@@ -637,12 +1950,13 @@ impl<'a> PatchedSchema<'a> {
                     }
                 }
                 let is_canonical = was_canonical || name_i == 0;
-                self.patch.mounting_points.insert(
+                self.patch.mounting_points.insert_via_alias(
                     full_name,
                     MountingPoint::Param {
                         expecting: param.expecting,
                         is_canonical,
                     },
+                    &options,
                 );
             }
         }
@@ -657,6 +1971,7 @@ impl<'a> PatchedSchema<'a> {
             let mut all_paths = prev_data.all_paths.clone();
             all_paths.extend_from_slice(&data.all_paths);
             data.all_paths = all_paths;
+            data.deprecation = data.deprecation.or(prev_data.deprecation);
         }
 
         self.patch
@@ -3,6 +3,7 @@ use std::{
     collections::{BTreeSet, HashMap},
 };
 
+use super::MountOptions;
 use crate::{metadata::BasicTypes, value::Pointer};
 
 /// Mounting point info sufficient to resolve the mounted config / param.
@@ -76,15 +77,61 @@ impl Ord for KvPath {
     }
 }
 
+/// Entry in [`MountingPoints`].
+#[derive(Debug, Clone)]
+struct MountEntry {
+    mount: MountingPoint,
+    /// Options of [conditional](crate::metadata::AliasOptions::condition) aliases if the path is mounted only via such aliases.
+    /// Empty if the path is mounted unconditionally.
+    conditional_aliases: Vec<MountOptions>,
+}
+
+impl MountEntry {
+    /// Checks whether the mounting point is active, i.e., it's mounted unconditionally,
+    /// or at least one of the conditional aliases it's mounted via is active.
+    fn is_active(&self) -> bool {
+        self.conditional_aliases.is_empty()
+            || self.conditional_aliases.iter().any(MountOptions::is_active)
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub(super) struct MountingPoints {
     kv_paths: BTreeSet<KvPath>,
-    inner: HashMap<String, MountingPoint>,
+    inner: HashMap<String, MountEntry>,
 }
 
 impl MountingPoints {
     pub(super) fn get(&self, path: &str) -> Option<&MountingPoint> {
-        self.inner.get(path)
+        self.inner.get(path).map(|entry| &entry.mount)
+    }
+
+    fn active_paths(&self) -> impl Iterator<Item = &str> + '_ {
+        self.inner
+            .iter()
+            .filter_map(|(path, entry)| entry.is_active().then_some(path.as_str()))
+    }
+
+    pub(super) fn contains_path_or_descendants(&self, path: &str) -> bool {
+        self.active_paths().any(|mount_path| {
+            mount_path
+                .strip_prefix(path)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        })
+    }
+
+    /// Iterates over names of direct children of `prefix` that have mounted configs or params (possibly at descendant paths).
+    /// Mounting points only reachable via inactive conditional aliases are skipped.
+    pub(super) fn child_names<'s>(&'s self, prefix: &'s str) -> impl Iterator<Item = &'s str> + 's {
+        self.active_paths().filter_map(move |mount_path| {
+            let rest = if prefix.is_empty() {
+                mount_path
+            } else {
+                mount_path.strip_prefix(prefix)?.strip_prefix('.')?
+            };
+            let child = rest.split('.').next().unwrap();
+            (!child.is_empty()).then_some(child)
+        })
     }
 
     pub(super) fn by_kv_path<'s>(
@@ -98,17 +145,51 @@ impl MountingPoints {
             .range(..=KvPath::from(kv_path))
             .rev()
             .take_while(|&path| path.is_equivalent(kv_path));
-        kv_paths.map(|path| (Pointer(&path.0), &self.inner[&path.0]))
+        kv_paths.map(|path| (Pointer(&path.0), &self.inner[&path.0].mount))
     }
 
+    #[cfg(test)]
     pub(super) fn insert(&mut self, path: String, mount: MountingPoint) {
+        self.insert_via_alias(path, mount, &MountOptions::default());
+    }
+
+    /// Inserts a mounting point via an alias with the specified options.
+    pub(super) fn insert_via_alias(
+        &mut self,
+        path: String,
+        mount: MountingPoint,
+        options: &MountOptions,
+    ) {
         self.kv_paths.insert(KvPath(path.clone()));
-        self.inner.insert(path, mount);
+        let entry = MountEntry {
+            mount,
+            conditional_aliases: if options.is_conditional() {
+                vec![options.clone()]
+            } else {
+                vec![]
+            },
+        };
+        self.insert_entry(path, entry);
+    }
+
+    fn insert_entry(&mut self, path: String, mut entry: MountEntry) {
+        if let Some(prev_entry) = self.inner.get(&path) {
+            if prev_entry.conditional_aliases.is_empty() {
+                entry.conditional_aliases.clear();
+            } else if !entry.conditional_aliases.is_empty() {
+                let mut aliases = prev_entry.conditional_aliases.clone();
+                aliases.append(&mut entry.conditional_aliases);
+                entry.conditional_aliases = aliases;
+            }
+        }
+        self.inner.insert(path, entry);
     }
 
     pub(super) fn extend(&mut self, mut from: Self) {
         self.kv_paths.append(&mut from.kv_paths);
-        self.inner.extend(from.inner);
+        for (path, entry) in from.inner {
+            self.insert_entry(path, entry);
+        }
     }
 }
 
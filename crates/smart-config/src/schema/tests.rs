@@ -1,12 +1,15 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use assert_matches::assert_matches;
 
 use super::*;
 use crate::{
     metadata::BasicTypes,
-    testonly::{AliasedConfig, EnumConfig, NestedAliasedConfig, NestedConfig},
-    value::{StrValue, Value},
+    testonly::{
+        AliasedConfig, CompoundConfig, ConfigWithNesting, EnumConfig, NestedAliasedConfig,
+        NestedConfig, RenamedEnumConfig, SimpleEnum,
+    },
+    value::{Pointer, StrValue, Value},
     ConfigRepository, DescribeConfig, DeserializeConfig, Environment,
 };
 
@@ -20,7 +23,8 @@ struct TestConfig {
     #[config(deprecated = "string", default = TestConfig::default_str)]
     str: String,
     /// Optional value.
-    #[config(rename = "optional")]
+    #[config(rename = "optional", since = "0.2.0")]
+    #[config(unit = "items", examples("3", "42"))]
     optional_int: Option<u32>,
 }
 
@@ -58,6 +62,9 @@ fn getting_config_metadata() {
     assert_eq!(str_metadata.help, "String value.");
     assert_eq!(str_metadata.rust_type.name_in_code(), "String");
     assert_eq!(str_metadata.default_value_json().unwrap(), "default");
+    assert_eq!(str_metadata.since(), None);
+    assert_eq!(str_metadata.unit(), None);
+    assert_eq!(str_metadata.examples(), [] as [&str; 0]);
 
     let optional_metadata = &metadata.params[1];
     assert_eq!(optional_metadata.name, "optional");
@@ -66,6 +73,590 @@ fn getting_config_metadata() {
     let name_in_code = optional_metadata.rust_type.name_in_code();
     assert!(name_in_code.starts_with("Option"), "{name_in_code}");
     assert_eq!(optional_metadata.expecting, BasicTypes::INTEGER);
+    assert_eq!(optional_metadata.since(), Some("0.2.0"));
+    assert_eq!(optional_metadata.unit(), Some("items"));
+    assert_eq!(optional_metadata.examples(), ["3", "42"]);
+}
+
+#[test]
+fn looking_up_params_in_config_ref() {
+    let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    let config = schema.single(&TestConfig::DESCRIPTION).unwrap();
+
+    assert_eq!(config.param("optional_int").unwrap().name, "optional");
+    assert!(config.param("optional").is_none());
+    assert_eq!(
+        config.param_by_name("optional").unwrap().rust_field_name,
+        "optional_int"
+    );
+    assert_eq!(
+        config.param_by_name("string").unwrap().rust_field_name,
+        "str"
+    );
+    assert!(config.param_by_name("optional_int").is_none());
+    assert!(config.param_by_name("missing").is_none());
+}
+
+#[test]
+fn iterating_over_params() {
+    let mut schema = ConfigSchema::new(&NestingConfig::DESCRIPTION, "");
+    schema.insert(&NestedConfig::DESCRIPTION, "nested").unwrap();
+
+    let params: Vec<_> = schema
+        .iter_params()
+        .map(|(path, config, param)| {
+            (
+                path,
+                config.prefix(),
+                config.metadata().ty.name_in_code(),
+                param.rust_field_name,
+            )
+        })
+        .collect();
+    let expected = [
+        ("bool_value", "", "NestingConfig", "bool_value"),
+        (
+            "hierarchical.optional",
+            "hierarchical",
+            "TestConfig",
+            "optional_int",
+        ),
+        ("hierarchical.str", "hierarchical", "TestConfig", "str"),
+        ("nested.map", "nested", "NestedConfig", "map"),
+        ("nested.other_int", "nested", "NestedConfig", "other_int"),
+        ("nested.renamed", "nested", "NestedConfig", "simple_enum"),
+        ("optional", "", "TestConfig", "optional_int"),
+        ("str", "", "TestConfig", "str"),
+    ];
+    let expected: Vec<_> = expected
+        .into_iter()
+        .map(|(path, prefix, config, field)| (path.to_owned(), prefix, config, field))
+        .collect();
+    assert_eq!(params, expected);
+}
+
+#[test]
+fn counting_configs() {
+    let mut schema = ConfigSchema::default();
+    assert!(schema.is_empty());
+    assert_eq!(schema.len(), 0);
+
+    schema.insert(&NestingConfig::DESCRIPTION, "").unwrap();
+    assert!(!schema.is_empty());
+    assert_eq!(schema.len(), schema.iter().count());
+    let len = schema.len();
+    assert!(len > 1, "{len}");
+
+    // Re-inserting the same config at the same location is a no-op.
+    schema.insert(&NestingConfig::DESCRIPTION, "").unwrap();
+    assert_eq!(schema.len(), len);
+    schema
+        .insert(&NestedConfig::DESCRIPTION, "standalone")
+        .unwrap();
+    assert_eq!(schema.len(), len + 1);
+    assert_eq!(schema.len(), schema.iter().count());
+}
+
+#[test]
+fn listing_canonical_param_paths() {
+    let mut schema = ConfigSchema::new(&NestingConfig::DESCRIPTION, "test");
+    schema.insert(&TestConfig::DESCRIPTION, "other").unwrap();
+
+    let param = &TestConfig::DESCRIPTION.params[1];
+    let paths: Vec<_> = schema
+        .canonical_paths(&TestConfig::DESCRIPTION, param)
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "other.optional",
+            "test.optional",
+            "test.hierarchical.optional"
+        ]
+    );
+
+    let foreign_param = &NestingConfig::DESCRIPTION.params[0];
+    let paths: Vec<_> = schema
+        .canonical_paths(&TestConfig::DESCRIPTION, foreign_param)
+        .collect();
+    assert!(paths.is_empty(), "{paths:?}");
+    let paths: Vec<_> = schema
+        .canonical_paths(&NestingConfig::DESCRIPTION, foreign_param)
+        .collect();
+    assert_eq!(paths, ["test.bool_value"]);
+}
+
+#[test]
+fn finding_multi_mounted_configs() {
+    let mut schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    assert!(schema.find_multi_mounted().is_empty());
+
+    schema.insert(&TestConfig::DESCRIPTION, "other").unwrap();
+    schema.insert(&NestedConfig::DESCRIPTION, "nested").unwrap();
+    let multi_mounted = schema.find_multi_mounted();
+    assert_eq!(multi_mounted.len(), 1);
+    let (metadata, prefixes) = &multi_mounted[0];
+    assert_eq!(metadata.ty, TestConfig::DESCRIPTION.ty);
+    assert_eq!(*prefixes, ["other", "test"]);
+}
+
+#[test]
+fn finding_conflicting_aliases() {
+    #[derive(Debug, DescribeConfig)]
+    #[config(crate = crate)]
+    struct ConflictingConfig {
+        #[config(default)]
+        string: String,
+    }
+
+    let mut schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    schema.insert(&TestConfig::DESCRIPTION, "other").unwrap();
+    assert!(schema.conflicting_aliases().is_empty());
+
+    schema
+        .insert(&ConflictingConfig::DESCRIPTION, "test")
+        .unwrap();
+    let conflicts = schema.conflicting_aliases();
+    assert_eq!(conflicts.len(), 1, "{conflicts:?}");
+    let conflict = &conflicts[0];
+    assert_eq!(conflict.path, "test.string");
+    let (first_config, first_param) = conflict.first;
+    assert_eq!(
+        first_config.metadata().ty,
+        ConflictingConfig::DESCRIPTION.ty
+    );
+    assert_eq!(first_param.name, "string");
+    let (second_config, second_param) = conflict.second;
+    assert_eq!(second_config.metadata().ty, TestConfig::DESCRIPTION.ty);
+    assert_eq!(second_config.prefix(), "test");
+    assert_eq!(second_param.name, "str");
+}
+
+#[test]
+fn asserting_schema_validity() {
+    #[derive(Debug, DescribeConfig)]
+    #[config(crate = crate)]
+    struct ConflictingConfig {
+        #[config(default)]
+        string: String,
+    }
+
+    let mut schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    schema.insert(&NestedConfig::DESCRIPTION, "nested").unwrap();
+    schema.assert_valid().unwrap();
+    schema.assert_no_conflicts();
+
+    schema.insert(&TestConfig::DESCRIPTION, "other").unwrap();
+    schema
+        .insert(&ConflictingConfig::DESCRIPTION, "test")
+        .unwrap();
+    let err = schema.assert_valid().unwrap_err().to_string();
+    assert!(err.starts_with("found 2 issue(s)"), "{err}");
+    assert!(
+        err.contains(
+            "path `test.string` is claimed by `ConflictingConfig.string` (config at `test`) \
+             and `TestConfig.str` (config at `test`)"
+        ),
+        "{err}"
+    );
+    assert!(
+        err.contains("config `TestConfig` is mounted at multiple locations: `other`, `test`"),
+        "{err}"
+    );
+}
+
+#[test]
+fn asserting_schema_validity_with_nested_configs() {
+    // `TestConfig` is nested twice in `NestingConfig`, which is not an issue.
+    let mut schema = ConfigSchema::new(&NestingConfig::DESCRIPTION, "test");
+    assert_eq!(schema.find_multi_mounted().len(), 1);
+    schema.assert_no_conflicts();
+
+    schema.insert(&TestConfig::DESCRIPTION, "other").unwrap();
+    schema.assert_no_conflicts();
+    schema.insert(&TestConfig::DESCRIPTION, "another").unwrap();
+    let err = schema.assert_valid().unwrap_err().to_string();
+    assert!(err.starts_with("found 1 issue(s)"), "{err}");
+    assert!(
+        err.contains("config `TestConfig` is mounted at multiple locations: `another`, `other`"),
+        "{err}"
+    );
+}
+
+#[test]
+fn asserting_schema_validity_with_unreachable_params() {
+    let mut schema = ConfigSchema::new(&NestingConfig::DESCRIPTION, "test");
+    // Simulate a mount overriding a canonical param path; this is impossible to obtain via the public API.
+    schema.mounting_points.insert(
+        "test.str".into(),
+        MountingPoint::Param {
+            is_canonical: false,
+            expecting: BasicTypes::STRING,
+        },
+    );
+
+    let err = schema.assert_valid().unwrap_err().to_string();
+    assert!(err.starts_with("found 1 issue(s)"), "{err}");
+    assert!(
+        err.contains("param `str` cannot receive a value via its canonical path `test.str`"),
+        "{err}"
+    );
+}
+
+#[test]
+#[should_panic(expected = "config `TestConfig` is mounted at multiple locations")]
+fn asserting_no_schema_conflicts() {
+    let mut schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    schema.insert(&TestConfig::DESCRIPTION, "other").unwrap();
+    schema.assert_no_conflicts();
+}
+
+#[test]
+fn resolving_param_paths() {
+    let mut schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    schema
+        .get_mut(&TestConfig::DESCRIPTION, "test")
+        .unwrap()
+        .push_alias("old")
+        .unwrap();
+    schema.insert(&NestedConfig::DESCRIPTION, "nested").unwrap();
+
+    let (config, param) = schema.param_at("test.str").unwrap();
+    assert_eq!(config.prefix(), "test");
+    assert_eq!(param.rust_field_name, "str");
+    // Param alias
+    let (config, param) = schema.param_at("test.string").unwrap();
+    assert_eq!(config.prefix(), "test");
+    assert_eq!(param.rust_field_name, "str");
+    // Config alias
+    let (config, param) = schema.param_at("old.str").unwrap();
+    assert_eq!(config.prefix(), "test");
+    assert_eq!(param.rust_field_name, "str");
+    // Path-like param aliases
+    for path in ["nested.experimental.enum", "top.enum"] {
+        let (config, param) = schema.param_at(path).unwrap();
+        assert_eq!(config.prefix(), "nested");
+        assert_eq!(param.rust_field_name, "simple_enum");
+    }
+
+    assert!(schema.param_at("test").is_none());
+    assert!(schema.param_at("test.missing").is_none());
+    assert!(schema.param_at("").is_none());
+}
+
+#[test]
+fn pushing_owned_config_alias() {
+    let tenant = "acme".to_owned();
+    let mut schema = ConfigSchema::new(&NestedConfig::DESCRIPTION, "nested");
+    schema
+        .get_mut(&NestedConfig::DESCRIPTION, "nested")
+        .unwrap()
+        .push_alias_owned(format!("tenants.{tenant}"))
+        .unwrap();
+
+    let config = schema.single(&NestedConfig::DESCRIPTION).unwrap();
+    let aliases: Vec<_> = config.aliases().map(|(alias, _)| alias).collect();
+    assert_eq!(aliases, ["tenants.acme"]);
+    let (config, param) = schema.param_at("tenants.acme.renamed").unwrap();
+    assert_eq!(config.prefix(), "nested");
+    assert_eq!(param.rust_field_name, "simple_enum");
+}
+
+#[test]
+fn inserting_config_at_runtime_prefix() {
+    let mut schema = ConfigSchema::default();
+    for tenant in ["acme", "umbrella"] {
+        schema
+            .insert_at(&NestingConfig::DESCRIPTION, format!("tenants.{tenant}"))
+            .unwrap();
+    }
+
+    let prefixes: Vec<_> = schema.locate(&TestConfig::DESCRIPTION).collect();
+    assert_eq!(
+        prefixes,
+        [
+            "tenants.acme",
+            "tenants.acme.hierarchical",
+            "tenants.umbrella",
+            "tenants.umbrella.hierarchical"
+        ]
+    );
+    let (config, param) = schema
+        .param_at("tenants.umbrella.hierarchical.optional")
+        .unwrap();
+    assert_eq!(config.prefix(), "tenants.umbrella.hierarchical");
+    assert_eq!(param.rust_field_name, "optional_int");
+
+    let json = crate::config!("tenants.umbrella.hierarchical.optional": 42);
+    let repo = ConfigRepository::new(&schema).with(json);
+    let config: NestingConfig = repo.get("tenants.umbrella").unwrap().parse().unwrap();
+    assert_eq!(config.hierarchical.optional_int, Some(42));
+}
+
+#[test]
+fn renaming_config_prefix() {
+    let mut schema = ConfigSchema::default();
+    schema
+        .insert(&ConfigWithNesting::DESCRIPTION, "old")
+        .unwrap()
+        .push_alias("older")
+        .unwrap();
+    schema.insert(&TestConfig::DESCRIPTION, "test").unwrap();
+
+    schema
+        .rename_prefix(&ConfigWithNesting::DESCRIPTION, "old", "new")
+        .unwrap();
+    assert!(schema.get(&ConfigWithNesting::DESCRIPTION, "old").is_none());
+    let config = schema.get(&ConfigWithNesting::DESCRIPTION, "new").unwrap();
+    let aliases: Vec<_> = config
+        .aliases()
+        .map(|(alias, options)| (alias, options.is_deprecated))
+        .collect();
+    assert_eq!(aliases, [("older", false), ("old", true)]);
+    let nested = schema
+        .get(&NestedConfig::DESCRIPTION, "new.nested")
+        .unwrap();
+    let nested_aliases: HashSet<_> = nested.aliases().map(|(alias, _)| alias).collect();
+    assert!(
+        nested_aliases.contains("older.nested"),
+        "{nested_aliases:?}"
+    );
+    assert!(nested_aliases.contains("old.nest"), "{nested_aliases:?}");
+    assert!(schema.get(&TestConfig::DESCRIPTION, "test").is_some());
+
+    let json = crate::config!("old.value": 1, "older.nested.renamed": "first");
+    let repo = ConfigRepository::new(&schema).with(json);
+    let config: ConfigWithNesting = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.value, 1);
+    assert_eq!(config.nested.simple_enum, SimpleEnum::First);
+
+    // Errors
+    schema
+        .rename_prefix(&ConfigWithNesting::DESCRIPTION, "old", "other")
+        .unwrap_err();
+    schema
+        .rename_prefix(&NestedConfig::DESCRIPTION, "new.nested", "other")
+        .unwrap_err();
+    let err = schema
+        .rename_prefix(&ConfigWithNesting::DESCRIPTION, "new", "test.str")
+        .unwrap_err();
+    assert!(err.to_string().contains("already mounted"), "{err}");
+    // The schema must not change on error.
+    assert!(schema.get(&ConfigWithNesting::DESCRIPTION, "new").is_some());
+    assert!(schema
+        .get(&ConfigWithNesting::DESCRIPTION, "test.str")
+        .is_none());
+}
+
+#[test]
+fn getting_param_defaults() {
+    let schema = ConfigSchema::new(&NestedConfig::DESCRIPTION, "nested");
+    let config = schema.single(&NestedConfig::DESCRIPTION).unwrap();
+
+    let simple_enum = config.param("simple_enum").unwrap();
+    assert!(config.default_for(simple_enum).is_none());
+
+    let other_int = config.param("other_int").unwrap();
+    let default = config.default_for(other_int).unwrap();
+    assert_matches!(&default.inner, Value::Number(num) if num.as_u64() == Some(42));
+    assert_matches!(
+        default.origin.as_ref(),
+        ValueOrigin::Synthetic { transform, .. } if transform == "default"
+    );
+
+    let map = config.param("map").unwrap();
+    let default = config.default_for(map).unwrap();
+    assert_matches!(&default.inner, Value::Object(obj) if obj.is_empty());
+}
+
+#[test]
+fn getting_nested_param_defaults() {
+    #[derive(DescribeConfig)]
+    #[config(crate = crate)]
+    struct ConfigWithObjectDefault {
+        #[config(default_t = HashMap::from([("first".to_owned(), vec![1, 2])]))]
+        map: HashMap<String, Vec<u32>>,
+    }
+
+    let schema = ConfigSchema::new(&ConfigWithObjectDefault::DESCRIPTION, "test");
+    let config = schema
+        .single(&ConfigWithObjectDefault::DESCRIPTION)
+        .unwrap();
+    let map = config.param("map").unwrap();
+    let default = config.default_for(map).unwrap();
+    assert_matches!(
+        default.origin.as_ref(),
+        ValueOrigin::Synthetic { transform, .. } if transform == "default"
+    );
+
+    let array = default.get(Pointer("first")).unwrap();
+    assert_matches!(&array.inner, Value::Array(items) if items.len() == 2);
+    assert_matches!(
+        array.origin.as_ref(),
+        ValueOrigin::Path { source, path }
+            if path == "first" && Arc::ptr_eq(source, &default.origin)
+    );
+    let item = default.get(Pointer("first.1")).unwrap();
+    assert_matches!(&item.inner, Value::Number(num) if num.as_u64() == Some(2));
+    assert_matches!(
+        item.origin.as_ref(),
+        ValueOrigin::Path { source, path }
+            if path == "first.1" && Arc::ptr_eq(source, &default.origin)
+    );
+}
+
+#[test]
+fn generating_env_template() {
+    let mut schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    schema.insert(&NestedConfig::DESCRIPTION, "nested").unwrap();
+    let template = schema.to_env_template();
+    let expected = "\
+        # Required.\n\
+        NESTED_RENAMED=\n\
+        \n\
+        NESTED_OTHER_INT=42\n\
+        \n\
+        NESTED_MAP__JSON={}\n\
+        \n\
+        # String value.\n\
+        TEST_STR=default\n\
+        \n\
+        # Optional value.\n\
+        # TEST_OPTIONAL=\n";
+    assert_eq!(template, expected);
+
+    let filled_template = template.replace("NESTED_RENAMED=", "NESTED_RENAMED=first");
+    let mut env = Environment::from_dotenv("test.env", &filled_template).unwrap();
+    env.coerce_json().unwrap();
+    let repo = ConfigRepository::new(&schema).with(env);
+    let nested: NestedConfig = repo.single().unwrap().parse().unwrap();
+    assert_eq!(nested.simple_enum, SimpleEnum::First);
+    assert_eq!(nested.other_int, 42);
+    assert!(nested.map.is_empty());
+    let config: TestConfig = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.str, "default");
+    assert_eq!(config.optional_int, None);
+}
+
+#[test]
+fn env_template_escapes_strings_and_hides_secrets() {
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct EscapedConfig {
+        #[config(default_t = "say \"hi\"\nC:\\path #1".into())]
+        text: String,
+        #[config(default_t = "hunter2".into(), secret)]
+        password: String,
+    }
+
+    let schema = ConfigSchema::new(&EscapedConfig::DESCRIPTION, "app");
+    let template = schema.to_env_template();
+    let expected = "\
+        APP_TEXT=\"say \\\"hi\\\"\\nC:\\\\path #1\"\n\
+        \n\
+        # Secret.\n\
+        # APP_PASSWORD=\n";
+    assert_eq!(template, expected);
+    assert!(!template.contains("hunter2"), "{template}");
+
+    let env = Environment::from_dotenv("test.env", &template).unwrap();
+    let repo = ConfigRepository::new(&schema).with(env);
+    let config: EscapedConfig = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.text, "say \"hi\"\nC:\\path #1");
+    assert_eq!(config.password, "hunter2");
+}
+
+#[test]
+fn visiting_schema() {
+    #[derive(Debug, Default)]
+    struct RecordingVisitor(Vec<String>);
+
+    impl SchemaVisitor for RecordingVisitor {
+        fn enter_config(&mut self, config: ConfigRef<'_>) {
+            let name = config.metadata().ty.name_in_code();
+            self.0.push(format!("enter {name} @ {:?}", config.prefix()));
+        }
+
+        fn visit_param(&mut self, config: ConfigRef<'_>, param: &'static ParamMetadata) {
+            self.0.push(format!(
+                "param {}",
+                Pointer(config.prefix()).join(param.name)
+            ));
+        }
+
+        fn exit_config(&mut self, config: ConfigRef<'_>) {
+            let name = config.metadata().ty.name_in_code();
+            self.0.push(format!("exit {name} @ {:?}", config.prefix()));
+        }
+    }
+
+    let mut schema = ConfigSchema::new(&NestingConfig::DESCRIPTION, "");
+    schema.insert(&NestedConfig::DESCRIPTION, "nested").unwrap();
+    let mut visitor = RecordingVisitor::default();
+    schema.visit(&mut visitor);
+
+    assert_eq!(
+        visitor.0,
+        [
+            "enter NestingConfig @ \"\"",
+            "param bool_value",
+            "enter TestConfig @ \"\"",
+            "param str",
+            "param optional",
+            "exit TestConfig @ \"\"",
+            "enter TestConfig @ \"hierarchical\"",
+            "param hierarchical.str",
+            "param hierarchical.optional",
+            "exit TestConfig @ \"hierarchical\"",
+            "exit NestingConfig @ \"\"",
+            "enter NestedConfig @ \"nested\"",
+            "param nested.renamed",
+            "param nested.other_int",
+            "param nested.map",
+            "exit NestedConfig @ \"nested\"",
+        ]
+    );
+}
+
+#[test]
+fn diffing_schemas() {
+    #[derive(Debug, DescribeConfig)]
+    #[config(crate = crate)]
+    struct UpdatedTestConfig {
+        #[config(alias = "string")]
+        str: u64,
+        #[config(default)]
+        timeout_sec: u64,
+    }
+
+    let old_schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    assert!(old_schema.diff(&old_schema).is_empty());
+
+    let mut new_schema = ConfigSchema::default();
+    new_schema
+        .insert(&UpdatedTestConfig::DESCRIPTION, "test")
+        .unwrap()
+        .push_alias("alias")
+        .unwrap();
+
+    let diff = old_schema.diff(&new_schema);
+    assert!(!diff.is_empty());
+    let added: Vec<_> = diff.added_params().map(|(path, ..)| path).collect();
+    assert_eq!(added, ["test.timeout_sec"]);
+    let removed: Vec<_> = diff
+        .removed_params()
+        .map(|(path, config, param)| {
+            assert_eq!(config.prefix(), "test");
+            assert_eq!(param.rust_field_name, "optional_int");
+            path
+        })
+        .collect();
+    assert_eq!(removed, ["test.optional"]);
+    let changed: Vec<_> = diff.changed_types().collect();
+    assert_eq!(
+        changed,
+        [("test.str", BasicTypes::STRING, BasicTypes::INTEGER)]
+    );
 }
 
 #[test]
@@ -144,6 +735,11 @@ fn using_nesting() {
     assert_eq!(config_prefixes, [""]);
     let config_prefixes: HashSet<_> = schema.locate(&TestConfig::DESCRIPTION).collect();
     assert_eq!(config_prefixes, HashSet::from(["", "hierarchical"]));
+    let config_refs: Vec<_> = schema.get_all(&TestConfig::DESCRIPTION).collect();
+    let config_prefixes: Vec<_> = config_refs.iter().map(ConfigRef::prefix).collect();
+    assert_eq!(config_prefixes, ["", "hierarchical"]);
+    assert!(config_refs.iter().all(|config| !config.is_top_level()));
+    assert_eq!(schema.get_all(&NestingConfig::DESCRIPTION).count(), 1);
 
     let refs: Vec<_> = schema
         .iter()
@@ -224,6 +820,27 @@ fn using_nesting() {
     assert_eq!(config.flattened.optional_int, Some(777));
 }
 
+#[test]
+fn getting_tag_variant_for_nested_config() {
+    let mut schema = ConfigSchema::new(&EnumConfig::DESCRIPTION, "enum");
+    schema
+        .insert(&ConfigWithNesting::DESCRIPTION, "nesting")
+        .unwrap();
+
+    let top_level = schema.single(&EnumConfig::DESCRIPTION).unwrap();
+    assert!(top_level.tag_variant().is_none());
+    let nested = schema.get(&NestedConfig::DESCRIPTION, "enum").unwrap();
+    let variant = nested.tag_variant().unwrap();
+    assert_eq!(variant.rust_name, "Nested");
+    assert_eq!(variant.name, "Nested");
+
+    // Nested config without an enclosing enum
+    let nested = schema
+        .get(&NestedConfig::DESCRIPTION, "nesting.nested")
+        .unwrap();
+    assert!(nested.tag_variant().is_none());
+}
+
 #[derive(Debug, DescribeConfig)]
 #[config(crate = crate)]
 struct BogusParamConfig {
@@ -323,6 +940,81 @@ fn mountpoint_errors() {
     assert!(err.contains("expects integer"), "{err}");
 }
 
+/// Checks that each param is reachable via its canonical path, i.e., its canonical mounting point cannot be shadowed
+/// by another config or param. Since schema construction rejects such mounts, there's no need for a separate lint.
+#[test]
+fn canonical_param_mounts_are_never_shadowed() {
+    #[derive(DescribeConfig)]
+    #[config(crate = crate)]
+    struct ShadowingConfig {
+        #[config(default)]
+        str: u64,
+    }
+
+    let mut schema = ConfigSchema::new(&NestingConfig::DESCRIPTION, "test");
+    schema
+        .insert(&AliasedConfig::DESCRIPTION, "aliased")
+        .unwrap()
+        .push_alias("test")
+        .unwrap();
+    schema.coerce_serde_enums(true);
+    schema.insert(&EnumConfig::DESCRIPTION, "enum").unwrap();
+    // Attempts to shadow params must fail and leave the schema unchanged.
+    schema
+        .insert(&ShadowingConfig::DESCRIPTION, "test")
+        .unwrap_err();
+    schema
+        .insert(&TestConfig::DESCRIPTION, "test.bool_value")
+        .unwrap_err();
+
+    assert!(schema.unreachable_params().is_empty());
+    let mut param_count = 0;
+    for (path, _, param) in schema.iter_params() {
+        param_count += 1;
+        // The path may be shared by multiple params (necessarily with the same expected type).
+        let (_, found_param) = schema.param_at(&path).unwrap();
+        assert_eq!(found_param.expecting, param.expecting, "{path}");
+    }
+    assert!(param_count > 10, "{param_count}");
+}
+
+#[test]
+fn detecting_unreachable_params() {
+    let mut schema = ConfigSchema::new(&NestingConfig::DESCRIPTION, "test");
+    assert!(schema.unreachable_params().is_empty());
+
+    // Simulate mounts overriding canonical param paths; these are impossible to obtain via the public API.
+    let expecting = BasicTypes::STRING;
+    schema.mounting_points.insert(
+        "test.str".into(),
+        MountingPoint::Param {
+            is_canonical: false,
+            expecting,
+        },
+    );
+    schema.mounting_points.insert(
+        "test.hierarchical".into(),
+        MountingPoint::Param {
+            is_canonical: true,
+            expecting,
+        },
+    );
+
+    let unreachable: Vec<_> = schema
+        .unreachable_params()
+        .into_iter()
+        .map(|(path, param)| (path, param.rust_field_name))
+        .collect();
+    assert_eq!(
+        unreachable,
+        [
+            ("test.hierarchical.optional".to_owned(), "optional_int"),
+            ("test.hierarchical.str".to_owned(), "str"),
+            ("test.str".to_owned(), "str"),
+        ]
+    );
+}
+
 #[test]
 fn aliasing_mountpoint_errors() {
     let mut schema = ConfigSchema::default();
@@ -437,19 +1129,61 @@ fn aliasing_info_for_nested_configs() {
     }
 }
 
+#[test]
+fn listing_deprecations() {
+    let mut schema = ConfigSchema::default();
+    schema
+        .insert(&TestConfig::DESCRIPTION, "test")
+        .unwrap()
+        .push_alias_with_options("old", AliasOptions::new().deprecated_since("0.2.0"))
+        .unwrap();
+
+    let deprecations = schema.deprecations();
+    let paths: Vec<_> = deprecations
+        .iter()
+        .map(|dep| (dep.alias.as_str(), dep.canonical_path.as_str()))
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            ("old.optional", "test.optional"),
+            ("old.str", "test.str"),
+            ("old.string", "test.str"),
+            ("test.string", "test.str"),
+        ]
+    );
+    for dep in &deprecations {
+        assert_eq!(dep.config.metadata().ty.name_in_code(), "TestConfig");
+        assert_eq!(dep.config.prefix(), "test");
+        assert!(dep.options.is_deprecated);
+    }
+    assert_eq!(deprecations[0].param.name, "optional");
+    assert_eq!(deprecations[0].options.deprecated_since, Some("0.2.0"));
+    assert_eq!(deprecations[3].param.name, "str");
+    assert_eq!(deprecations[3].options.deprecated_since, None);
+}
+
 #[test]
 fn aliasing_does_not_change_config_depth() {
     let mut schema = ConfigSchema::default();
     schema.insert(&AliasedConfig::DESCRIPTION, "test").unwrap();
 
     let expected_index_by_depth = BTreeSet::from([
-        (0, any::TypeId::of::<AliasedConfig>()),
-        (1, any::TypeId::of::<NestedAliasedConfig>()),
+        (0, "AliasedConfig", any::TypeId::of::<AliasedConfig>()),
+        (
+            1,
+            "NestedAliasedConfig",
+            any::TypeId::of::<NestedAliasedConfig>(),
+        ),
     ]);
     assert_eq!(schema.configs["test"].by_depth, expected_index_by_depth);
     assert_eq!(
         schema.configs["test.nested"].by_depth,
-        BTreeSet::from([(1, any::TypeId::of::<NestedAliasedConfig>())])
+        BTreeSet::from([(
+            1,
+            "NestedAliasedConfig",
+            any::TypeId::of::<NestedAliasedConfig>()
+        )])
     );
 
     schema
@@ -465,7 +1199,11 @@ fn aliasing_does_not_change_config_depth() {
     assert_eq!(schema.configs["test"].by_depth, expected_index_by_depth);
     assert_eq!(
         schema.configs["test.nested"].by_depth,
-        BTreeSet::from([(1, any::TypeId::of::<NestedAliasedConfig>())])
+        BTreeSet::from([(
+            1,
+            "NestedAliasedConfig",
+            any::TypeId::of::<NestedAliasedConfig>()
+        )])
     );
 
     // Insert a top-level config at the location of a nested config.
@@ -475,8 +1213,12 @@ fn aliasing_does_not_change_config_depth() {
 
     assert_eq!(schema.configs["test"].by_depth, expected_index_by_depth);
     let expected_nested_index_by_depth = BTreeSet::from([
-        (0, any::TypeId::of::<TestConfig>()),
-        (1, any::TypeId::of::<NestedAliasedConfig>()),
+        (0, "TestConfig", any::TypeId::of::<TestConfig>()),
+        (
+            1,
+            "NestedAliasedConfig",
+            any::TypeId::of::<NestedAliasedConfig>(),
+        ),
     ]);
     assert_eq!(
         schema.configs["test.nested"].by_depth,
@@ -546,4 +1288,485 @@ fn path_aliases_with_enum_coercion() {
             "with.str"
         ]
     );
+    let resolved: Vec<_> = config_ref.resolved_paths(str_param).collect();
+    assert_eq!(resolved.len(), str_paths.len());
+    for (resolved, path) in resolved.iter().zip(&str_paths) {
+        assert_eq!(resolved.path, *path);
+    }
+    assert_eq!(resolved[0].kind, ResolvedPathKind::Canonical);
+    assert_eq!(
+        resolved[1].kind,
+        ResolvedPathKind::Alias { deprecated: false }
+    );
+    for resolved in &resolved[2..] {
+        assert_eq!(
+            resolved.kind,
+            ResolvedPathKind::EnumCoerced { deprecated: false }
+        );
+    }
+}
+
+#[test]
+fn resolving_param_paths_with_kinds() {
+    let mut schema = ConfigSchema::default();
+    schema
+        .insert(&TestConfig::DESCRIPTION, "test")
+        .unwrap()
+        .push_alias("alias")
+        .unwrap();
+    let config_ref = schema.single(&TestConfig::DESCRIPTION).unwrap();
+    let str_param = &TestConfig::DESCRIPTION.params[0];
+    let resolved: Vec<_> = config_ref
+        .resolved_paths(str_param)
+        .map(|resolved| (resolved.path, resolved.kind))
+        .collect();
+    assert_eq!(
+        resolved,
+        [
+            ("test.str".to_owned(), ResolvedPathKind::Canonical),
+            (
+                "test.string".to_owned(),
+                ResolvedPathKind::Alias { deprecated: true }
+            ),
+            (
+                "alias.str".to_owned(),
+                ResolvedPathKind::Alias { deprecated: false }
+            ),
+            (
+                "alias.string".to_owned(),
+                ResolvedPathKind::Alias { deprecated: true }
+            ),
+        ]
+    );
+
+    let paths: Vec<_> = config_ref
+        .all_paths_for_param(str_param)
+        .map(|(path, options)| (path, options.is_deprecated))
+        .collect();
+    let expected_paths: Vec<_> = resolved
+        .iter()
+        .map(|(path, kind)| (path.clone(), kind.is_deprecated()))
+        .collect();
+    assert_eq!(paths, expected_paths);
+}
+
+#[test]
+fn exporting_schema_to_dot() {
+    let mut schema = ConfigSchema::default();
+    schema
+        .insert(&NestingConfig::DESCRIPTION, "test")
+        .unwrap()
+        .push_deprecated_alias("old")
+        .unwrap();
+    schema
+        .single_mut(&NestingConfig::DESCRIPTION)
+        .unwrap()
+        .push_alias("other")
+        .unwrap();
+
+    let dot = schema.to_dot();
+    assert!(dot.starts_with("digraph schema {\n"), "{dot}");
+    assert!(dot.ends_with("}\n"), "{dot}");
+    assert!(
+        dot.contains(r#"config0 [label="NestingConfig\ntest"];"#),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(r#"config2 [label="TestConfig\ntest.hierarchical"];"#),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(r#"config1 [label="TestConfig\ntest"];"#),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(r#"config0 -> config2 [label="hierarchical"];"#),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(r#"config0 -> config1 [label="(flattened)"];"#),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(r#"alias0 [label="old", shape=ellipse, style=dashed];"#),
+        "{dot}"
+    );
+    assert!(
+        dot.contains(r#"alias1 [label="other", shape=ellipse];"#),
+        "{dot}"
+    );
+    assert!(dot.contains("alias1 -> config0 [style=dotted];"), "{dot}");
+}
+
+#[test]
+fn validating_complete_config_source() {
+    let mut schema = ConfigSchema::default();
+    schema
+        .insert(&ConfigWithNesting::DESCRIPTION, "test")
+        .unwrap()
+        .push_alias("old")
+        .unwrap();
+    schema
+        .insert(&RenamedEnumConfig::DESCRIPTION, "enum")
+        .unwrap();
+
+    let errors = schema
+        .validate_complete(crate::config!("enum.version": "v0"))
+        .unwrap_err();
+    let error_paths: Vec<_> = errors.iter().map(ParseError::path).collect();
+    assert_eq!(error_paths, ["test.nested.renamed", "test.value"]);
+    for err in errors.iter() {
+        assert_eq!(err.code(), "missing_field");
+    }
+    assert_eq!(errors.first().config().ty.name_in_code(), "NestedConfig");
+    assert_eq!(errors.first().param().unwrap().name, "renamed");
+
+    // Aliases should be taken into account.
+    let json = crate::config!("old.value": 1, "test.nest.enum": "first", "enum.version": "v0");
+    schema.validate_complete(json).unwrap();
+
+    // Only the active enum variant should be checked.
+    let cases = [
+        (crate::config!(), "enum.str"),
+        (crate::config!("enum.version": "v1"), "enum.int"),
+        (crate::config!("enum.version": "previous"), "enum.int"),
+        (crate::config!("enum.version": "v3"), "enum.type"),
+        (
+            crate::config!("enum.version": "v3", "enum.type": "Nested"),
+            "enum.renamed",
+        ),
+    ];
+    for (json, expected_path) in cases {
+        let mut json = json;
+        json.merge("test.value", 1);
+        json.merge("test.nested.renamed", "first");
+        let errors = schema.validate_complete(json).unwrap_err();
+        let error_paths: Vec<_> = errors.iter().map(ParseError::path).collect();
+        assert_eq!(error_paths, [expected_path]);
+    }
+}
+
+#[test]
+fn validating_complete_source_with_optional_nested_configs() {
+    let schema = ConfigSchema::new(&CompoundConfig::DESCRIPTION, "test");
+    let json = crate::config!("test.nested.renamed": "first", "test.renamed": "second");
+    schema.validate_complete(json).unwrap();
+
+    let json = crate::config!(
+        "test.nested.renamed": "first",
+        "test.renamed": "second",
+        "test.nested_opt.other_int": 1,
+        "test.default.other_int": 2,
+    );
+    let errors = schema.validate_complete(json).unwrap_err();
+    let error_paths: Vec<_> = errors.iter().map(ParseError::path).collect();
+    assert_eq!(
+        error_paths,
+        ["test.default.renamed", "test.nested_opt.renamed"]
+    );
+}
+
+#[test]
+fn overriding_enum_coercion_for_config() {
+    let mut schema = ConfigSchema::default();
+    schema.coerce_serde_enums(true);
+    schema.insert(&EnumConfig::DESCRIPTION, "serde").unwrap();
+    schema
+        .insert(&RenamedEnumConfig::DESCRIPTION, "native")
+        .unwrap()
+        .push_alias("old")
+        .unwrap()
+        .coerce_serde_enums(false)
+        .unwrap();
+    // Coercion can be enabled for a config as well.
+    schema.coerce_serde_enums(false);
+    schema
+        .insert(&EnumConfig::DESCRIPTION, "enabled")
+        .unwrap()
+        .coerce_serde_enums(true)
+        .unwrap();
+
+    let str_param = EnumConfig::DESCRIPTION
+        .params
+        .iter()
+        .find(|param| param.name == "string")
+        .unwrap();
+    let config_ref = schema.get(&EnumConfig::DESCRIPTION, "serde").unwrap();
+    let paths: Vec<_> = config_ref
+        .all_paths_for_param(str_param)
+        .map(|(path, _)| path)
+        .collect();
+    assert!(
+        paths.contains(&"serde.with_fields.string".to_owned()),
+        "{paths:?}"
+    );
+    let config_ref = schema.get(&EnumConfig::DESCRIPTION, "enabled").unwrap();
+    let paths: Vec<_> = config_ref
+        .all_paths_for_param(str_param)
+        .map(|(path, _)| path)
+        .collect();
+    assert!(
+        paths.contains(&"enabled.with_fields.string".to_owned()),
+        "{paths:?}"
+    );
+
+    // `EnumConfig` nested in `RenamedEnumConfig` should not have coerced paths.
+    let config_ref = schema.get(&EnumConfig::DESCRIPTION, "native").unwrap();
+    let paths: Vec<_> = config_ref
+        .all_paths_for_param(str_param)
+        .map(|(path, _)| path)
+        .collect();
+    assert_eq!(
+        paths,
+        ["native.string", "native.str", "old.string", "old.str"]
+    );
+    let config_ref = schema
+        .get(&RenamedEnumConfig::DESCRIPTION, "native")
+        .unwrap();
+    let aliases: Vec<_> = config_ref.aliases().map(|(alias, _)| alias).collect();
+    assert_eq!(aliases, ["old"]);
+
+    // Parsing should work as expected.
+    let json = crate::config!(
+        "serde.with_fields.string": "test",
+        "native.version": "v3",
+        "native.type": "WithFields",
+        "native.str": "native",
+        "enabled.fields.str": "enabled",
+    );
+    let repo = ConfigRepository::new(&schema).with(json);
+    let config: EnumConfig = repo.get("serde").unwrap().parse().unwrap();
+    assert_matches!(config, EnumConfig::WithFields { string: Some(s), .. } if s == "test");
+    let config: RenamedEnumConfig = repo.get("native").unwrap().parse().unwrap();
+    assert_matches!(
+        config,
+        RenamedEnumConfig::V3(EnumConfig::WithFields { string: Some(s), .. }) if s == "native"
+    );
+    let config: EnumConfig = repo.get("enabled").unwrap().parse().unwrap();
+    assert_matches!(config, EnumConfig::WithFields { string: Some(s), .. } if s == "enabled");
+
+    let mut schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "test");
+    let err = schema
+        .get_mut(&NestedConfig::DESCRIPTION, "test.nested")
+        .unwrap()
+        .coerce_serde_enums(true)
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("not top-level"), "{err}");
+}
+
+#[test]
+fn frozen_schema_basics() {
+    fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+    let mut schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "test");
+    schema.insert(&EnumConfig::DESCRIPTION, "enum").unwrap();
+    let schema = schema.freeze();
+    assert_send_sync(&schema);
+
+    let cloned = schema.clone();
+    assert!(std::ptr::eq::<ConfigSchema>(&*schema, &*cloned));
+    assert_eq!(cloned.len(), schema.len());
+    let locations: Vec<_> = cloned.locate(&NestedConfig::DESCRIPTION).collect();
+    assert_eq!(locations, ["enum", "test.nested"]);
+    assert!(cloned.get(&EnumConfig::DESCRIPTION, "enum").is_some());
+
+    let json = crate::config!("test.value": 1, "test.nested.renamed": "first");
+    let repo = ConfigRepository::new(&cloned).with(json);
+    let config: ConfigWithNesting = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.value, 1);
+}
+
+#[test]
+fn extracting_subschema() {
+    let mut schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "test");
+    schema
+        .get_mut(&NestedConfig::DESCRIPTION, "test.nested")
+        .unwrap()
+        .push_alias("test.legacy")
+        .unwrap()
+        .push_alias("elsewhere")
+        .unwrap();
+    schema.insert(&EnumConfig::DESCRIPTION, "enum").unwrap();
+
+    let subschema = schema.subschema("test");
+    let configs: Vec<_> = subschema
+        .iter()
+        .map(|config| (config.prefix(), config.metadata().ty.name_in_code()))
+        .collect();
+    assert_eq!(
+        configs,
+        [("", "ConfigWithNesting"), ("nested", "NestedConfig")]
+    );
+    let nested = subschema.get(&NestedConfig::DESCRIPTION, "nested").unwrap();
+    assert!(!nested.is_top_level());
+    let aliases: Vec<_> = nested.aliases().map(|(alias, _)| alias).collect();
+    assert_eq!(aliases, ["nest", "legacy"]);
+    assert!(subschema.contains_config_at(Pointer("nested")));
+    assert!(!subschema.contains_config_at(Pointer("enum")));
+
+    let json = crate::config!("value": 1, "legacy.renamed": "first");
+    let repo = ConfigRepository::new(&subschema).with(json);
+    let config: ConfigWithNesting = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.value, 1);
+    assert_eq!(config.nested.simple_enum, SimpleEnum::First);
+
+    // Extracting a nested config makes it top-level.
+    let subschema = schema.subschema("test.nested");
+    let nested = subschema.single(&NestedConfig::DESCRIPTION).unwrap();
+    assert_eq!(nested.prefix(), "");
+    assert!(nested.is_top_level());
+    assert_eq!(nested.aliases().count(), 0);
+    assert_eq!(subschema.len(), 1);
+
+    // The whole schema is retained for the empty prefix.
+    let subschema = schema.subschema("");
+    assert_eq!(subschema.len(), schema.len());
+    let nested = subschema
+        .get(&NestedConfig::DESCRIPTION, "test.nested")
+        .unwrap();
+    let aliases: Vec<_> = nested.aliases().map(|(alias, _)| alias).collect();
+    assert_eq!(aliases, ["test.nest", "test.legacy", "elsewhere"]);
+
+    assert!(schema.subschema("missing").is_empty());
+}
+
+#[test]
+fn getting_config_doc() {
+    /// Config with a multi-line
+    /// description.
+    ///
+    /// - List item
+    ///   continued
+    #[derive(DescribeConfig)]
+    #[config(crate = crate)]
+    struct MultilineConfig {
+        #[allow(dead_code)]
+        value: u32,
+    }
+
+    let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    let config = schema.single(&TestConfig::DESCRIPTION).unwrap();
+    assert_eq!(
+        config.doc(),
+        "# Test configuration\n\nExtended description."
+    );
+    assert_eq!(
+        config.metadata().help,
+        "# Test configuration\nExtended description."
+    );
+
+    let schema = ConfigSchema::new(&MultilineConfig::DESCRIPTION, "");
+    let config = schema.single(&MultilineConfig::DESCRIPTION).unwrap();
+    assert_eq!(
+        config.doc(),
+        "Config with a multi-line\ndescription.\n\n- List item\n  continued"
+    );
+}
+
+#[test]
+fn comparing_config_refs() {
+    let schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "test");
+    let config = schema.single(&ConfigWithNesting::DESCRIPTION).unwrap();
+    assert_eq!(
+        config.key(),
+        (any::TypeId::of::<ConfigWithNesting>(), "test")
+    );
+
+    let refs_from_iter: HashSet<_> = schema.iter().collect();
+    assert_eq!(refs_from_iter.len(), 2);
+    let refs_from_params: HashSet<_> = schema.iter_params().map(|(_, config, _)| config).collect();
+    assert_eq!(refs_from_params, refs_from_iter);
+    assert!(refs_from_iter.contains(&config));
+
+    let nested = schema
+        .get(&NestedConfig::DESCRIPTION, "test.nested")
+        .unwrap();
+    assert_ne!(nested, config);
+    assert_eq!(nested.parent_link().unwrap().0, config);
+
+    let other_schema = schema.clone();
+    let other_config = other_schema
+        .single(&ConfigWithNesting::DESCRIPTION)
+        .unwrap();
+    assert_eq!(other_config.key(), config.key());
+    assert_ne!(other_config, config);
+}
+
+#[test]
+fn exporting_schema_to_markdown() {
+    let mut schema = ConfigSchema::default();
+    schema.insert(&NestingConfig::DESCRIPTION, "test").unwrap();
+
+    let md = schema.to_markdown();
+    assert!(md.contains("## `test.hierarchical`\n"), "{md}");
+    assert!(
+        md.contains(
+            "| `test.hierarchical.optional` | integer | `null` | 0.2.0 | Optional value. |"
+        ),
+        "{md}"
+    );
+    assert!(
+        md.contains(
+            "| `test.str` | string | `\"default\"` |  | String value. Deprecated aliases: `string`. |"
+        ),
+        "{md}"
+    );
+    assert!(
+        md.contains("| `test.bool_value` | Boolean | `false` |  |  |"),
+        "{md}"
+    );
+}
+
+#[test]
+fn exporting_schema_to_json_schema() {
+    let mut schema = ConfigSchema::default();
+    schema.insert(&NestingConfig::DESCRIPTION, "test").unwrap();
+
+    let json = schema.to_json_schema();
+    let test = &json["properties"]["test"];
+    assert_eq!(test["type"], "object");
+    assert_eq!(
+        test["properties"]["bool_value"],
+        serde_json::json!({ "type": "boolean", "default": false })
+    );
+    assert_eq!(
+        test["properties"]["optional"],
+        serde_json::json!({
+            "type": "integer",
+            "description": "Optional value.",
+            "default": null,
+            "x-since": "0.2.0",
+        })
+    );
+
+    let hierarchical = &test["properties"]["hierarchical"];
+    assert_eq!(
+        hierarchical["description"],
+        "# Test configuration\nExtended description."
+    );
+    assert_eq!(hierarchical["properties"]["optional"]["x-since"], "0.2.0");
+    assert_eq!(
+        hierarchical["properties"]["str"]["default"],
+        serde_json::json!("default")
+    );
+}
+
+#[test]
+fn combining_conditional_mount_options() {
+    let always = MountOptions::from(AliasOptions::new().with_condition(|| true));
+    let never = MountOptions::from(AliasOptions::new().with_condition(|| false));
+    let unconditional = MountOptions::from(AliasOptions::new());
+    assert!(!unconditional.is_conditional());
+    assert!(always.clone().combine(&unconditional).is_active());
+    assert!(always.clone().combine(&always).is_active());
+    assert!(!always.clone().combine(&never).is_active());
+    assert!(!never.clone().combine(&always).is_active());
+    assert!(!unconditional.clone().combine(&never).is_active());
+
+    let nested = unconditional.combine(&always.combine(&never));
+    assert!(nested.is_conditional());
+    assert!(!nested.is_active());
+    // Only the most specific condition is retained in public options.
+    assert!(nested.alias.is_active());
 }
@@ -0,0 +1,66 @@
+//! Visiting configs in a schema.
+
+use std::{any, collections::HashMap};
+
+use super::{ConfigRef, ConfigSchema};
+use crate::metadata::ParamMetadata;
+
+/// Visitor of configs and params in a [`ConfigSchema`]. Used in [`ConfigSchema::visit()`].
+///
+/// All methods have no-op default implementations, so that a visitor can only implement methods it needs.
+pub trait SchemaVisitor {
+    /// Called when entering a config, before any of its params or sub-configs are visited.
+    fn enter_config(&mut self, config: ConfigRef<'_>) {
+        let _ = config;
+    }
+
+    /// Visits a param in the specified config.
+    fn visit_param(&mut self, config: ConfigRef<'_>, param: &'static ParamMetadata) {
+        let _ = (config, param);
+    }
+
+    /// Called when exiting a config, after all its params and sub-configs are visited.
+    fn exit_config(&mut self, config: ConfigRef<'_>) {
+        let _ = config;
+    }
+}
+
+type ConfigKey<'a> = (&'a str, any::TypeId);
+
+impl ConfigSchema {
+    /// Recursively visits all configs in this schema together with their params. Top-level configs are visited
+    /// in the same order as in [`Self::iter()`]. Each config is entered before its params and sub-configs are visited,
+    /// and is exited after that; i.e., parents are always visited before their children.
+    pub fn visit<V: SchemaVisitor>(&self, visitor: &mut V) {
+        let mut children = HashMap::<_, Vec<_>>::new();
+        let mut roots = vec![];
+        for config in self.iter() {
+            if let Some((parent, _)) = config.parent_link() {
+                let parent_key = (parent.prefix(), parent.metadata().ty.id());
+                children.entry(parent_key).or_default().push(config);
+            } else {
+                roots.push(config);
+            }
+        }
+
+        for config in roots {
+            Self::visit_config(config, &children, visitor);
+        }
+    }
+
+    fn visit_config<'a, V: SchemaVisitor>(
+        config: ConfigRef<'a>,
+        children: &HashMap<ConfigKey<'a>, Vec<ConfigRef<'a>>>,
+        visitor: &mut V,
+    ) {
+        visitor.enter_config(config);
+        for param in config.metadata().params {
+            visitor.visit_param(config, param);
+        }
+        let key = (config.prefix(), config.metadata().ty.id());
+        for &child in children.get(&key).into_iter().flatten() {
+            Self::visit_config(child, children, visitor);
+        }
+        visitor.exit_config(config);
+    }
+}
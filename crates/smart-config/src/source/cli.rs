@@ -0,0 +1,226 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::Context as _;
+
+use super::{ConfigSource, Hierarchical};
+use crate::{
+    value::{Map, Pointer, Value, ValueOrigin, WithOrigin},
+    Json,
+};
+
+/// Configuration overrides sourced from command-line style `key=value` args, e.g. provided
+/// via a `--set db.url=postgres://localhost/db` CLI option.
+///
+/// Keys are split into path segments by `.`s. Values are parsed as JSON if possible (e.g., numbers, `true` / `false`,
+/// arrays like `[1, 2]` or objects); otherwise, values are treated as strings. To force a value that is valid JSON
+/// to be interpreted as a string, quote it, e.g. `name='"123"'`. If a key is repeated, the last value wins.
+/// Keys where one is a prefix of another (e.g., `db=1` and `db.url=x`) are considered conflicting.
+///
+/// Since sources added to a [`ConfigRepository`](crate::ConfigRepository) override the previously added ones,
+/// this source should usually be added last so that it has the highest priority.
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::{CliOverrides, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig};
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct DbConfig {
+///     url: String,
+///     #[config(default_t = 10)]
+///     pool_size: usize,
+///     #[config(default)]
+///     replicas: Vec<String>,
+/// }
+///
+/// let schema = ConfigSchema::new(&DbConfig::DESCRIPTION, "db");
+/// let overrides = CliOverrides::new([
+///     "db.url=postgres://localhost/db",
+///     "db.pool_size=50",
+///     r#"db.replicas=["postgres://replica/db"]"#,
+/// ])?;
+/// let repo = ConfigRepository::new(&schema).with(overrides);
+/// let config: DbConfig = repo.single()?.parse()?;
+/// assert_eq!(config.url, "postgres://localhost/db");
+/// assert_eq!(config.pool_size, 50);
+/// assert_eq!(config.replicas, ["postgres://replica/db"]);
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct CliOverrides {
+    inner: WithOrigin,
+}
+
+impl CliOverrides {
+    /// Parses overrides from the provided `key=value` args.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any of the args is not in the `key=value` form, if the key contains empty path segments
+    /// (e.g., `=value` or `db..url=value`), or if keys of two args conflict (e.g., `db=1` and `db.url=x`).
+    pub fn new<S: AsRef<str>>(args: impl IntoIterator<Item = S>) -> anyhow::Result<Self> {
+        let origin = Arc::new(ValueOrigin::Custom("command-line overrides".to_owned()));
+        let mut inner = WithOrigin::new(Value::Object(Map::new()), origin.clone());
+        let mut args_by_key = BTreeMap::<String, String>::new();
+        for arg in args {
+            let arg = arg.as_ref();
+            let (key, raw_value) = arg
+                .split_once('=')
+                .with_context(|| format!("override `{arg}` is not in the `key=value` form"))?;
+            let key = key.trim();
+            let has_empty_segments = key.split('.').any(str::is_empty);
+            let Some((parent, last_segment)) =
+                Pointer(key).split_last().filter(|_| !has_empty_segments)
+            else {
+                anyhow::bail!("override `{arg}` has an empty path segment in its key");
+            };
+            if let Some(prev_arg) = Self::find_conflicting_arg(&args_by_key, key) {
+                anyhow::bail!("override `{arg}` conflicts with override `{prev_arg}`");
+            }
+            args_by_key.insert(key.to_owned(), arg.to_owned());
+
+            let value = serde_json::from_str(raw_value)
+                .unwrap_or_else(|_| serde_json::Value::String(raw_value.to_owned()));
+            let value = Json::map_value(value, &origin, key.to_owned());
+            inner
+                .ensure_object(parent, |_| origin.clone())
+                .insert(last_segment.to_owned(), value);
+        }
+        Ok(Self { inner })
+    }
+
+    /// Finds a previous arg with the key being a strict ancestor or descendant of `key`.
+    fn find_conflicting_arg<'a>(
+        args_by_key: &'a BTreeMap<String, String>,
+        key: &str,
+    ) -> Option<&'a str> {
+        let ancestor_arg = Pointer(key)
+            .with_ancestors()
+            .filter(|ancestor| ancestor.0 != key)
+            .find_map(|ancestor| args_by_key.get(ancestor.0));
+        let descendant_prefix = format!("{key}.");
+        let descendant_arg = || {
+            args_by_key
+                .range(descendant_prefix.clone()..)
+                .next()
+                .filter(|(descendant, _)| descendant.starts_with(&descendant_prefix))
+                .map(|(_, arg)| arg)
+        };
+        ancestor_arg.or_else(descendant_arg).map(String::as_str)
+    }
+
+    /// Returns the hierarchical value corresponding to these overrides.
+    pub fn value(&self) -> &WithOrigin {
+        &self.inner
+    }
+}
+
+impl ConfigSource for CliOverrides {
+    type Kind = Hierarchical;
+
+    fn into_contents(self) -> WithOrigin<Map> {
+        self.inner.map(|value| match value {
+            Value::Object(map) => map,
+            _ => Map::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::value::StrValue;
+
+    #[test]
+    fn parsing_cli_overrides() {
+        let overrides = CliOverrides::new([
+            "db.url=postgres://localhost/db",
+            "db.pool_size=50",
+            "db.replicas=[1, 2]",
+            "flag=true",
+            r#"name="123""#,
+            "empty=",
+            "db.pool_size=60",
+        ])
+        .unwrap();
+        let value = overrides.value();
+
+        let url = value.pointer("db.url").unwrap();
+        assert_eq!(url.inner.as_plain_str(), Some("postgres://localhost/db"));
+        assert_matches!(
+            url.origin.as_ref(),
+            ValueOrigin::Path { source, path }
+                if path == "db.url" && matches!(source.as_ref(), ValueOrigin::Custom(_))
+        );
+        assert_eq!(
+            url.origin.to_string(),
+            "command-line overrides -> path 'db.url'"
+        );
+
+        let pool_size = value.pointer("db.pool_size").unwrap();
+        assert_matches!(&pool_size.inner, Value::Number(num) if *num == 60_u64.into());
+        let replica = value.pointer("db.replicas.1").unwrap();
+        assert_matches!(&replica.inner, Value::Number(num) if *num == 2_u64.into());
+        assert_matches!(
+            replica.origin.as_ref(),
+            ValueOrigin::Path { path, .. } if path == "db.replicas.1"
+        );
+        assert_matches!(value.pointer("flag").unwrap().inner, Value::Bool(true));
+        assert_matches!(
+            &value.pointer("name").unwrap().inner,
+            Value::String(StrValue::Plain(s)) if s == "123"
+        );
+        assert_eq!(
+            value.pointer("empty").unwrap().inner.as_plain_str(),
+            Some("")
+        );
+    }
+
+    #[test]
+    fn invalid_cli_overrides() {
+        let err = CliOverrides::new(["db.url"]).unwrap_err().to_string();
+        assert!(
+            err.contains("`db.url` is not in the `key=value` form"),
+            "{err}"
+        );
+
+        for arg in ["=1", "db..url=1", "db.=1"] {
+            let err = CliOverrides::new([arg]).unwrap_err().to_string();
+            assert!(err.contains("empty path segment"), "{err}");
+        }
+    }
+
+    #[test]
+    fn conflicting_cli_overrides() {
+        let err = CliOverrides::new(["db=1", "db.url=x"])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "override `db.url=x` conflicts with override `db=1`");
+
+        let err = CliOverrides::new(["db.url=x", "other=1", "db=1"])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "override `db=1` conflicts with override `db.url=x`");
+
+        let err = CliOverrides::new(["db.pool.size=1", "db=1"])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            err,
+            "override `db=1` conflicts with override `db.pool.size=1`"
+        );
+
+        // Keys sharing a string prefix, but not a path prefix, are not conflicting.
+        let overrides = CliOverrides::new(["db=1", "db_url=x", "db-=2"]).unwrap();
+        assert_eq!(
+            overrides
+                .value()
+                .pointer("db_url")
+                .unwrap()
+                .inner
+                .as_plain_str(),
+            Some("x")
+        );
+    }
+}
@@ -1,12 +1,12 @@
-use std::{env, fmt, mem, sync::Arc};
+use std::{borrow::Cow, collections::BTreeMap, env, fmt, mem, sync::Arc};
 
 use anyhow::Context as _;
 
-use super::{ConfigSource, Flat};
+use super::{ConfigSource, Flat, Hierarchical};
 use crate::{
     testing::MOCK_ENV_VARS,
     utils::JsonObject,
-    value::{FileFormat, Map, Value, ValueOrigin, WithOrigin},
+    value::{FileFormat, Map, Pointer, Value, ValueOrigin, WithOrigin},
     Json,
 };
 
@@ -96,11 +96,17 @@ impl Environment {
             let (name, variable_value) = line.split_once('=').with_context(|| {
                 format!("Incorrect line for setting environment variable: {line}")
             })?;
-            let variable_value = variable_value.trim_matches('"');
+            let variable_value = match variable_value
+                .strip_prefix('"')
+                .and_then(|value| value.strip_suffix('"'))
+            {
+                Some(quoted) => unescape_dotenv_value(quoted),
+                None => variable_value.trim_matches('"').to_owned(),
+            };
             map.insert(
                 name.to_lowercase(),
                 WithOrigin {
-                    inner: Value::from(variable_value.to_owned()),
+                    inner: Value::from(variable_value),
                     origin: Arc::new(ValueOrigin::Path {
                         source: origin.clone(),
                         path: name.into(),
@@ -193,8 +199,7 @@ impl Environment {
     /// requires to escape Boolean values and nulls to strings.
     pub fn convert_flat_params(flat_params: &JsonObject, prefix: &str) -> JsonObject {
         let vars = flat_params.iter().map(|(path, value)| {
-            let mut var_name = path.replace('.', "_").to_uppercase();
-            var_name.insert_str(0, prefix);
+            let mut var_name = Self::var_name(prefix, path);
             let value: serde_json::Value = match value {
                 serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
                     var_name.push_str("__JSON");
@@ -206,6 +211,59 @@ impl Environment {
         });
         vars.collect()
     }
+
+    /// Returns the name of the env var corresponding to the specified param path, so that the var is mapped
+    /// to this path by an environment with the specified prefix.
+    pub(crate) fn var_name(prefix: &str, path: &str) -> String {
+        let mut var_name = path.replace('.', "_").to_uppercase();
+        var_name.insert_str(0, prefix);
+        var_name
+    }
+
+    /// Formats a value for a `.env` file so that it is parsed back by [`Self::from_dotenv()`] as is.
+    pub(crate) fn dotenv_value(value: &str) -> Cow<'_, str> {
+        let needs_quoting =
+            value.contains(|ch: char| ch.is_whitespace() || matches!(ch, '"' | '\\' | '\'' | '#'));
+        if !needs_quoting {
+            return Cow::Borrowed(value);
+        }
+
+        let mut quoted = String::with_capacity(value.len() + 2);
+        quoted.push('"');
+        for ch in value.chars() {
+            match ch {
+                '"' | '\\' => {
+                    quoted.push('\\');
+                    quoted.push(ch);
+                }
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                _ => quoted.push(ch),
+            }
+        }
+        quoted.push('"');
+        Cow::Owned(quoted)
+    }
+}
+
+/// Unescapes a double-quoted value in a `.env` file.
+fn unescape_dotenv_value(quoted: &str) -> String {
+    let mut value = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\\' {
+            value.push(ch);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => value.push('\n'),
+            Some('r') => value.push('\r'),
+            Some('t') => value.push('\t'),
+            Some(escaped) => value.push(escaped),
+            None => value.push('\\'),
+        }
+    }
+    value
 }
 
 #[derive(Debug)]
@@ -234,11 +292,180 @@ impl ConfigSource for Environment {
     }
 }
 
+/// Hierarchical configuration sourced from environment variables with an explicit separator between path segments.
+///
+/// Unlike [`Environment`], which is a flat source and relies on the config schema to map var names to params,
+/// this source splits var names into path segments by the specified separator. For example, with the `MYAPP` prefix
+/// and the `__` separator, the `MYAPP__DB__URL` var is mapped to the `db.url` path. Var names are lowercased;
+/// vars not starting with the prefix followed by the separator are ignored, as well as vars with empty path segments.
+///
+/// If a var path is a prefix of another var path (e.g., `MYAPP__DB` and `MYAPP__DB__URL`), the more specific var wins.
+/// Lists can be expressed using numeric path segments (e.g., `MYAPP__HOSTS__0`, `MYAPP__HOSTS__1`) together
+/// with [array coercion](Self::coerce_arrays()).
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::NestedEnvironment;
+/// let env = NestedEnvironment::from_iter("MYAPP", "__", [
+///     ("MYAPP__DB__URL", "postgres://localhost/db"),
+///     ("MYAPP__PORT", "3000"),
+///     ("OTHER__PORT", "4000"),
+/// ]);
+/// assert_eq!(
+///     env.value().pointer("db.url").unwrap().inner.as_plain_str(),
+///     Some("postgres://localhost/db")
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct NestedEnvironment {
+    inner: WithOrigin,
+}
+
+impl NestedEnvironment {
+    /// Loads environment variables with the specified prefix and separator.
+    pub fn prefixed(prefix: &str, separator: &str) -> Self {
+        MOCK_ENV_VARS.with_borrow(|mock_vars| {
+            let mock_vars = mock_vars
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()));
+            // Mock vars are chained last, so they override real vars with the same name.
+            Self::from_iter(prefix, separator, env::vars().chain(mock_vars))
+        })
+    }
+
+    /// Creates a custom environment.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `separator` is empty.
+    pub fn from_iter<K, V>(
+        prefix: &str,
+        separator: &str,
+        env: impl IntoIterator<Item = (K, V)>,
+    ) -> Self
+    where
+        K: AsRef<str> + Into<String>,
+        V: Into<String>,
+    {
+        assert!(!separator.is_empty(), "separator must not be empty");
+
+        let origin = Arc::new(ValueOrigin::EnvVars);
+        // Sort vars by path, so that ancestors are always processed before their descendants.
+        let mut vars = BTreeMap::new();
+        for (name, value) in env {
+            let Some(path) = Self::var_path(name.as_ref(), prefix, separator) else {
+                continue;
+            };
+            vars.insert(path, (name.into(), value.into()));
+        }
+
+        let mut inner = WithOrigin::new(Value::Object(Map::new()), origin.clone());
+        for (path, (name, value)) in vars {
+            let path = Pointer(&path);
+            inner.ensure_object(path, |_| origin.clone());
+            *inner.get_mut(path).unwrap() = WithOrigin {
+                inner: Value::from(value),
+                origin: Arc::new(ValueOrigin::Path {
+                    source: origin.clone(),
+                    path: name,
+                }),
+            };
+        }
+        Self { inner }
+    }
+
+    fn var_path(name: &str, prefix: &str, separator: &str) -> Option<String> {
+        let name = name.strip_prefix(prefix)?.strip_prefix(separator)?;
+        let segments: Vec<_> = name.split(separator).collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return None;
+        }
+        Some(segments.join(".").to_lowercase())
+    }
+
+    /// Returns the hierarchical value corresponding to this environment.
+    pub fn value(&self) -> &WithOrigin {
+        &self.inner
+    }
+
+    /// Coerces objects with contiguous numeric keys into arrays, so that e.g. `MYAPP__LIST__0=a` and `MYAPP__LIST__1=b`
+    /// vars are mapped to the `["a", "b"]` array at `list`. Objects with both numeric and non-numeric keys are not coerced.
+    /// Numeric keys with leading zeros (e.g., `01`) are not considered to be indices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if indices for any array are not contiguous (e.g., `MYAPP__LIST__0` and `MYAPP__LIST__2`);
+    /// provides a list of all such arrays. Successful coercions are still applied in this case.
+    pub fn coerce_arrays(&mut self) -> anyhow::Result<()> {
+        let mut errors = vec![];
+        Self::coerce_arrays_recursively(&mut self.inner, "", &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!(errors.join("; ")))
+        }
+    }
+
+    fn coerce_arrays_recursively(value: &mut WithOrigin, path: &str, errors: &mut Vec<String>) {
+        let Value::Object(map) = &mut value.inner else {
+            return;
+        };
+        for (key, child) in map.iter_mut() {
+            Self::coerce_arrays_recursively(child, &Pointer(path).join(key), errors);
+        }
+
+        let is_index = |key: &str| key.parse::<usize>().is_ok_and(|idx| idx.to_string() == key);
+        if map.is_empty() || !map.keys().all(|key| is_index(key)) {
+            return;
+        }
+        let items: BTreeMap<usize, _> = mem::take(map)
+            .into_iter()
+            .map(|(key, child)| (key.parse().unwrap(), child))
+            .collect();
+        let last_idx = *items.keys().next_back().unwrap();
+        if last_idx == items.len() - 1 {
+            value.inner = Value::Array(items.into_values().collect());
+            return;
+        }
+
+        let vars: Vec<_> = items
+            .values()
+            .filter_map(|child| match child.origin.as_ref() {
+                ValueOrigin::Path { path, .. } => Some(format!("`{path}`")),
+                _ => None,
+            })
+            .collect();
+        let indices: Vec<_> = items.keys().collect();
+        errors.push(format!(
+            "non-contiguous indices {indices:?} for array at `{path}` (vars: {})",
+            vars.join(", ")
+        ));
+        // Restore the original object.
+        *map = items
+            .into_iter()
+            .map(|(idx, child)| (idx.to_string(), child))
+            .collect();
+    }
+}
+
+impl ConfigSource for NestedEnvironment {
+    type Kind = Hierarchical;
+
+    fn into_contents(self) -> WithOrigin<Map> {
+        self.inner.map(|value| match value {
+            Value::Object(map) => map,
+            _ => Map::default(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
 
     use super::*;
+    use crate::testing::MockEnvGuard;
 
     #[test]
     fn parsing_dotenv_contents() {
@@ -247,6 +474,7 @@ mod tests {
             r#"
             APP_TEST=what
             APP_OTHER="test string"
+            APP_ESCAPED="say \"hi\"\nC:\\path"
 
             # Overwriting vars should be supported
             APP_TEST=42
@@ -254,7 +482,7 @@ mod tests {
         )
         .unwrap();
 
-        assert_eq!(env.map.len(), 2, "{:?}", env.map);
+        assert_eq!(env.map.len(), 3, "{:?}", env.map);
         assert_eq!(env.map["app_test"].inner.as_plain_str(), Some("42"));
         let origin = &env.map["app_test"].origin;
         let ValueOrigin::Path { path, source } = origin.as_ref() else {
@@ -269,14 +497,83 @@ mod tests {
             env.map["app_other"].inner.as_plain_str(),
             Some("test string")
         );
+        assert_eq!(
+            env.map["app_escaped"].inner.as_plain_str(),
+            Some("say \"hi\"\nC:\\path")
+        );
 
         let env = env.strip_prefix("app_");
-        assert_eq!(env.map.len(), 2, "{:?}", env.map);
+        assert_eq!(env.map.len(), 3, "{:?}", env.map);
         assert_eq!(env.map["test"].inner.as_plain_str(), Some("42"));
         assert_matches!(env.map["test"].origin.as_ref(), ValueOrigin::Path { path, .. } if path == "APP_TEST");
         assert_eq!(env.map["other"].inner.as_plain_str(), Some("test string"));
     }
 
+    #[test]
+    fn coercing_arrays_in_nested_env() {
+        let mut env = NestedEnvironment::from_iter(
+            "MYAPP",
+            "__",
+            [
+                ("MYAPP__LIST__1", "b"),
+                ("MYAPP__LIST__0", "a"),
+                ("MYAPP__NESTED__0__PORT", "3000"),
+                ("MYAPP__NESTED__1__PORT", "3001"),
+                ("MYAPP__MAP__01", "not an index"),
+                ("MYAPP__MIXED__0", "0"),
+                ("MYAPP__MIXED__VALUE", "?"),
+            ],
+        );
+        env.coerce_arrays().unwrap();
+
+        let list = env.value().get(Pointer("list")).unwrap();
+        let Value::Array(items) = &list.inner else {
+            panic!("unexpected value: {list:?}");
+        };
+        let items: Vec<_> = items.iter().map(|item| item.inner.as_plain_str()).collect();
+        assert_eq!(items, [Some("a"), Some("b")]);
+
+        let port = env.value().get(Pointer("nested.1.port")).unwrap();
+        assert_eq!(port.inner.as_plain_str(), Some("3001"));
+        assert_matches!(
+            env.value().get(Pointer("nested")).unwrap().inner,
+            Value::Array(_)
+        );
+        assert_matches!(
+            env.value().get(Pointer("map")).unwrap().inner,
+            Value::Object(_)
+        );
+        assert_matches!(
+            env.value().get(Pointer("mixed")).unwrap().inner,
+            Value::Object(_)
+        );
+    }
+
+    #[test]
+    fn coercing_arrays_with_non_contiguous_indices() {
+        let mut env = NestedEnvironment::from_iter(
+            "MYAPP",
+            "__",
+            [
+                ("MYAPP__LIST__0", "a"),
+                ("MYAPP__LIST__2", "c"),
+                ("MYAPP__OTHER__0", "ok"),
+            ],
+        );
+        let err = env.coerce_arrays().unwrap_err().to_string();
+        assert!(err.contains("non-contiguous indices [0, 2]"), "{err}");
+        assert!(err.contains("at `list`"), "{err}");
+        assert!(err.contains("`MYAPP__LIST__2`"), "{err}");
+
+        // Successful coercions should still be applied.
+        assert_matches!(
+            env.value().get(Pointer("other")).unwrap().inner,
+            Value::Array(_)
+        );
+        let item = env.value().get(Pointer("list.2")).unwrap();
+        assert_eq!(item.inner.as_plain_str(), Some("c"));
+    }
+
     #[test]
     fn converting_flat_params() {
         let params = serde_json::json!({
@@ -302,4 +599,58 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn creating_nested_env() {
+        let env = NestedEnvironment::from_iter(
+            "MYAPP",
+            "__",
+            [
+                ("MYAPP__DB__URL", "postgres://localhost/db"),
+                ("MYAPP__DB__POOL__SIZE", "10"),
+                ("MYAPP__PORT", "3000"),
+                ("MYAPP__API", "overridden"),
+                ("MYAPP__API__PORT", "8080"),
+                ("MYAPP____EMPTY", "?"),
+                ("MYAPPPORT", "4000"),
+                ("OTHER__PORT", "4000"),
+            ],
+        );
+
+        let value = env.value();
+        assert_eq!(
+            value.pointer("db.url").unwrap().inner.as_plain_str(),
+            Some("postgres://localhost/db")
+        );
+        let pool_size = value.pointer("db.pool.size").unwrap();
+        assert_eq!(pool_size.inner.as_plain_str(), Some("10"));
+        assert_matches!(
+            pool_size.origin.as_ref(),
+            ValueOrigin::Path { path, source }
+                if path == "MYAPP__DB__POOL__SIZE" && matches!(source.as_ref(), ValueOrigin::EnvVars)
+        );
+        assert_eq!(
+            value.pointer("api.port").unwrap().inner.as_plain_str(),
+            Some("8080")
+        );
+
+        let map = env.into_contents().inner;
+        let keys: Vec<_> = map.keys().map(String::as_str).collect();
+        assert_eq!(keys, ["api", "db", "port"]);
+    }
+
+    #[test]
+    fn nested_env_uses_mock_vars() {
+        let guard = MockEnvGuard::default();
+        guard.set_env("MYAPP__NESTED__VALUE".into(), "42".into());
+        let env = NestedEnvironment::prefixed("MYAPP", "__");
+        assert_eq!(
+            env.value()
+                .pointer("nested.value")
+                .unwrap()
+                .inner
+                .as_plain_str(),
+            Some("42")
+        );
+    }
 }
@@ -0,0 +1,376 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use anyhow::Context;
+
+use super::{ConfigSource, Hierarchical, Json, Yaml};
+use crate::{
+    utils::tracing,
+    value::{FileFormat, Map, Value, ValueOrigin, WithOrigin},
+};
+
+/// Retry policy for [`HttpRequest`]s. Only network errors (including non-successful HTTP statuses) are retried;
+/// parsing errors are returned immediately.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    max_retries: usize,
+    interval: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Returns a policy with no retries.
+    fn default() -> Self {
+        Self::new(0, Duration::ZERO)
+    }
+}
+
+impl RetryPolicy {
+    /// Creates a policy with the specified max number of retries and a constant interval between retries.
+    pub const fn new(max_retries: usize, interval: Duration) -> Self {
+        Self {
+            max_retries,
+            interval,
+        }
+    }
+}
+
+/// Errors that can occur when fetching an [`Http`] source.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum HttpSourceError {
+    /// Error sending the request or receiving the response, or a non-successful HTTP status.
+    Network(anyhow::Error),
+    /// Error parsing the response body.
+    Parse(anyhow::Error),
+}
+
+impl fmt::Display for HttpSourceError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(err) => write!(formatter, "network error: {err:#}"),
+            Self::Parse(err) => write!(formatter, "error parsing response: {err:#}"),
+        }
+    }
+}
+
+impl std::error::Error for HttpSourceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Network(err) | Self::Parse(err) => Some(err.as_ref()),
+        }
+    }
+}
+
+/// Request to fetch an [`Http`] source. Created using [`Http::request()`].
+#[derive(Debug, Clone)]
+pub struct HttpRequest {
+    url: String,
+    format: FileFormat,
+    retry_policy: RetryPolicy,
+    timeout: Option<Duration>,
+}
+
+impl HttpRequest {
+    /// Sets the retry policy for the request. By default, the request is not retried.
+    #[must_use]
+    pub fn with_retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = policy;
+        self
+    }
+
+    /// Sets a timeout for each request attempt. By default, there is no timeout.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Fetches the source by sending a GET request to the URL and parsing the response body.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails after all retries, or the response body cannot be parsed.
+    pub async fn fetch(self) -> Result<Http, HttpSourceError> {
+        if matches!(
+            self.format,
+            FileFormat::Toml | FileFormat::Dotenv | FileFormat::Text
+        ) {
+            let err = anyhow::anyhow!("unsupported format: {}", self.format);
+            return Err(HttpSourceError::Parse(err));
+        }
+
+        let client = reqwest::Client::new();
+        let mut attempt = 0;
+        let body = loop {
+            match self.fetch_body(&client).await {
+                Ok(body) => break body,
+                Err(err) if attempt < self.retry_policy.max_retries => {
+                    attempt += 1;
+                    tracing::warn!(
+                        url = self.url,
+                        attempt,
+                        "failed fetching config source, retrying: {err:#}"
+                    );
+                    tokio::time::sleep(self.retry_policy.interval).await;
+                }
+                Err(err) => return Err(HttpSourceError::Network(err)),
+            }
+        };
+
+        let origin = Arc::new(ValueOrigin::Custom(format!(
+            "{} from '{}'",
+            self.format, self.url
+        )));
+        let inner =
+            Self::parse_body(&body, self.format, &origin).map_err(HttpSourceError::Parse)?;
+        Ok(Http { origin, inner })
+    }
+
+    async fn fetch_body(&self, client: &reqwest::Client) -> anyhow::Result<String> {
+        let mut request = client.get(&self.url);
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        let response = request.send().await?.error_for_status()?;
+        Ok(response.text().await?)
+    }
+
+    fn parse_body(
+        body: &str,
+        format: FileFormat,
+        origin: &Arc<ValueOrigin>,
+    ) -> anyhow::Result<Map> {
+        let value = match format {
+            FileFormat::Json => {
+                let value: serde_json::Value =
+                    serde_json::from_str(body).context("failed parsing JSON")?;
+                Json::map_value(value, origin, String::new())
+            }
+            FileFormat::Yaml => {
+                let value: serde_yaml::Value =
+                    serde_yaml::from_str(body).context("failed parsing YAML")?;
+                Yaml::map_value(value, origin, String::new())?
+            }
+            FileFormat::Toml | FileFormat::Dotenv | FileFormat::Text => {
+                unreachable!("checked previously")
+            }
+        };
+        match value.inner {
+            Value::Object(map) => Ok(map),
+            _ => anyhow::bail!("response body is not an object"),
+        }
+    }
+}
+
+/// Configuration source fetched from an HTTP endpoint. The response body can be in the JSON or YAML format.
+///
+/// Values in the source have origins rooted at [`ValueOrigin::Custom`] describing the endpoint.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// use smart_config::{value::FileFormat, Http, RetryPolicy};
+///
+/// # async fn test() -> anyhow::Result<()> {
+/// let source = Http::request("http://config-server/app.json", FileFormat::Json)
+///     .with_retry_policy(RetryPolicy::new(3, Duration::from_secs(1)))
+///     .fetch()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Http {
+    origin: Arc<ValueOrigin>,
+    inner: Map,
+}
+
+impl Http {
+    /// Creates a request to fetch a source from the specified URL.
+    pub fn request(url: impl Into<String>, format: FileFormat) -> HttpRequest {
+        HttpRequest {
+            url: url.into(),
+            format,
+            retry_policy: RetryPolicy::default(),
+            timeout: None,
+        }
+    }
+}
+
+impl ConfigSource for Http {
+    type Kind = Hierarchical;
+
+    fn into_contents(self) -> WithOrigin<Map> {
+        WithOrigin::new(self.inner, self.origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use assert_matches::assert_matches;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::*;
+    use crate::value::StrValue;
+
+    /// Starts a mock HTTP server responding with the provided `(status, body)` pairs in order; the last response
+    /// is repeated indefinitely. Returns the server URL and the counter of received requests.
+    async fn start_mock_server(responses: Vec<(u16, &'static str)>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/config", listener.local_addr().unwrap());
+        let request_count = Arc::new(AtomicUsize::new(0));
+        let counter = request_count.clone();
+
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let idx = counter.fetch_add(1, Ordering::SeqCst);
+                let (status, body) = responses[idx.min(responses.len() - 1)];
+
+                let mut buffer = [0_u8; 4_096];
+                let _ = stream.read(&mut buffer).await.unwrap();
+                let response = format!(
+                    "HTTP/1.1 {status} Mock\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                stream.shutdown().await.ok();
+            }
+        });
+        (url, request_count)
+    }
+
+    #[tokio::test]
+    async fn fetching_json_source() {
+        let (url, request_count) = start_mock_server(vec![(
+            200,
+            r#"{ "test": { "port": 3000, "name": "app" } }"#,
+        )])
+        .await;
+        let source = Http::request(&url, FileFormat::Json).fetch().await.unwrap();
+        assert_eq!(request_count.load(Ordering::SeqCst), 1);
+
+        let contents = source.into_contents();
+        assert_matches!(
+            contents.origin.as_ref(),
+            ValueOrigin::Custom(description) if description.contains(&url)
+        );
+        let port = &contents.inner["test"];
+        let Value::Object(test) = &port.inner else {
+            panic!("unexpected value: {port:?}");
+        };
+        assert_matches!(&test["name"].inner, Value::String(StrValue::Plain(s)) if s == "app");
+        assert_matches!(
+            test["port"].origin.as_ref(),
+            ValueOrigin::Path { source, path } if path == "test.port" && Arc::ptr_eq(source, &contents.origin)
+        );
+    }
+
+    #[tokio::test]
+    async fn fetching_yaml_source() {
+        let (url, _) = start_mock_server(vec![(200, "test:\n  port: 3000\n")]).await;
+        let source = Http::request(&url, FileFormat::Yaml).fetch().await.unwrap();
+        let contents = source.into_contents();
+        assert_matches!(&contents.inner["test"].inner, Value::Object(obj) if obj.len() == 1);
+    }
+
+    #[tokio::test]
+    async fn retrying_network_errors() {
+        let (url, request_count) =
+            start_mock_server(vec![(503, ""), (500, ""), (200, r#"{ "port": 3000 }"#)]).await;
+        let err = Http::request(&url, FileFormat::Json)
+            .with_retry_policy(RetryPolicy::new(1, Duration::from_millis(10)))
+            .fetch()
+            .await
+            .unwrap_err();
+        assert_matches!(err, HttpSourceError::Network(_));
+        assert_eq!(request_count.load(Ordering::SeqCst), 2);
+
+        let source = Http::request(&url, FileFormat::Json)
+            .with_retry_policy(RetryPolicy::new(3, Duration::from_millis(10)))
+            .fetch()
+            .await
+            .unwrap();
+        assert_eq!(request_count.load(Ordering::SeqCst), 3);
+        assert!(source.into_contents().inner.contains_key("port"));
+    }
+
+    #[tokio::test]
+    async fn reading_http_fallback() {
+        use crate::fallback::{self, FallbackSource};
+
+        let (url, _) =
+            start_mock_server(vec![(200, r#"{ "api": { "ports": [3000, 3001] } }"#)]).await;
+        let url: &'static str = Box::leak(url.into_boxed_str());
+        let fallback = fallback::Http::new(url, "/api/ports/1");
+        let value = tokio::task::spawn_blocking(move || fallback.provide_value())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_matches!(&value.inner, Value::Number(num) if *num == 3001_u64.into());
+        assert_matches!(
+            value.origin.as_ref(),
+            ValueOrigin::Custom(description) if description.contains(url) && description.contains("/api/ports/1")
+        );
+
+        let fallback = fallback::Http::new(url, "/api/missing");
+        let value = tokio::task::spawn_blocking(move || fallback.provide_value())
+            .await
+            .unwrap();
+        assert!(value.is_none());
+    }
+
+    #[tokio::test]
+    async fn http_fallback_errors() {
+        use crate::fallback::{self, FallbackSource};
+
+        let (url, request_count) = start_mock_server(vec![(500, ""), (200, "{ what? }")]).await;
+        let url: &'static str = Box::leak(url.into_boxed_str());
+        let fallback = fallback::Http::new(url, "");
+        for _ in 0..2 {
+            let value = tokio::task::spawn_blocking(move || fallback.provide_value())
+                .await
+                .unwrap();
+            assert!(value.is_none());
+        }
+        assert_eq!(request_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn http_fallback_documents_are_shared() {
+        use crate::fallback::{self, FallbackSource};
+
+        let (url, request_count) =
+            start_mock_server(vec![(200, r#"{ "port": 3000, "name": "app" }"#)]).await;
+        let url: &'static str = Box::leak(url.into_boxed_str());
+        let (port, name) = tokio::task::spawn_blocking(move || {
+            fallback::with_shared_documents(|| {
+                let port = fallback::Http::new(url, "/port").provide_value();
+                let name = fallback::Http::new(url, "/name").provide_value();
+                (port, name)
+            })
+        })
+        .await
+        .unwrap();
+        assert_matches!(port.unwrap().inner, Value::Number(num) if num == 3000_u64.into());
+        assert_matches!(name.unwrap().inner, Value::String(StrValue::Plain(s)) if s == "app");
+        assert_eq!(request_count.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn parse_errors_are_not_retried() {
+        let (url, request_count) = start_mock_server(vec![(200, "{ what? }")]).await;
+        let err = Http::request(&url, FileFormat::Json)
+            .with_retry_policy(RetryPolicy::new(3, Duration::from_millis(10)))
+            .fetch()
+            .await
+            .unwrap_err();
+        assert_matches!(err, HttpSourceError::Parse(_));
+        assert_eq!(request_count.load(Ordering::SeqCst), 1);
+    }
+}
@@ -1,4 +1,6 @@
-use std::sync::Arc;
+use std::{collections::HashMap, io, iter, str, sync::Arc};
+
+use serde::de;
 
 use super::{ConfigSource, Hierarchical};
 use crate::value::{FileFormat, Map, Pointer, Value, ValueOrigin, WithOrigin};
@@ -18,14 +20,150 @@ impl Json {
 
     /// Creates a source with the specified name and contents.
     pub fn new(filename: &str, object: serde_json::Map<String, serde_json::Value>) -> Self {
-        let origin = Arc::new(ValueOrigin::File {
+        let origin = ValueOrigin::File {
             name: filename.to_owned(),
             format: FileFormat::Json,
-        });
+        };
+        Self::with_origin(origin, object)
+    }
+
+    /// Creates a source with the specified origin and contents. This is useful for sources built programmatically,
+    /// so that errors caused by values in the source point to a meaningful origin (e.g., a [`ValueOrigin::Custom`]
+    /// or [`ValueOrigin::Synthetic`] one) rather than a file. See also the [`config_with_origin!`](crate::config_with_origin)
+    /// macro.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{value::ValueOrigin, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig, Json};
+    /// #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    /// struct TestConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let origin = ValueOrigin::Custom("tenant defaults".to_owned());
+    /// let serde_json::Value::Object(object) = serde_json::json!({ "port": "??" }) else {
+    ///     unreachable!();
+    /// };
+    /// let json = Json::with_origin(origin, object);
+    ///
+    /// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "");
+    /// let repo = ConfigRepository::new(&schema).with(json);
+    /// let err = repo.single::<TestConfig>()?.parse().unwrap_err();
+    /// assert_eq!(err.first().origin().to_string(), "tenant defaults -> path 'port'");
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn with_origin(
+        origin: impl Into<Arc<ValueOrigin>>,
+        object: serde_json::Map<String, serde_json::Value>,
+    ) -> Self {
+        let origin = origin.into();
         let inner = Self::map_value(serde_json::Value::Object(object), &origin, String::new());
         Self { origin, inner }
     }
 
+    /// Creates a source from an owned JSON `value`, e.g. one obtained from another library. This avoids
+    /// serializing the value and parsing it back. Values in the created source have a [`ValueOrigin::Custom`] origin;
+    /// to specify another origin, destructure the object and use [`Self::with_origin()`].
+    ///
+    /// Numbers are preserved as is; e.g., integers are not converted to floats.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `value` is not a JSON object.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{testing, DescribeConfig, DeserializeConfig, Json};
+    /// #[derive(DescribeConfig, DeserializeConfig)]
+    /// struct TestConfig {
+    ///     port: u16,
+    ///     ratio: f64,
+    /// }
+    ///
+    /// let value = serde_json::json!({ "port": 8080, "ratio": 0.5 });
+    /// let json = Json::from_value(value)?;
+    /// let config: TestConfig = testing::test(json)?;
+    /// assert_eq!(config.port, 8080);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn from_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        let serde_json::Value::Object(object) = value else {
+            let unexpected = match &value {
+                serde_json::Value::Null => de::Unexpected::Unit,
+                serde_json::Value::Bool(value) => de::Unexpected::Bool(*value),
+                serde_json::Value::Number(_) => de::Unexpected::Other("number"),
+                serde_json::Value::String(value) => de::Unexpected::Str(value),
+                serde_json::Value::Array(_) => de::Unexpected::Seq,
+                serde_json::Value::Object(_) => unreachable!(),
+            };
+            return Err(de::Error::invalid_type(unexpected, &"JSON object"));
+        };
+        let origin = ValueOrigin::Custom("JSON value".to_owned());
+        Ok(Self::with_origin(origin, object))
+    }
+
+    /// Parses a source with the specified name from JSON text. Unlike [`Self::new()`], origins of values
+    /// in the created source record line / column locations of values in `text`, so that they are reported
+    /// in [`ParseError`](crate::ParseError)s.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not valid JSON or is not a JSON object.
+    pub fn parse(filename: &str, text: &str) -> Result<Self, serde_json::Error> {
+        let object = serde_json::from_str(text)?;
+        let mut this = Self::new(filename, object);
+        let locations = LocationScanner::scan(text);
+        Self::attach_locations(&mut this.inner, &locations);
+        Ok(this)
+    }
+
+    /// Reads a source with the specified name from a JSON `reader` (e.g., a file). The JSON text is streamed
+    /// from the reader rather than being loaded into memory as a whole, which makes this method preferable
+    /// to [`Self::parse()`] for large inputs. As a downside, origins of values in the created source
+    /// do not record value locations in the input.
+    ///
+    /// The `reader` is not buffered internally; wrap it into a [`BufReader`](io::BufReader) if necessary.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input cannot be read, is not valid JSON or is not a JSON object.
+    pub fn from_reader(filename: &str, reader: impl io::Read) -> Result<Self, serde_json::Error> {
+        let object = serde_json::from_reader(reader)?;
+        Ok(Self::new(filename, object))
+    }
+
+    pub(crate) fn attach_locations(
+        value: &mut WithOrigin,
+        locations: &HashMap<String, (usize, usize)>,
+    ) {
+        if let ValueOrigin::Path { source, path } = value.origin.as_ref() {
+            if let Some(&(line, column)) = locations.get(path) {
+                value.origin = Arc::new(ValueOrigin::FileLocation {
+                    source: source.clone(),
+                    path: path.clone(),
+                    line,
+                    column,
+                });
+            }
+        }
+
+        match &mut value.inner {
+            Value::Array(items) => {
+                for item in items {
+                    Self::attach_locations(item, locations);
+                }
+            }
+            Value::Object(map) => {
+                for value in map.values_mut() {
+                    Self::attach_locations(value, locations);
+                }
+            }
+            _ => { /* no nested values */ }
+        }
+    }
+
     /// Merges a value at the specified path into JSON.
     ///
     /// If any ancestors in `at` are not objects, they are replaced with objects.
@@ -136,6 +274,132 @@ impl ConfigSource for Json {
     }
 }
 
+/// Lenient scanner of JSON text recording start locations of all values. Assumes that the text is valid JSON.
+#[derive(Debug)]
+struct LocationScanner<'a> {
+    chars: iter::Peekable<str::Chars<'a>>,
+    line: usize,
+    column: usize,
+    locations: HashMap<String, (usize, usize)>,
+}
+
+impl<'a> LocationScanner<'a> {
+    fn scan(text: &'a str) -> HashMap<String, (usize, usize)> {
+        let mut scanner = Self {
+            chars: text.chars().peekable(),
+            line: 1,
+            column: 1,
+            locations: HashMap::new(),
+        };
+        scanner.value("");
+        scanner.locations
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let ch = self.chars.next()?;
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(ch)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(char::is_ascii_whitespace) {
+            self.next_char();
+        }
+    }
+
+    fn value(&mut self, path: &str) {
+        self.skip_whitespace();
+        if !path.is_empty() {
+            // Duplicate keys are resolved in the same way as in `serde_json`; the last value wins.
+            self.locations
+                .insert(path.to_owned(), (self.line, self.column));
+        }
+
+        match self.chars.peek() {
+            Some('{') => self.object(path),
+            Some('[') => self.array(path),
+            Some('"') => {
+                self.string();
+            }
+            _ => {
+                // Scalar value (number, boolean or null)
+                while self
+                    .chars
+                    .peek()
+                    .is_some_and(|&ch| !matches!(ch, ',' | ']' | '}') && !ch.is_ascii_whitespace())
+                {
+                    self.next_char();
+                }
+            }
+        }
+    }
+
+    fn object(&mut self, path: &str) {
+        self.next_char(); // '{'
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some(',') => {
+                    self.next_char();
+                }
+                Some('"') => {
+                    let key = self.string();
+                    self.skip_whitespace();
+                    self.next_char(); // ':'
+                    self.value(&Pointer(path).join(&key));
+                }
+                Some('}') => {
+                    self.next_char();
+                    break;
+                }
+                _ => break,
+            }
+        }
+    }
+
+    fn array(&mut self, path: &str) {
+        self.next_char(); // '['
+        let mut index = 0_usize;
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some(',') => {
+                    self.next_char();
+                }
+                Some(']') => {
+                    self.next_char();
+                    break;
+                }
+                None => break,
+                Some(_) => {
+                    self.value(&Pointer(path).join(&index.to_string()));
+                    index += 1;
+                }
+            }
+        }
+    }
+
+    /// Scans a string and returns its unescaped contents.
+    fn string(&mut self) -> String {
+        let mut raw = String::new();
+        raw.extend(self.next_char()); // '"'
+        while let Some(ch) = self.next_char() {
+            raw.push(ch);
+            match ch {
+                '\\' => raw.extend(self.next_char()),
+                '"' => break,
+                _ => { /* continue scanning */ }
+            }
+        }
+        serde_json::from_str(&raw).unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -143,6 +407,33 @@ mod tests {
     use super::*;
     use crate::{testonly::extract_json_name, value::StrValue};
 
+    #[test]
+    fn creating_json_from_value() {
+        let value = serde_json::json!({
+            "int": 123,
+            "float": 1.5,
+            "nested": { "array": [-1, 2.0] },
+        });
+        let json = Json::from_value(value).unwrap();
+        assert_matches!(json.origin.as_ref(), ValueOrigin::Custom(name) if name == "JSON value");
+
+        let int = json.inner.pointer("int").unwrap();
+        assert_matches!(&int.inner, Value::Number(num) if num.is_u64());
+        let float = json.inner.pointer("float").unwrap();
+        assert_matches!(&float.inner, Value::Number(num) if num.is_f64());
+        let item = json.inner.pointer("nested.array.0").unwrap();
+        assert_matches!(&item.inner, Value::Number(num) if num.is_i64());
+        assert_matches!(
+            item.origin.as_ref(),
+            ValueOrigin::Path { path, .. } if path == "nested.array.0"
+        );
+        let item = json.inner.pointer("nested.array.1").unwrap();
+        assert_matches!(&item.inner, Value::Number(num) if num.is_f64());
+
+        let err = Json::from_value(serde_json::json!([1, 2])).unwrap_err();
+        assert!(err.to_string().contains("expected JSON object"), "{err}");
+    }
+
     #[test]
     fn creating_json_config() {
         let json = serde_json::json!({
@@ -190,6 +481,101 @@ mod tests {
         assert_matches!(&array.inner, Value::Array(items) if items.len() == 2);
     }
 
+    #[test]
+    fn parsing_json_with_locations() {
+        let text = r#"{
+  "bool_value": true,
+  "nested": {
+    "str": "what\"?",
+    "array": [1, { "int": 3 }]
+  }
+}"#;
+        let json = Json::parse("test.json", text).unwrap();
+
+        let assert_location = |path: &str, expected_line: usize, expected_column: usize| {
+            let value = json.inner.get(Pointer(path)).unwrap();
+            assert_matches!(
+                value.origin.as_ref(),
+                ValueOrigin::FileLocation { path: origin_path, source, line, column }
+                    if origin_path == path
+                        && extract_json_name(source) == "test.json"
+                        && (*line, *column) == (expected_line, expected_column)
+            );
+        };
+        assert_location("bool_value", 2, 17);
+        assert_location("nested", 3, 13);
+        assert_location("nested.str", 4, 12);
+        assert_location("nested.array", 5, 14);
+        assert_location("nested.array.0", 5, 15);
+        assert_location("nested.array.1.int", 5, 27);
+
+        let str = json.inner.get(Pointer("nested.str")).unwrap();
+        assert_eq!(
+            str.origin.to_string(),
+            "JSON file 'test.json' -> path 'nested.str' (test.json:4:12)"
+        );
+
+        let err = Json::parse("test.json", "[1, 2]").unwrap_err();
+        assert!(err.is_data(), "{err}");
+    }
+
+    #[test]
+    fn reading_json_from_reader() {
+        let text = br#"{ "bool_value": true, "nested": { "array": [1, 2] } }"#;
+        let json = Json::from_reader("test.json", io::Cursor::new(text)).unwrap();
+
+        let item = json.inner.get(Pointer("nested.array.1")).unwrap();
+        assert_matches!(&item.inner, Value::Number(num) if *num == 2_u64.into());
+        assert_matches!(
+            item.origin.as_ref(),
+            ValueOrigin::Path { path, source }
+                if path == "nested.array.1" && extract_json_name(source) == "test.json"
+        );
+
+        let err = Json::from_reader("test.json", io::Cursor::new(b"[1, 2]")).unwrap_err();
+        assert!(err.is_data(), "{err}");
+        let err = Json::from_reader("test.json", io::Cursor::new(b"{ \"truncated\"")).unwrap_err();
+        assert!(err.is_eof(), "{err}");
+    }
+
+    #[test]
+    fn creating_config_with_custom_origin() {
+        let json = crate::config_with_origin!("tenant defaults", {
+            "nested.str": "???",
+            "nested.int_value": 123,
+        });
+        let str = json.inner.get(Pointer("nested.str")).unwrap();
+        assert_matches!(
+            str.origin.as_ref(),
+            ValueOrigin::Path { path, source }
+                if path == "nested.str"
+                    && matches!(source.as_ref(), ValueOrigin::Custom(name) if name == "tenant defaults")
+        );
+        assert_eq!(
+            json.inner
+                .get(Pointer("nested"))
+                .unwrap()
+                .origin
+                .to_string(),
+            "tenant defaults -> path 'nested'"
+        );
+
+        let origin = Arc::new(ValueOrigin::Synthetic {
+            source: Arc::default(),
+            transform: "generated".to_owned(),
+        });
+        let serde_json::Value::Object(object) = serde_json::json!({ "bool_value": true }) else {
+            unreachable!();
+        };
+        let json = Json::with_origin(origin.clone(), object);
+        assert!(Arc::ptr_eq(&json.inner.origin, &origin));
+        let bool_value = json.inner.get(Pointer("bool_value")).unwrap();
+        assert_matches!(
+            bool_value.origin.as_ref(),
+            ValueOrigin::Path { source, .. } if Arc::ptr_eq(source, &origin)
+        );
+    }
+
     #[test]
     fn creating_config_using_macro() {
         let json = config! {
@@ -46,3 +46,44 @@ macro_rules! config {
         }
     };
 }
+
+/// Same as [`config!`], but allows specifying a human-readable origin of the created [`Json`](crate::Json) input
+/// (as a [`ValueOrigin::Custom`](crate::value::ValueOrigin::Custom) description). This is useful to make errors
+/// caused by programmatically built inputs point to where the input was defined.
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::{ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig};
+/// #[derive(Debug, DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     port: u16,
+/// }
+///
+/// let json = smart_config::config_with_origin!("tenant defaults", {
+///     "test.port": "??",
+/// });
+/// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+/// let repo = ConfigRepository::new(&schema).with(json);
+/// let err = repo.single::<TestConfig>()?.parse().unwrap_err();
+/// assert_eq!(err.first().origin().to_string(), "tenant defaults -> path 'test.port'");
+/// # anyhow::Ok(())
+/// ```
+#[macro_export]
+macro_rules! config_with_origin {
+    ($origin:expr, { $($path:tt : $value:expr),* $(,)? }) => {
+        {
+            const _:() = {
+                $crate::metadata::_private::assert_paths(&[$($path,)*]);
+            };
+
+            let origin = $crate::value::ValueOrigin::Custom(::std::string::ToString::to_string(&$origin));
+            #[allow(unused_mut)]
+            let mut json = $crate::Json::with_origin(origin, ::core::default::Default::default());
+            $(
+            json.merge($path, $value);
+            )*
+            json
+        }
+    };
+}
@@ -1,29 +1,50 @@
 use std::{
     any,
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Write as _,
     iter,
     marker::PhantomData,
+    mem,
     sync::Arc,
+    time::{Duration, Instant},
 };
 
-pub use self::{env::Environment, json::Json, yaml::Yaml};
+use serde::de::Error as _;
+
+#[cfg(feature = "http")]
+pub use self::http::{Http, HttpRequest, HttpSourceError, RetryPolicy};
+#[cfg(feature = "toml")]
+pub use self::toml::Toml;
+pub use self::{
+    cli::CliOverrides,
+    env::{Environment, NestedEnvironment},
+    json::Json,
+    yaml::Yaml,
+};
 use crate::{
-    de::{DeserializeContext, DeserializerOptions},
-    fallback::Fallbacks,
-    metadata::{BasicTypes, ConfigTag, ConfigVariant, TypeSuffixes},
+    de::{DeserializeContext, DeserializerOptions, UnknownKeys},
+    error::{ErrorKind, LocationInConfig},
+    fallback::{self, FallbackPriority, Fallbacks},
+    metadata::{AliasOptions, BasicTypes, ConfigMetadata, ConfigTag, ConfigVariant, TypeSuffixes},
     schema::{ConfigData, ConfigRef, ConfigSchema},
-    utils::{merge_json, EnumVariant, JsonObject},
-    value::{Map, Pointer, Value, ValueOrigin, WithOrigin},
+    utils::{merge_json, tracing, EnumVariant, JsonObject},
+    value::{Map, OriginKind, Pointer, StrValue, Value, ValueOrigin, WithOrigin},
     visit::Serializer,
-    DescribeConfig, DeserializeConfig, DeserializeConfigError, ParseError, ParseErrors,
+    DescribeConfig, DeserializeConfig, DeserializeConfigError, ParseError, ParseErrorCategory,
+    ParseErrors, Severity,
 };
 
 #[macro_use]
 mod macros;
+mod cli;
 mod env;
+#[cfg(feature = "http")]
+mod http;
 mod json;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "toml")]
+mod toml;
 mod yaml;
 
 /// Kind of a [`ConfigSource`].
@@ -51,11 +72,23 @@ impl ConfigSourceKind for Flat {
 }
 
 /// Source of configuration parameters that can be added to a [`ConfigRepository`].
+///
+/// Besides param values, a source may contain *unset markers* (by default, `{ "$unset": true }` objects) which remove
+/// values provided by lower-priority sources. See [`ConfigRepository`](ConfigRepository#unsetting-values) docs for details.
 pub trait ConfigSource {
     /// Kind of the source.
     type Kind: ConfigSourceKind;
     /// Converts this source into config contents.
     fn into_contents(self) -> WithOrigin<Map>;
+
+    /// Wraps this source so that object entries with `null` values are removed from it, making the corresponding
+    /// params absent (and thus using their default values). See [`StripNulls`] for details.
+    fn strip_nulls(self) -> StripNulls<Self>
+    where
+        Self: Sized,
+    {
+        StripNulls { inner: self }
+    }
 }
 
 /// Wraps a hierarchical source into a prefix.
@@ -100,18 +133,108 @@ impl<T: ConfigSource<Kind = Hierarchical>> ConfigSource for Prefixed<T> {
     }
 }
 
+/// Wraps a source removing object entries with `null` values from it before merging. Created using
+/// [`ConfigSource::strip_nulls()`].
+///
+/// This is useful for sources generated by templating systems that emit `key: null` for unset values.
+/// Entries are removed from objects on all levels, but arrays are left as is since `null` array elements
+/// may be meaningful.
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::{testing, ConfigSource, DescribeConfig, DeserializeConfig, Yaml};
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(default_t = 3)]
+///     retries: u32,
+/// }
+///
+/// let yaml = serde_yaml::from_str("retries: null")?;
+/// let yaml = Yaml::new("test.yml", yaml)?;
+/// let config: TestConfig = testing::test(yaml.strip_nulls())?;
+/// assert_eq!(config.retries, 3);
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct StripNulls<T> {
+    inner: T,
+}
+
+impl<T: ConfigSource> ConfigSource for StripNulls<T> {
+    type Kind = T::Kind;
+
+    fn into_contents(self) -> WithOrigin<Map> {
+        let mut contents = self.inner.into_contents();
+        WithOrigin::strip_nulls_from_map(&mut contents.inner);
+        contents
+    }
+}
+
 /// Prioritized list of configuration sources. Can be used to push multiple sources at once
 /// into a [`ConfigRepository`].
+///
+/// Each source has an integer priority; sources with higher priority override sources with lower priority.
+/// Sources with the same priority are applied in the order they were added, so that the latter sources
+/// override the former ones. [Fallbacks](crate::fallback) are placed automatically when creating a [`ConfigRepository`],
+/// so they don't need to be added explicitly.
+///
+/// # Examples
+///
+/// ```
+/// # use smart_config::{ConfigSchema, ConfigSources, DescribeConfig, DeserializeConfig, Environment};
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     port: u16,
+///     #[config(default_t = 1)]
+///     thread_count: usize,
+/// }
+///
+/// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+/// let env = Environment::from_iter("APP_", [("APP_TEST_PORT", "8080")]);
+/// let config: TestConfig = ConfigSources::default()
+///     // Env vars are added first, but have higher priority
+///     .with_source(1, env)
+///     .with_source(0, smart_config::config!("test.port": 3000, "test.thread_count": 4))
+///     .parse(&schema)?;
+/// assert_eq!(config.port, 8080);
+/// assert_eq!(config.thread_count, 4);
+/// # anyhow::Ok(())
+/// ```
 #[derive(Debug, Clone, Default)]
 pub struct ConfigSources {
-    inner: Vec<(WithOrigin<Map>, bool)>,
+    inner: Vec<(i32, WithOrigin<Map>, bool)>,
 }
 
 impl ConfigSources {
-    /// Pushes a configuration source at the end of the list.
+    /// Pushes a configuration source at the end of the list with the default (zero) priority.
     pub fn push<S: ConfigSource>(&mut self, source: S) {
+        self.push_with_priority(0, source);
+    }
+
+    /// Pushes a configuration source with the specified priority.
+    pub fn push_with_priority<S: ConfigSource>(&mut self, priority: i32, source: S) {
         self.inner
-            .push((source.into_contents(), <S::Kind>::IS_FLAT));
+            .push((priority, source.into_contents(), <S::Kind>::IS_FLAT));
+    }
+
+    /// Adds a configuration source with the specified priority.
+    #[must_use]
+    pub fn with_source<S: ConfigSource>(mut self, priority: i32, source: S) -> Self {
+        self.push_with_priority(priority, source);
+        self
+    }
+
+    /// Parses a single config of the specified type from these sources. This is a shortcut for creating
+    /// a [`ConfigRepository`] with [`ConfigRepository::with_all()`] and calling [`ConfigRepository::single()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the config is not present in the `schema` or is present at multiple locations,
+    /// or if parsing the config fails.
+    pub fn parse<C: DeserializeConfig>(self, schema: &ConfigSchema) -> anyhow::Result<C> {
+        let repo = ConfigRepository::new(schema).with_all(self);
+        Ok(repo.single::<C>()?.parse()?)
     }
 }
 
@@ -125,7 +248,38 @@ pub struct SourceInfo {
     pub param_count: usize,
 }
 
+/// Statistics about preprocessing and parsing configs in a [`ConfigRepository`]. Can be used to profile
+/// config loading without a `tracing` subscriber.
+///
+/// Returned by [`ConfigRepository::stats()`] (only covers preprocessing) and [`ConfigRepository::parse_all_with_stats()`]
+/// (covers both preprocessing and parsing).
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ParseStats {
+    /// Number of top-level configs parsed.
+    pub configs_parsed: usize,
+    /// Number of params with values in the merged sources (including fallbacks). Params set to their default values
+    /// are not counted.
+    pub params_resolved: usize,
+    /// Number of params with values provided by [fallbacks](crate::fallback) in the merged sources.
+    /// Fallback values overridden by other sources are not counted.
+    pub fallbacks_hit: usize,
+    /// Time spent resolving fallbacks and merging them into the repository.
+    pub fallbacks_elapsed: Duration,
+    /// Time spent preprocessing and merging sources into the repository (excluding fallbacks).
+    pub sources_elapsed: Duration,
+    /// Time spent deserializing configs.
+    pub parsing_elapsed: Duration,
+}
+
 /// Configuration serialization options.
+///
+/// # Ordering
+///
+/// Serialization output is deterministic. The serializer inserts params in the declaration order
+/// (i.e., in the order of [`ConfigMetadata::params`]), followed by nested configs. The resulting key order
+/// is determined by [`serde_json::Map`]; by default, keys are ordered alphabetically. If the `preserve_order` feature
+/// of `serde_json` is enabled in the dependency graph, keys follow the declaration order instead.
 #[derive(Debug, Clone, Default)]
 pub struct SerializerOptions {
     pub(crate) diff_with_default: bool,
@@ -177,7 +331,8 @@ impl SerializerOptions {
 /// # Merging sources
 ///
 /// [`Self::with()`] merges a new source into this repo. The new source has higher priority and will overwrite
-/// values defined in old sources, including via parameter aliases.
+/// values defined in old sources, including via parameter aliases. The only exception are
+/// [high-priority fallbacks](crate::fallback#priority), which always override values from file sources.
 ///
 /// # Type coercion
 ///
@@ -226,6 +381,98 @@ impl SerializerOptions {
 ///   are removed.
 /// - **Hiding secrets:** Values corresponding to [secret params](crate::de#secrets) are wrapped in
 ///   opaque, zero-on-drop wrappers.
+///
+/// # Unsetting values
+///
+/// A source can remove a value set by a lower-priority source by specifying an *unset marker*
+/// `{ "$unset": true }` at the corresponding location. The marker can be placed at a param or at a config
+/// (in which case all config params are unset), and is processed after aliases are resolved. Once a value is unset,
+/// the param falls back to its default value (if any). Unset locations are recorded and can be inspected
+/// via [`Self::unset_values()`].
+///
+/// Since an unset marker is a regular object, it may be ambiguous with legitimate values of object params
+/// (e.g., a map param with a `$unset` key). The marker key can be changed, or markers can be disabled altogether,
+/// via [`ConfigSchema::unset_marker()`].
+///
+/// ```rust
+/// use smart_config::{config, testing, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig};
+///
+/// #[derive(Debug, DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(default_t = 3)]
+///     retries: u32,
+/// }
+///
+/// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+/// let repo = ConfigRepository::new(&schema)
+///     .with(config!("test.retries": 10))
+///     .with(config!("test.retries": serde_json::json!({ "$unset": true })));
+/// let config: TestConfig = repo.single()?.parse()?;
+/// assert_eq!(config.retries, 3);
+/// assert_eq!(repo.unset_values().count(), 1);
+/// # anyhow::Ok(())
+/// ```
+///
+/// # Merge strategy
+///
+/// By default, objects provided by multiple sources are deep-merged, with values from higher-priority sources
+/// overriding values at the same paths. Params are always merged atomically, with the exception of [`rest`](crate::DescribeConfig#rest)
+/// params and values at the [catch-all path](ConfigSchema::set_catch_all()), which are deep-merged as well.
+/// A source can be added with [`MergeStrategy::Replace`] via [`Self::with_merge_strategy()`], so that objects
+/// in it fully shadow objects provided by lower-priority sources.
+///
+/// ```rust
+/// # use std::collections::HashMap;
+/// use smart_config::{config, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig, MergeStrategy};
+///
+/// #[derive(Debug, DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(default, rest)]
+///     limits: HashMap<String, u32>,
+/// }
+///
+/// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+/// let repo = ConfigRepository::new(&schema)
+///     .with(config!("test.reads": 10, "test.writes": 5))
+///     .with_merge_strategy(config!("test.reads": 20), MergeStrategy::Replace);
+/// let config: TestConfig = repo.single()?.parse()?;
+/// assert_eq!(config.limits, HashMap::from([("reads".into(), 20)]));
+/// # anyhow::Ok(())
+/// ```
+///
+/// # Unknown keys
+///
+/// Keys in hierarchical sources that do not correspond to any param or config in the schema (including via aliases)
+/// are recorded as *unknown keys* before garbage collection and can be inspected via [`Self::unknown_keys()`].
+/// Keys moved into a [`rest`](crate::DescribeConfig#rest) param or a [catch-all param](ConfigSchema::set_catch_all())
+/// are not considered unknown. Unknown keys are only reported for leaf values; e.g., for an unknown object,
+/// all its leaf entries are reported. Env variables are not checked since they routinely contain unrelated values.
+///
+/// By default, unknown keys are ignored during parsing. This can be changed using the
+/// [`unknown_keys`](DeserializerOptions::unknown_keys) deserializer option, so that unknown keys are reported
+/// by the parser for the closest enclosing config as warnings or errors.
+///
+/// ```rust
+/// use smart_config::{
+///     config, de::UnknownKeys, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig,
+/// };
+///
+/// #[derive(Debug, DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(default_t = 3)]
+///     retries: u32,
+/// }
+///
+/// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+/// let mut repo = ConfigRepository::new(&schema).with(config!("test.retires": 10));
+/// let unknown_keys: Vec<_> = repo.unknown_keys().map(|(path, _)| path).collect();
+/// assert_eq!(unknown_keys, ["test.retires"]);
+///
+/// repo.deserializer_options().unknown_keys = UnknownKeys::Deny;
+/// let err = repo.single::<TestConfig>()?.parse().unwrap_err();
+/// assert_eq!(err.first().code(), "unknown_key");
+/// # anyhow::Ok(())
+/// ```
 #[derive(Debug, Clone)]
 pub struct ConfigRepository<'a> {
     schema: &'a ConfigSchema,
@@ -233,18 +480,102 @@ pub struct ConfigRepository<'a> {
     de_options: DeserializerOptions,
     sources: Vec<SourceInfo>,
     merged: WithOrigin,
+    unset_values: BTreeMap<String, Arc<ValueOrigin>>,
+    overridden_values: BTreeMap<String, Vec<Arc<ValueOrigin>>>,
+    unknown_keys: BTreeMap<String, Arc<ValueOrigin>>,
+    deprecated_aliases: BTreeMap<String, DeprecatedAliasUsage>,
+    unknown_enum_tags: BTreeMap<String, UnknownEnumTag>,
+    stats: ParseStats,
+}
+
+/// Strategy to merge a source into a [`ConfigRepository`]. See [`ConfigRepository`] docs for details.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum MergeStrategy {
+    /// Deep-merge objects from the source with objects from the previously added sources.
+    #[default]
+    DeepMerge,
+    /// Objects from the source fully replace objects at the same paths from the previously added sources.
+    /// Objects corresponding to configs (and their ancestors) are still deep-merged, so that the source
+    /// doesn't erase unrelated params. Thus, this strategy only influences [`rest`](crate::DescribeConfig#rest) params
+    /// and values at the [catch-all path](ConfigSchema::set_catch_all()).
+    Replace,
+}
+
+/// Options for [`WithOrigin::guided_merge()`].
+#[derive(Debug)]
+struct MergeOptions<'a> {
+    schema: &'a ConfigSchema,
+    strategy: MergeStrategy,
+    config_paths: &'a HashSet<Pointer<'a>>,
+    /// Whether the merged source is a file source. Values from file sources do not override
+    /// [high-priority fallbacks](crate::fallback#priority).
+    is_file_source: bool,
+}
+
+/// Information about a deprecated param alias used in a source.
+#[derive(Debug, Clone)]
+struct DeprecatedAliasUsage {
+    canonical_path: String,
+    config_prefix: String,
+    config: &'static ConfigMetadata,
+    param_index: usize,
+    origin: Arc<ValueOrigin>,
+    options: AliasOptions,
+}
+
+/// Unknown variant key in an [externally tagged](ConfigSchema::accept_external_tag()) enum config.
+#[derive(Debug, Clone)]
+struct UnknownEnumTag {
+    key: String,
+    origin: Arc<ValueOrigin>,
 }
 
 impl<'a> ConfigRepository<'a> {
     /// Creates an empty config repo based on the provided schema.
     pub fn new(schema: &'a ConfigSchema) -> Self {
+        Self::new_with_fallback_filter(schema, |_| true)
+    }
+
+    /// Creates an empty config repo based on the provided schema, only applying [fallbacks](crate::fallback)
+    /// for params in configs which prefixes pass the provided `filter`. This allows disabling fallbacks for a part
+    /// of the schema, e.g. in tests.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{testing, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig};
+    /// #[derive(DescribeConfig, DeserializeConfig)]
+    /// struct LogConfig {
+    ///     #[config(default_t = "info".into(), fallback = &smart_config::fallback::Env("APP_LOG"))]
+    ///     directives: String,
+    /// }
+    ///
+    /// let mut schema = ConfigSchema::new(&LogConfig::DESCRIPTION, "log");
+    /// schema.insert(&LogConfig::DESCRIPTION, "test.log")?;
+    ///
+    /// // Mock env vars are active while `tester` is alive.
+    /// let mut tester = testing::Tester::<()>::default();
+    /// tester.set_env("APP_LOG", "debug");
+    /// let repo = ConfigRepository::new_with_fallback_filter(&schema, |prefix| !prefix.starts_with("test"));
+    ///
+    /// let config: LogConfig = repo.get("log").unwrap().parse()?;
+    /// assert_eq!(config.directives, "debug");
+    /// let config: LogConfig = repo.get("test.log").unwrap().parse()?;
+    /// assert_eq!(config.directives, "info");
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn new_with_fallback_filter(
+        schema: &'a ConfigSchema,
+        filter: impl Fn(&str) -> bool,
+    ) -> Self {
         let prefixes_for_canonical_configs: HashSet<_> = schema
             .iter_ll()
             .flat_map(|(path, _)| path.with_ancestors())
             .chain([Pointer("")])
             .collect();
 
-        let this = Self {
+        let mut this = Self {
             schema,
             prefixes_for_canonical_configs,
             de_options: DeserializerOptions::default(),
@@ -253,12 +584,34 @@ impl<'a> ConfigRepository<'a> {
                 inner: Value::Object(Map::default()),
                 origin: Arc::default(),
             },
+            unset_values: BTreeMap::new(),
+            overridden_values: BTreeMap::new(),
+            unknown_keys: BTreeMap::new(),
+            deprecated_aliases: BTreeMap::new(),
+            unknown_enum_tags: BTreeMap::new(),
+            stats: ParseStats::default(),
         };
-        if let Some(fallbacks) = Fallbacks::new(schema) {
-            this.with(fallbacks)
-        } else {
-            this
+
+        let started_at = Instant::now();
+        let (low_priority, high_priority) = fallback::with_shared_documents(|| {
+            (
+                Fallbacks::new_filtered(schema, FallbackPriority::Low, &filter),
+                Fallbacks::new_filtered(schema, FallbackPriority::High, &filter),
+            )
+        });
+        if let Some(fallbacks) = low_priority {
+            this = this.with(fallbacks);
+        }
+        // High-priority fallbacks are inserted once; file sources added later do not override them
+        // (see `MergeOptions::is_file_source`), while other sources do.
+        if let Some(fallbacks) = high_priority {
+            this = this.with(fallbacks);
         }
+        this.stats = ParseStats {
+            fallbacks_elapsed: started_at.elapsed(),
+            ..ParseStats::default()
+        };
+        this
     }
 
     /// Returns the wrapped configuration schema.
@@ -273,18 +626,37 @@ impl<'a> ConfigRepository<'a> {
 
     /// Extends this environment with a new configuration source.
     #[must_use]
-    pub fn with<S: ConfigSource>(mut self, source: S) -> Self {
-        self.insert_inner(source.into_contents(), <S::Kind>::IS_FLAT);
+    pub fn with<S: ConfigSource>(self, source: S) -> Self {
+        self.with_merge_strategy(source, MergeStrategy::DeepMerge)
+    }
+
+    /// Extends this environment with a new configuration source using the specified strategy to merge it
+    /// with the previously added sources. See [type-level docs](#merge-strategy) for details.
+    #[must_use]
+    pub fn with_merge_strategy<S: ConfigSource>(
+        mut self,
+        source: S,
+        strategy: MergeStrategy,
+    ) -> Self {
+        self.insert_inner(source.into_contents(), <S::Kind>::IS_FLAT, strategy);
         self
     }
 
-    #[tracing::instrument(
-        level = "debug",
-        name = "ConfigRepository::insert",
-        skip(self, contents)
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            name = "ConfigRepository::insert",
+            skip(self, contents)
+        )
     )]
-    fn insert_inner(&mut self, contents: WithOrigin<Map>, is_flat: bool) {
-        let mut source_value = if is_flat {
+    fn insert_inner(&mut self, contents: WithOrigin<Map>, is_flat: bool, strategy: MergeStrategy) {
+        let started_at = Instant::now();
+        let mut source_value = if let (true, Some(delimiter)) =
+            (is_flat, self.schema.custom_kv_delimiter())
+        {
+            WithOrigin::nest_delimited_kvs(contents.inner, self.schema, delimiter, &contents.origin)
+        } else if is_flat {
             WithOrigin::nest_kvs(contents.inner, self.schema, &contents.origin)
         } else {
             WithOrigin {
@@ -293,26 +665,71 @@ impl<'a> ConfigRepository<'a> {
             }
         };
 
-        let param_count =
-            source_value.preprocess_source(self.schema, &self.prefixes_for_canonical_configs);
+        let param_count = source_value.preprocess_source(
+            self.schema,
+            &self.prefixes_for_canonical_configs,
+            &mut self.unknown_keys,
+            &mut self.deprecated_aliases,
+            &mut self.unknown_enum_tags,
+        );
         tracing::debug!(param_count, "Inserted source into config repo");
-        self.merged
-            .guided_merge(source_value, self.schema, Pointer(""));
+        let merge_options = MergeOptions {
+            schema: self.schema,
+            strategy,
+            config_paths: &self.prefixes_for_canonical_configs,
+            is_file_source: contents.origin.kind() == OriginKind::File,
+        };
+        self.merged.guided_merge(
+            source_value,
+            &merge_options,
+            Pointer(""),
+            &mut self.unset_values,
+            &mut self.overridden_values,
+        );
         self.sources.push(SourceInfo {
             origin: contents.origin,
             param_count,
         });
+        self.stats.sources_elapsed += started_at.elapsed();
     }
 
-    ///  Extends this environment with a multiple configuration sources.
+    /// Extends this repository with multiple configuration sources. Sources are applied in the order of increasing
+    /// priority; sources with the same priority are applied in the order they were added.
     #[must_use]
     pub fn with_all(mut self, sources: ConfigSources) -> Self {
-        for (contents, is_flat) in sources.inner {
-            self.insert_inner(contents, is_flat);
+        let mut sources = sources.inner;
+        sources.sort_by_key(|(priority, ..)| *priority);
+        for (_, contents, is_flat) in sources {
+            self.insert_inner(contents, is_flat, MergeStrategy::DeepMerge);
         }
         self
     }
 
+    /// Returns statistics about preprocessing sources merged in this repository, such as the time spent resolving fallbacks.
+    /// Use [`Self::parse_all_with_stats()`] to get parsing statistics as well.
+    pub fn stats(&self) -> ParseStats {
+        let mut stats = self.stats.clone();
+        let resolved_values = self
+            .schema
+            .iter()
+            .flat_map(|config| {
+                let prefix = Pointer(config.prefix());
+                config
+                    .metadata()
+                    .params
+                    .iter()
+                    .map(move |param| prefix.join(param.name))
+            })
+            .filter_map(|path| self.merged.get(Pointer(&path)));
+        for value in resolved_values {
+            stats.params_resolved += 1;
+            if value.origin.is_fallback() {
+                stats.fallbacks_hit += 1;
+            }
+        }
+        stats
+    }
+
     /// Provides information about sources merged in this repository.
     pub fn sources(&self) -> &[SourceInfo] {
         &self.sources
@@ -323,6 +740,94 @@ impl<'a> ConfigRepository<'a> {
         &self.merged
     }
 
+    /// Iterates over canonical paths [unset](#unsetting-values) in the merged sources, together with
+    /// the origins of the corresponding unset markers. Paths set again by a later source are not included;
+    /// if a later source only sets some values inside an unset config, the config path is still included.
+    pub fn unset_values(&self) -> impl Iterator<Item = (&str, &Arc<ValueOrigin>)> + '_ {
+        self.unset_values
+            .iter()
+            .map(|(path, origin)| (path.as_str(), origin))
+    }
+
+    /// Returns the origin of the value at the specified canonical `path` in the merged sources, i.e., the origin
+    /// of the source that "won" for the path. Returns `None` if none of the sources provide a value at `path`.
+    ///
+    /// Default param values are not taken into account since they are applied during parsing.
+    pub fn origin_of(&self, path: &str) -> Option<&Arc<ValueOrigin>> {
+        Some(&self.merged.get(Pointer(path))?.origin)
+    }
+
+    /// Checks whether the value at the specified canonical `path` in the merged sources is provided
+    /// by a [fallback](crate::fallback), i.e., whether no other source provides a value for the param
+    /// (or the value is overridden by a [high-priority fallback](crate::fallback#priority)).
+    ///
+    /// Returns `false` if there is no value at `path`, e.g. if the corresponding param will be set to its default value.
+    /// See [`ValueOrigin::is_fallback()`] for details.
+    pub fn used_fallback(&self, path: &str) -> bool {
+        self.origin_of(path)
+            .is_some_and(|origin| origin.is_fallback())
+    }
+
+    /// Iterates over origins of values at the specified canonical `path` that were overridden by later sources
+    /// (or by [high-priority fallbacks](crate::fallback#priority)), in the order the values were provided.
+    /// The origin of the winning value is not included; it can be obtained via [`Self::origin_of()`].
+    ///
+    /// Params are merged atomically, so overrides are recorded at param paths; e.g., if a source overrides
+    /// a single entry in an object param, the entire object is considered overridden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{config, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig, Environment};
+    /// # use smart_config::value::ValueOrigin;
+    /// #[derive(DescribeConfig, DeserializeConfig)]
+    /// struct TestConfig {
+    ///     port: u16,
+    /// }
+    ///
+    /// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    /// let env = Environment::from_iter("APP_", [("APP_TEST_PORT", "3000")]);
+    /// let repo = ConfigRepository::new(&schema)
+    ///     .with(config!("test.port": 8080))
+    ///     .with(env);
+    ///
+    /// let origin = repo.origin_of("test.port").unwrap();
+    /// assert_eq!(origin.to_string(), "env variable 'APP_TEST_PORT'");
+    /// let overridden: Vec<_> = repo.overridden_origins("test.port").collect();
+    /// assert_eq!(overridden.len(), 1);
+    /// assert!(overridden[0].to_string().contains("test.port"));
+    /// ```
+    pub fn overridden_origins(&self, path: &str) -> impl Iterator<Item = &Arc<ValueOrigin>> + '_ {
+        self.overridden_values.get(path).into_iter().flatten()
+    }
+
+    /// Iterates over [unknown keys](#unknown-keys) encountered in the merged sources, together with the origins
+    /// of their values. Keys are ordered by path.
+    pub fn unknown_keys(&self) -> impl Iterator<Item = (&str, &Arc<ValueOrigin>)> + '_ {
+        self.unknown_keys
+            .iter()
+            .map(|(path, origin)| (path.as_str(), origin))
+    }
+
+    /// Iterates over deprecated aliases used in the merged sources, together with the canonical paths they resolve to.
+    /// Aliases are ordered by path. The same usages are reported as warnings when parsing the affected configs
+    /// (e.g., via [`ConfigParser::parse_with_warnings()`]).
+    pub fn used_deprecated_aliases(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.effective_deprecated_aliases()
+            .map(|(alias, usage)| (alias, usage.canonical_path.as_str()))
+    }
+
+    /// Iterates over deprecated alias usages providing the merged value for the canonical path, i.e., not overridden
+    /// by a later source.
+    fn effective_deprecated_aliases(
+        &self,
+    ) -> impl Iterator<Item = (&str, &DeprecatedAliasUsage)> + '_ {
+        self.deprecated_aliases.iter().filter_map(|(alias, usage)| {
+            let merged = self.merged.get(Pointer(&usage.canonical_path))?;
+            Arc::ptr_eq(&merged.origin, &usage.origin).then_some((alias.as_str(), usage))
+        })
+    }
+
     /// Returns canonical JSON for all configurations contained in the schema, with values filled both from the contained sources
     /// and from defaults.
     ///
@@ -363,6 +868,72 @@ impl<'a> ConfigRepository<'a> {
         Ok(json)
     }
 
+    /// Parses all top-level configs in the schema. Unlike parsing configs one by one and bailing on the first failure,
+    /// this method attempts to parse *all* configs even if some of them fail, so that the returned errors cover all configs.
+    /// Nested configs are not parsed separately; they can be accessed via their parent configs.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors for all configs that failed parsing, in the order of [`Self::iter()`].
+    pub fn parse_all(&self) -> Result<ParsedConfigs, ParseErrors> {
+        self.parse_all_with_stats().0
+    }
+
+    /// Same as [`Self::parse_all()`], but additionally returns statistics about preprocessing and parsing configs.
+    /// This is useful for profiling config loading, e.g. to detect configs slowing down the application startup.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{config, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig};
+    /// #[derive(DescribeConfig, DeserializeConfig)]
+    /// struct TestConfig {
+    ///     port: u16,
+    ///     #[config(default_t = 3)]
+    ///     workers: usize,
+    /// }
+    ///
+    /// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "test");
+    /// let repo = ConfigRepository::new(&schema).with(config!("test.port": 8080));
+    /// let (configs, stats) = repo.parse_all_with_stats();
+    /// configs?;
+    /// assert_eq!(stats.configs_parsed, 1);
+    /// assert_eq!(stats.params_resolved, 1); // `workers` is set to the default value
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn parse_all_with_stats(&self) -> (Result<ParsedConfigs, ParseErrors>, ParseStats) {
+        let mut stats = self.stats();
+        let started_at = Instant::now();
+        let mut configs = HashMap::new();
+        let mut errors = ParseErrors::default();
+        for config_parser in self.iter() {
+            let config_ref = config_parser.config();
+            if !config_ref.is_top_level() {
+                continue;
+            }
+
+            stats.configs_parsed += 1;
+            match config_parser.parse() {
+                Ok(config) => {
+                    let key = (
+                        config_ref.metadata().ty.id(),
+                        config_ref.prefix().to_owned(),
+                    );
+                    configs.insert(key, config);
+                }
+                Err(err) => errors.merge(err),
+            }
+        }
+        stats.parsing_elapsed = started_at.elapsed();
+
+        let output = if errors.has_errors() {
+            Err(errors)
+        } else {
+            Ok(ParsedConfigs { inner: configs })
+        };
+        (output, stats)
+    }
+
     /// Iterates over parsers for all configs in the schema.
     pub fn iter(&self) -> impl Iterator<Item = ConfigParser<'_, ()>> + '_ {
         self.schema.iter().map(|config_ref| ConfigParser {
@@ -398,6 +969,32 @@ impl<'a> ConfigRepository<'a> {
     }
 }
 
+/// Top-level configs parsed by [`ConfigRepository::parse_all()`].
+#[derive(Debug)]
+pub struct ParsedConfigs {
+    inner: HashMap<(any::TypeId, String), Box<dyn any::Any>>,
+}
+
+impl ParsedConfigs {
+    /// Gets a reference to the config of the specified type mounted at the canonical `prefix`.
+    /// Returns `None` if there is no such top-level config in the schema.
+    pub fn get<C: DeserializeConfig>(&self, prefix: &str) -> Option<&C> {
+        let config = self
+            .inner
+            .get(&(any::TypeId::of::<C>(), prefix.to_owned()))?;
+        config.downcast_ref()
+    }
+
+    /// Takes the config of the specified type mounted at the canonical `prefix`.
+    /// Returns `None` if there is no such top-level config in the schema, or if the config was already taken.
+    pub fn take<C: DeserializeConfig>(&mut self, prefix: &str) -> Option<C> {
+        let config = self
+            .inner
+            .remove(&(any::TypeId::of::<C>(), prefix.to_owned()))?;
+        Some(*config.downcast().ok()?)
+    }
+}
+
 /// Parser of configuration input in a [`ConfigRepository`].
 #[derive(Debug)]
 pub struct ConfigParser<'a, C> {
@@ -438,13 +1035,48 @@ impl<'a, C> ConfigParser<'a, C> {
         self.config_ref
     }
 
+    /// Returns the value tree the config is parsed from, i.e., the object at the config prefix in the merged sources
+    /// (including fallbacks). Origins of all values in the tree are preserved, which is useful for debugging.
+    /// Returns `None` if none of the sources provide values for the config.
+    ///
+    /// Default param values are not included into the tree since they are applied during parsing.
+    pub fn resolved_tree(&self) -> Option<&'a WithOrigin> {
+        self.repo.merged.get(Pointer(self.config_ref.prefix()))
+    }
+
     fn with_context<R>(
         &self,
         action: impl FnOnce(DeserializeContext<'_>) -> Result<R, DeserializeConfigError>,
     ) -> Result<R, ParseErrors> {
+        self.with_context_and_warnings(action)
+            .map(|(output, _)| output)
+    }
+
+    fn with_context_and_warnings<R>(
+        &self,
+        action: impl FnOnce(DeserializeContext<'_>) -> Result<R, DeserializeConfigError>,
+    ) -> Result<(R, Vec<ParseError>), ParseErrors> {
         let mut errors = ParseErrors::default();
         let prefix = self.config_ref.prefix();
         let metadata = self.config_ref.data.metadata;
+        if let Some(warning) = self.deprecation_warning() {
+            errors.push(warning);
+        }
+        for err in self.unknown_key_errors() {
+            errors.push(err);
+        }
+        for warning in self.deprecated_alias_warnings() {
+            errors.push(warning);
+        }
+        let unknown_tag_errors = self.unknown_tag_errors();
+        if !unknown_tag_errors.is_empty() {
+            // Enum configs with unknown tags cannot be parsed, and parsing would produce confusing missing tag errors.
+            for err in unknown_tag_errors {
+                errors.push(err);
+            }
+            return Err(errors);
+        }
+
         let ctx = DeserializeContext::new(
             &self.repo.de_options,
             &self.repo.merged,
@@ -452,12 +1084,174 @@ impl<'a, C> ConfigParser<'a, C> {
             metadata,
             &mut errors,
         );
-        action(ctx).map_err(|_| {
-            if errors.len() == 0 {
-                errors.push(ParseError::generic(prefix.to_owned(), metadata));
+        match action(ctx) {
+            // Errors may be present if unknown keys are denied.
+            Ok(output) if !errors.has_errors() => Ok((output, errors.into_iter().collect())),
+            Ok(_) => Err(errors),
+            Err(_) => {
+                if !errors.has_errors() {
+                    errors.push(ParseError::generic(prefix.to_owned(), metadata));
+                }
+                Err(errors)
             }
-            errors
-        })
+        }
+    }
+}
+
+impl<C> ConfigParser<'_, C> {
+    /// Returns a warning if the config is deprecated and receives values from sources.
+    fn deprecation_warning(&self) -> Option<ParseError> {
+        let message = self.config_ref.deprecation()?;
+        let prefix = self.config_ref.prefix();
+        let value = self.repo.merged.get(Pointer(prefix))?;
+        if matches!(&value.inner, Value::Object(map) if map.is_empty()) {
+            return None;
+        }
+
+        let err = format!("config is deprecated: {message}");
+        let mut warning = ParseError::generic(prefix.to_owned(), self.config_ref.metadata());
+        warning.inner = serde_json::Error::custom(err);
+        warning.origin = value.origin.clone();
+        warning.severity = Severity::Warning;
+        Some(warning)
+    }
+
+    /// Checks whether the specified config is this config or one of its (transitively) nested configs.
+    fn contains_config(&self, config_ref: ConfigRef<'_>) -> bool {
+        let this_prefix = self.config_ref.prefix();
+        let this_ty = self.config_ref.metadata().ty.id();
+        let mut config_ref = Some(config_ref);
+        while let Some(current) = config_ref {
+            if current.prefix() == this_prefix && current.metadata().ty.id() == this_ty {
+                return true;
+            }
+            config_ref = current.parent_link().map(|(parent, _)| parent);
+        }
+        false
+    }
+
+    /// Returns warnings for deprecated aliases used for params in this config or its nested configs.
+    fn deprecated_alias_warnings(&self) -> Vec<ParseError> {
+        let schema = self.repo.schema;
+
+        let usages = self
+            .repo
+            .effective_deprecated_aliases()
+            .filter(|(_, usage)| {
+                schema
+                    .get(usage.config, &usage.config_prefix)
+                    .is_some_and(|config_ref| self.contains_config(config_ref))
+            });
+        let warnings = usages.map(|(alias_path, usage)| {
+            let mut message = format!("alias `{alias_path}` is deprecated");
+            if let Some(version) = usage.options.deprecated_since {
+                write!(message, " since {version}").unwrap();
+            }
+            if let Some(version) = usage.options.remove_in {
+                write!(message, " and will be removed in {version}").unwrap();
+            }
+            write!(message, "; use `{}` instead", usage.canonical_path).unwrap();
+
+            let mut warning = ParseError::generic(usage.canonical_path.clone(), usage.config);
+            warning.inner = serde_json::Error::custom(message);
+            warning.origin = usage.origin.clone();
+            warning.location_in_config = Some(LocationInConfig::Param(usage.param_index));
+            warning.severity = Severity::Warning;
+            warning
+        });
+        warnings.collect()
+    }
+
+    /// Returns errors for unknown variant keys in [externally tagged](ConfigSchema::accept_external_tag()) enum configs
+    /// in this config or its nested configs.
+    fn unknown_tag_errors(&self) -> Vec<ParseError> {
+        let schema = self.repo.schema;
+        // Nested enum configs in inactive variants may have spurious unknown tags (e.g., if they are flattened
+        // into an enum config with the unknown tag).
+        let configs = schema.iter().filter(|&config_ref| {
+            self.contains_config(config_ref)
+                && ConfigSchema::is_config_active(&self.repo.merged, config_ref)
+        });
+        let errors = configs.filter_map(|config_ref| {
+            let tag = config_ref.metadata().tag?;
+            let tag_path = Pointer(config_ref.prefix()).join(tag.param.name);
+            let unknown_tag = self.repo.unknown_enum_tags.get(&tag_path)?;
+            if self.repo.merged.get(Pointer(&tag_path)).is_some() {
+                return None; // The tag is provided by another source
+            }
+
+            let expected: Vec<_> = tag
+                .variants
+                .iter()
+                .map(|variant| format!("`{}`", variant.name))
+                .collect();
+            let mut err = ParseError::generic(tag_path, config_ref.metadata());
+            err.inner = serde_json::Error::custom(format!(
+                "unknown variant key `{}` for externally tagged enum, expected one of {}",
+                unknown_tag.key,
+                expected.join(", ")
+            ));
+            err.kind = ErrorKind::UnknownTag;
+            err.origin = unknown_tag.origin.clone();
+            err.location_in_config = config_ref
+                .metadata()
+                .params
+                .iter()
+                .position(|param| std::ptr::eq(param, tag.param))
+                .map(LocationInConfig::Param);
+            Some(err)
+        });
+        errors.collect()
+    }
+
+    /// Returns errors / warnings for unknown keys for which this config is the closest enclosing config.
+    /// Additionally, returns errors for unknown keys in this config or its nested configs that
+    /// [deny unknown keys](ConfigMetadata::deny_unknown).
+    fn unknown_key_errors(&self) -> Vec<ParseError> {
+        let default_severity = match self.repo.de_options.unknown_keys {
+            UnknownKeys::Ignore => None,
+            UnknownKeys::Warn => Some(Severity::Warning),
+            UnknownKeys::Deny => Some(Severity::Error),
+        };
+        let schema = self.repo.schema;
+        let prefix = self.config_ref.prefix();
+
+        let unknown_keys = self.repo.unknown_keys.iter().filter_map(|(path, origin)| {
+            let closest_config = Pointer(path)
+                .with_ancestors()
+                .filter(|ancestor| {
+                    ancestor.0 != path.as_str() && schema.contains_config_at(*ancestor)
+                })
+                .last()
+                .or_else(|| {
+                    schema
+                        .contains_config_at(Pointer(""))
+                        .then_some(Pointer(""))
+                })?;
+
+            let is_denied = schema.iter().any(|config_ref| {
+                config_ref.prefix() == closest_config.0
+                    && config_ref.metadata().deny_unknown
+                    && self.contains_config(config_ref)
+            });
+            let severity = if is_denied {
+                Severity::Error
+            } else if closest_config.0 == prefix {
+                default_severity?
+            } else {
+                return None;
+            };
+            Some((path, origin, severity))
+        });
+        let errors = unknown_keys.map(|(path, origin, severity)| {
+            let mut err = ParseError::generic(path.clone(), self.config_ref.metadata());
+            err.inner = serde_json::Error::custom(format!("unknown key `{path}`"));
+            err.category = ParseErrorCategory::UnknownKey;
+            err.origin = origin.clone();
+            err.severity = severity;
+            err
+        });
+        errors.collect()
     }
 }
 
@@ -473,6 +1267,16 @@ impl<C: DeserializeConfig> ConfigParser<'_, C> {
         self.with_context(|ctx| ctx.deserialize_config::<C>())
     }
 
+    /// Performs parsing, returning [warnings](crate::Severity::Warning) encountered during parsing together with the config.
+    ///
+    /// # Errors
+    ///
+    /// Returns errors encountered during parsing, same as [`Self::parse()`]. In this case, warnings are included into the returned errors.
+    #[allow(clippy::redundant_closure_for_method_calls)] // doesn't work as an fn pointer because of the context lifetime
+    pub fn parse_with_warnings(self) -> Result<(C, Vec<ParseError>), ParseErrors> {
+        self.with_context_and_warnings(|ctx| ctx.deserialize_config::<C>())
+    }
+
     /// Parses an optional config. Returns `None` if the config object is not present (i.e., none of the config params / sub-configs
     /// are set); otherwise, tries to perform parsing.
     ///
@@ -486,23 +1290,66 @@ impl<C: DeserializeConfig> ConfigParser<'_, C> {
 }
 
 impl WithOrigin {
+    /// Returns a copy of this value tree with values of [secret params](crate::de#secrets) replaced
+    /// by the `"[redacted]"` string. Secret params are located using their canonical paths in the provided `schema`;
+    /// origins of the redacted values are retained.
+    ///
+    /// This is useful to log the fully assembled config tree (e.g., [`ConfigRepository::merged()`]) for diagnostics.
+    #[must_use]
+    pub fn redacted(&self, schema: &ConfigSchema) -> Self {
+        let mut this = self.clone();
+        for (prefix, config_data) in schema.iter_ll() {
+            let Some(Self {
+                inner: Value::Object(config_object),
+                ..
+            }) = this.get_mut(prefix)
+            else {
+                continue;
+            };
+
+            for param in config_data.metadata.params {
+                if !param.type_description().contains_secrets() {
+                    continue;
+                }
+                if let Some(value) = config_object.get_mut(param.name) {
+                    if !matches!(value.inner, Value::Null) {
+                        value.inner = Value::String(StrValue::Plain("[redacted]".to_owned()));
+                    }
+                }
+            }
+        }
+        this
+    }
+
     fn preprocess_source(
         &mut self,
         schema: &ConfigSchema,
         prefixes_for_canonical_configs: &HashSet<Pointer<'_>>,
+        unknown_keys: &mut BTreeMap<String, Arc<ValueOrigin>>,
+        deprecated_aliases: &mut BTreeMap<String, DeprecatedAliasUsage>,
+        unknown_enum_tags: &mut BTreeMap<String, UnknownEnumTag>,
     ) -> usize {
-        self.copy_aliased_values(schema);
+        self.convert_external_enum_tags(schema, unknown_enum_tags);
+        self.copy_aliased_values(schema, deprecated_aliases);
         self.mark_secrets(schema);
         self.convert_serde_enums(schema);
         self.nest_object_params_and_sub_configs(schema);
         self.nest_array_params(schema);
+        self.capture_rest_entries(schema);
+        self.capture_unknown_keys(schema, prefixes_for_canonical_configs);
+        self.collect_unknown_keys(schema, Pointer(""), unknown_keys);
         self.collect_garbage(schema, prefixes_for_canonical_configs, Pointer(""))
     }
 
-    #[tracing::instrument(level = "debug", skip_all)]
-    fn copy_aliased_values(&mut self, schema: &ConfigSchema) {
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn copy_aliased_values(
+        &mut self,
+        schema: &ConfigSchema,
+        deprecated_aliases: &mut BTreeMap<String, DeprecatedAliasUsage>,
+    ) {
         for (prefix, config_data) in schema.iter_ll() {
-            let (new_values, new_map_origin) = self.copy_aliases_for_config(config_data);
+            let (new_values, new_map_origin) =
+                self.copy_aliases_for_config(config_data, deprecated_aliases);
             if new_values.is_empty() {
                 continue;
             }
@@ -521,13 +1368,18 @@ impl WithOrigin {
     }
 
     #[must_use = "returned map should be inserted into the config"]
-    fn copy_aliases_for_config(&self, config: &ConfigData) -> (Map, Option<Arc<ValueOrigin>>) {
-        let prefix = config.prefix();
-        let canonical_map = match self.get(prefix).map(|val| &val.inner) {
+    #[allow(clippy::too_many_lines)] // mostly comments and logging
+    fn copy_aliases_for_config(
+        &self,
+        config: &ConfigData,
+        deprecated_aliases: &mut BTreeMap<String, DeprecatedAliasUsage>,
+    ) -> (Map, Option<Arc<ValueOrigin>>) {
+        let config_prefix = config.prefix();
+        let canonical_map = match self.get(config_prefix).map(|val| &val.inner) {
             Some(Value::Object(map)) => Some(map),
             Some(_) => {
                 tracing::warn!(
-                    prefix = prefix.0,
+                    prefix = config_prefix.0,
                     config = ?config.metadata.ty,
                     "canonical config location contains a non-object"
                 );
@@ -539,11 +1391,14 @@ impl WithOrigin {
         let mut new_values = Map::new();
         let mut new_map_origin = None;
 
-        for param in config.metadata.params {
+        for (param_index, param) in config.metadata.params.iter().enumerate() {
             // Create a prioritized iterator of all candidate paths
             let all_paths = config.all_paths_for_param(param);
 
             for (path, alias_options) in all_paths {
+                if !alias_options.is_active() {
+                    continue;
+                }
                 let (prefix, name) = Pointer(&path)
                     .split_last()
                     .expect("param paths are never empty");
@@ -594,15 +1449,30 @@ impl WithOrigin {
                     }
 
                     if !new_values.contains_key(canonical_key) {
-                        if alias_options.is_deprecated {
+                        if alias_options.alias.is_deprecated {
+                            let canonical_path = config_prefix.join(canonical_key);
                             tracing::warn!(
                                 path,
                                 origin = %val.origin,
                                 config = ?config.metadata.ty,
                                 param = param.rust_field_name,
-                                canonical_path = prefix.join(canonical_key),
+                                canonical_path,
                                 "using deprecated alias; please use canonical_path instead"
                             );
+
+                            let alias_key = match suffix {
+                                Some(suffix) => format!("{name}_{suffix}"),
+                                None => name.to_owned(),
+                            };
+                            let usage = DeprecatedAliasUsage {
+                                canonical_path,
+                                config_prefix: config_prefix.0.to_owned(),
+                                config: config.metadata,
+                                param_index,
+                                origin: val.origin.clone(),
+                                options: alias_options.alias,
+                            };
+                            deprecated_aliases.insert(prefix.join(&alias_key), usage);
                         }
 
                         tracing::trace!(
@@ -659,7 +1529,7 @@ impl WithOrigin {
                         "marked param as secret"
                     );
                     str.make_secret();
-                } else {
+                } else if !value.is_unset_marker(schema) {
                     tracing::warn!(
                         prefix = prefix.0,
                         config = ?config_data.metadata.ty,
@@ -671,7 +1541,95 @@ impl WithOrigin {
         }
     }
 
-    #[tracing::instrument(level = "debug", skip_all)]
+    /// Converts [externally tagged](ConfigSchema::accept_external_tag()) enum configs to the internally tagged form.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn convert_external_enum_tags(
+        &mut self,
+        schema: &ConfigSchema,
+        unknown_enum_tags: &mut BTreeMap<String, UnknownEnumTag>,
+    ) {
+        if !schema.accepts_external_tag() {
+            return;
+        }
+
+        // Configs at the same prefix are ordered by depth, so that tags of the enclosing enum configs are converted first.
+        for config_data in schema.iter() {
+            let Some(tag) = &config_data.metadata().tag else {
+                continue; // Not an enum config, nothing to do.
+            };
+            let prefix = Pointer(config_data.prefix());
+            let Some(WithOrigin {
+                inner: Value::Object(map),
+                ..
+            }) = self.get_mut(prefix)
+            else {
+                continue;
+            };
+            if map.contains_key(tag.param.name) {
+                continue;
+            }
+
+            let mut unclaimed_keys = map
+                .keys()
+                .filter(|key| !schema.contains_path_or_descendants(Pointer(&prefix.join(key))));
+            let (Some(key), None) = (unclaimed_keys.next(), unclaimed_keys.next()) else {
+                continue;
+            };
+            if !matches!(map[key].inner, Value::Object(_)) {
+                continue;
+            }
+            let key = key.clone();
+            let tag_path = prefix.join(tag.param.name);
+
+            let Some(variant) = Self::find_variant_by_key(tag, &key) else {
+                tracing::info!(
+                    config = ?config_data.metadata().ty,
+                    prefix = prefix.0,
+                    key,
+                    "unknown variant key for externally tagged enum"
+                );
+                let origin = map[&key].origin.clone();
+                unknown_enum_tags.insert(tag_path, UnknownEnumTag { key, origin });
+                continue;
+            };
+
+            tracing::debug!(
+                config = ?config_data.metadata().ty,
+                prefix = prefix.0,
+                variant = variant.name,
+                "converting externally tagged enum"
+            );
+            let content = map.remove(&key).unwrap();
+            let Value::Object(content_map) = content.inner else {
+                unreachable!(); // checked above
+            };
+            let origin = ValueOrigin::Synthetic {
+                source: content.origin,
+                transform: "externally tagged enum".to_owned(),
+            };
+            map.insert(
+                tag.param.name.to_owned(),
+                WithOrigin::new(variant.name.to_owned().into(), Arc::new(origin)),
+            );
+            for (name, value) in content_map {
+                map.entry(name).or_insert(value);
+            }
+            unknown_enum_tags.remove(&tag_path);
+        }
+    }
+
+    fn find_variant_by_key(tag: &'static ConfigTag, key: &str) -> Option<&'static ConfigVariant> {
+        tag.variants.iter().find(|variant| {
+            iter::once(variant.name)
+                .chain(variant.aliases.iter().copied())
+                .any(|name| {
+                    name == key
+                        || EnumVariant::new(name).is_some_and(|name| name.to_snake_case() == key)
+                })
+        })
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
     fn convert_serde_enums(&mut self, schema: &ConfigSchema) {
         for config_data in schema.iter() {
             let config_meta = config_data.metadata();
@@ -686,7 +1644,9 @@ impl WithOrigin {
 
             let canonical_map = self.get(prefix).and_then(|val| val.inner.as_object());
             let alias_maps = config_data
+                .data
                 .aliases()
+                .filter(|(_, options)| options.is_active())
                 .filter_map(|(alias, _)| self.get(Pointer(alias))?.inner.as_object());
 
             if canonical_map.is_some_and(|map| map.contains_key(tag.param.name)) {
@@ -774,6 +1734,185 @@ impl WithOrigin {
         Some((variant, variant_content))
     }
 
+    /// Moves entries in config objects not claimed by params or sub-configs into the `rest` param of the config, if any.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn capture_rest_entries(&mut self, schema: &ConfigSchema) {
+        for (prefix, config_data) in schema.iter_ll() {
+            let Some(rest_param) = config_data
+                .metadata
+                .params
+                .iter()
+                .find(|param| param.is_rest)
+            else {
+                continue;
+            };
+            let rest_path = prefix.join(rest_param.name);
+            if let Some(val) = self.get(Pointer(&rest_path)) {
+                if !matches!(&val.inner, Value::Object(_)) {
+                    tracing::warn!(
+                        path = rest_path,
+                        origin = %val.origin,
+                        "`rest` param location contains a non-object; not capturing unclaimed entries"
+                    );
+                    continue;
+                }
+            }
+
+            let Some(config_object) = self.get_mut(prefix) else {
+                continue;
+            };
+            let config_origin = config_object.origin.clone();
+            let Value::Object(config_object) = &mut config_object.inner else {
+                continue;
+            };
+            let claimed_keys = schema.claimed_keys(prefix);
+            let unclaimed_keys: Vec<_> = config_object
+                .keys()
+                .filter(|key| !claimed_keys.contains(key))
+                .cloned()
+                .collect();
+            if unclaimed_keys.is_empty() {
+                continue;
+            }
+
+            let captured: Map = unclaimed_keys
+                .into_iter()
+                .filter_map(|key| {
+                    let value = config_object.remove(&key)?;
+                    Some((key, value))
+                })
+                .collect();
+            tracing::debug!(
+                path = rest_path,
+                keys = ?captured.keys().collect::<Vec<_>>(),
+                "captured unclaimed config entries"
+            );
+            let rest_object = config_object
+                .entry(rest_param.name.to_owned())
+                .or_insert_with(|| {
+                    let origin = Arc::new(ValueOrigin::Synthetic {
+                        source: config_origin,
+                        transform: format!("capture of unclaimed entries for '{rest_path}'"),
+                    });
+                    Self::new(Value::Object(Map::new()), origin)
+                });
+            let Value::Object(rest_object) = &mut rest_object.inner else {
+                unreachable!(); // Due to the checks above
+            };
+            for (key, value) in captured {
+                rest_object.entry(key).or_insert(value);
+            }
+        }
+    }
+
+    /// Moves top-level keys not claimed by the schema into the catch-all param, if one is set.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
+    fn capture_unknown_keys(
+        &mut self,
+        schema: &ConfigSchema,
+        prefixes_for_canonical_configs: &HashSet<Pointer<'_>>,
+    ) {
+        let Some(catch_all_path) = schema.catch_all() else {
+            return;
+        };
+        if let Some(val) = self.get(catch_all_path) {
+            if !matches!(&val.inner, Value::Object(_)) {
+                tracing::warn!(
+                    path = catch_all_path.0,
+                    origin = %val.origin,
+                    "catch-all param location contains a non-object; not capturing unknown keys"
+                );
+                return;
+            }
+        }
+        let Value::Object(map) = &mut self.inner else {
+            return;
+        };
+        let unknown_keys: Vec<_> = map
+            .keys()
+            .filter(|key| {
+                !prefixes_for_canonical_configs.contains(&Pointer(key.as_str()))
+                    && !schema.is_known_top_level_key(key)
+            })
+            .cloned()
+            .collect();
+        if unknown_keys.is_empty() {
+            return;
+        }
+
+        let captured: Map = unknown_keys
+            .into_iter()
+            .filter_map(|key| {
+                let value = map.remove(&key)?;
+                Some((key, value))
+            })
+            .collect();
+        tracing::debug!(
+            path = catch_all_path.0,
+            keys = ?captured.keys().collect::<Vec<_>>(),
+            "captured unknown top-level keys"
+        );
+        let source_origin = self.origin.clone();
+        let catch_all = self.ensure_object(catch_all_path, |_| {
+            Arc::new(ValueOrigin::Synthetic {
+                source: source_origin.clone(),
+                transform: "capture of unknown top-level keys".to_owned(),
+            })
+        });
+        for (key, value) in captured {
+            catch_all.entry(key).or_insert(value);
+        }
+    }
+
+    /// Collects paths to leaf values that do not correspond to any params or configs in the schema
+    /// (including via aliases). Must be called before garbage collection.
+    fn collect_unknown_keys(
+        &self,
+        schema: &ConfigSchema,
+        at: Pointer<'_>,
+        unknown_keys: &mut BTreeMap<String, Arc<ValueOrigin>>,
+    ) {
+        let Value::Object(map) = &self.inner else {
+            return;
+        };
+        if self.is_unset_marker(schema) {
+            return;
+        }
+
+        let mut claimed_keys = None;
+        for (key, value) in map {
+            let child_path = at.join(key);
+            let child_path = Pointer(&child_path);
+            if schema.contains_param(child_path) {
+                // Params are atomic, so we don't check their contents.
+            } else if schema.contains_path_or_descendants(child_path) {
+                value.collect_unknown_keys(schema, child_path, unknown_keys);
+            } else if !claimed_keys
+                .get_or_insert_with(|| schema.claimed_keys(at))
+                .contains(key)
+            {
+                value.collect_leaf_paths(child_path, unknown_keys);
+            }
+        }
+    }
+
+    fn collect_leaf_paths(
+        &self,
+        at: Pointer<'_>,
+        leaf_paths: &mut BTreeMap<String, Arc<ValueOrigin>>,
+    ) {
+        match &self.inner {
+            Value::Object(map) if !map.is_empty() => {
+                for (key, value) in map {
+                    value.collect_leaf_paths(Pointer(&at.join(key)), leaf_paths);
+                }
+            }
+            _ => {
+                leaf_paths.insert(at.0.to_owned(), self.origin.clone());
+            }
+        }
+    }
+
     /// Removes all values that do not correspond to canonical params or their ancestors.
     fn collect_garbage(
         &mut self,
@@ -783,6 +1922,9 @@ impl WithOrigin {
     ) -> usize {
         if schema.contains_canonical_param(at) {
             1
+        } else if prefixes_for_canonical_configs.contains(&at) && self.is_unset_marker(schema) {
+            // Retain unset markers for configs; they will be processed during merging.
+            1
         } else if prefixes_for_canonical_configs.contains(&at) {
             if let Value::Object(map) = &mut self.inner {
                 let mut count = 0;
@@ -812,7 +1954,7 @@ impl WithOrigin {
     /// For example, we have an object param at `test.param` and a source with a value at `test.param_ms`.
     /// This transform will copy this value to `test.param.ms` (i.e., inside the param object), provided that
     /// the source doesn't contain `test.param` or contains an object at this path.
-    #[tracing::instrument(level = "debug", skip_all)]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
     fn nest_object_params_and_sub_configs(&mut self, schema: &ConfigSchema) {
         for (prefix, config_data) in schema.iter_ll() {
             let Some(config_object) = self.get_mut(prefix) else {
@@ -902,7 +2044,7 @@ impl WithOrigin {
     ///
     /// For example, we have an array param at `test.param` and a source with values at `test.param_0`, `test.param_1`, `test.param_2`
     /// (and no `test.param`). This transform will copy these values as a 3-element array at `test.param`.
-    #[tracing::instrument(level = "debug", skip_all)]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
     fn nest_array_params(&mut self, schema: &ConfigSchema) {
         for (prefix, config_data) in schema.iter_ll() {
             let Some(config_object) = self.get_mut(prefix) else {
@@ -971,7 +2113,7 @@ impl WithOrigin {
     /// Nests a flat key–value map into a structured object using the provided `schema`.
     ///
     /// Has complexity `O(kvs.len() * log(n_params))`, which seems about the best possible option if `kvs` is not presorted.
-    #[tracing::instrument(level = "debug", skip_all)]
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all))]
     fn nest_kvs(kvs: Map, schema: &ConfigSchema, source_origin: &Arc<ValueOrigin>) -> Self {
         let mut dest = Self {
             inner: Value::Object(Map::new()),
@@ -1026,6 +2168,61 @@ impl WithOrigin {
         dest
     }
 
+    /// Nests a flat key–value map into a structured object by splitting keys on the custom `delimiter`.
+    fn nest_delimited_kvs(
+        kvs: Map,
+        schema: &ConfigSchema,
+        delimiter: char,
+        source_origin: &Arc<ValueOrigin>,
+    ) -> Self {
+        let mut dest = Self {
+            inner: Value::Object(Map::new()),
+            origin: source_origin.clone(),
+        };
+        let origin = Arc::new(ValueOrigin::Synthetic {
+            source: source_origin.clone(),
+            transform: format!("nesting kv entries split by '{delimiter}'"),
+        });
+
+        for (key, value) in kvs {
+            let mut segments: Vec<_> = key
+                .split(delimiter)
+                .filter(|segment| !segment.is_empty())
+                .collect();
+            let Some(mut last_segment) = segments.pop().map(str::to_owned) else {
+                continue;
+            };
+            let mut parent = segments.join(".");
+
+            // Convert array indexes to the `_${i}` suffixes, which are handled when nesting array params.
+            let is_index = last_segment.bytes().all(|ch| ch.is_ascii_digit());
+            if is_index && !parent.is_empty() {
+                let is_array_param =
+                    schema
+                        .param_expecting(Pointer(&parent))
+                        .is_some_and(|expecting| {
+                            expecting.contains(BasicTypes::ARRAY)
+                                && !expecting.contains(BasicTypes::OBJECT)
+                        });
+                if is_array_param {
+                    let (grandparent, array_name) = Pointer(&parent).split_last().unwrap();
+                    last_segment = format!("{array_name}_{last_segment}");
+                    parent = grandparent.0.to_owned();
+                }
+            }
+
+            tracing::trace!(
+                key,
+                parent,
+                last_segment,
+                "nested delimited key–value entry"
+            );
+            dest.ensure_object(Pointer(&parent), |_| origin.clone())
+                .insert(last_segment, value);
+        }
+        dest
+    }
+
     fn copy_kv_entry(
         &mut self,
         source_origin: &Arc<ValueOrigin>,
@@ -1050,24 +2247,108 @@ impl WithOrigin {
             .insert(field_name, value);
     }
 
+    /// Checks whether this value is an [unset marker](ConfigRepository#unsetting-values).
+    fn is_unset_marker(&self, schema: &ConfigSchema) -> bool {
+        let (Value::Object(map), Some(marker_key)) = (&self.inner, schema.unset_marker_key())
+        else {
+            return false;
+        };
+        map.len() == 1
+            && matches!(
+                map.get(marker_key).map(|val| &val.inner),
+                Some(Value::Bool(true))
+            )
+    }
+
+    fn is_high_priority_fallback(&self) -> bool {
+        self.origin.fallback_priority() == Some(FallbackPriority::High)
+    }
+
     /// Deep merge stopped at params (i.e., params are always merged atomically).
-    fn guided_merge(&mut self, overrides: Self, schema: &ConfigSchema, current_path: Pointer<'_>) {
+    ///
+    /// Values at the paths overridden by [unset markers](ConfigRepository#unsetting-values) are removed,
+    /// with the marker origins recorded in `unset_values`. A record is removed once a value is set at the same path. Origins of overridden values are recorded in `overridden_values`.
+    fn guided_merge(
+        &mut self,
+        overrides: Self,
+        options: &MergeOptions<'_>,
+        current_path: Pointer<'_>,
+        unset_values: &mut BTreeMap<String, Arc<ValueOrigin>>,
+        overridden_values: &mut BTreeMap<String, Vec<Arc<ValueOrigin>>>,
+    ) {
+        let schema = options.schema;
         match (&mut self.inner, overrides.inner) {
             (Value::Object(this), Value::Object(other))
-                if !schema.contains_canonical_param(current_path) =>
+                if !schema.contains_canonical_param(current_path)
+                    || schema.catch_all() == Some(current_path)
+                    || schema.is_rest_param(current_path) =>
             {
+                if options.strategy == MergeStrategy::Replace
+                    && !this.is_empty()
+                    && !options.config_paths.contains(&current_path)
+                {
+                    // Clear the object, but still merge into it so that unset markers are processed.
+                    this.clear();
+                    let prev_origin = mem::replace(&mut self.origin, overrides.origin);
+                    if !Arc::ptr_eq(&prev_origin, &self.origin) {
+                        overridden_values
+                            .entry(current_path.0.to_owned())
+                            .or_default()
+                            .push(prev_origin);
+                    }
+                }
+
                 for (key, value) in other {
-                    if let Some(existing_value) = this.get_mut(&key) {
-                        let child_path = current_path.join(&key);
-                        existing_value.guided_merge(value, schema, Pointer(&child_path));
-                    } else {
-                        this.insert(key, value);
+                    let child_path = current_path.join(&key);
+                    if options.is_file_source
+                        && this
+                            .get(&key)
+                            .is_some_and(WithOrigin::is_high_priority_fallback)
+                    {
+                        // Values from file sources have lower priority than high-priority fallbacks.
+                        overridden_values
+                            .entry(child_path)
+                            .or_default()
+                            .push(value.origin);
+                        continue;
                     }
+                    if value.is_unset_marker(schema) {
+                        this.remove(&key);
+                        unset_values.insert(child_path, value.origin);
+                        continue;
+                    }
+
+                    let existing_value = this.entry(key).or_insert_with(|| WithOrigin {
+                        // Use an empty object as a merge target so that nested unset markers are processed.
+                        inner: if matches!(value.inner, Value::Object(_)) {
+                            Value::Object(Map::new())
+                        } else {
+                            Value::Null
+                        },
+                        origin: value.origin.clone(),
+                    });
+                    existing_value.guided_merge(
+                        value,
+                        options,
+                        Pointer(&child_path),
+                        unset_values,
+                        overridden_values,
+                    );
                 }
             }
             (this, value) => {
                 *this = value;
-                self.origin = overrides.origin;
+                // Only the value at this path is set again; unset records for ancestors (e.g., for the enclosing config)
+                // are retained since other values in them remain unset.
+                unset_values.remove(current_path.0);
+                // Newly inserted values share the origin with the override (see the `or_insert_with()` call above).
+                let prev_origin = mem::replace(&mut self.origin, overrides.origin);
+                if !Arc::ptr_eq(&prev_origin, &self.origin) {
+                    overridden_values
+                        .entry(current_path.0.to_owned())
+                        .or_default()
+                        .push(prev_origin);
+                }
             }
         }
     }
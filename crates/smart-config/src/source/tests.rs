@@ -9,21 +9,273 @@ use secrecy::ExposeSecret;
 
 use super::*;
 use crate::{
-    de,
-    metadata::{AliasOptions, SizeUnit},
+    de, fallback,
+    metadata::{AliasOptions, ParamMetadata, SizeUnit},
     testing,
     testing::MockEnvGuard,
     testonly::{
         extract_env_var_name, extract_json_name, test_config_roundtrip, test_deserialize,
         AliasedConfig, ComposedConfig, CompoundConfig, ConfigWithComplexTypes, ConfigWithFallbacks,
-        ConfigWithNestedValidations, ConfigWithNesting, ConfigWithValidations, DefaultingConfig,
-        EnumConfig, KvTestConfig, NestedConfig, RenamedEnumConfig, SecretConfig, SimpleEnum,
-        ValueCoercingConfig,
+        ConfigWithNestedValidations, ConfigWithNesting, ConfigWithValidations, ConfigWithWarnings,
+        DefaultingConfig, EnumConfig, KvTestConfig, NestedConfig, RenamedEnumConfig, SecretConfig,
+        SimpleEnum, ValueCoercingConfig,
     },
-    value::StrValue,
-    ByteSize, DescribeConfig, SerializerOptions,
+    value::{FileFormat, OriginKind, StrValue},
+    ByteSize, DescribeConfig, ErrorKind, ErrorWithOrigin, MergeStrategy, SerializerOptions,
+    Severity,
 };
 
+#[test]
+fn warnings_for_deprecated_aliases() {
+    let mut schema = ConfigSchema::default();
+    schema
+        .insert(&NestedConfig::DESCRIPTION, "test")
+        .unwrap()
+        .push_alias_with_options(
+            "old",
+            AliasOptions::new()
+                .deprecated_since("0.2.0")
+                .remove_in("0.4.0"),
+        )
+        .unwrap();
+
+    let json = config!("old.renamed": "first", "test.other_int": 3);
+    let repo = ConfigRepository::new(&schema).with(json);
+    let (config, warnings) = repo
+        .single::<NestedConfig>()
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap();
+    assert_eq!(config.simple_enum, SimpleEnum::First);
+    let used_aliases: Vec<_> = repo.used_deprecated_aliases().collect();
+    assert_eq!(used_aliases, [("old.renamed", "test.renamed")]);
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    let warning = &warnings[0];
+    assert_eq!(warning.severity(), Severity::Warning);
+    assert_eq!(warning.path(), "test.renamed");
+    assert_eq!(warning.param().unwrap().name, "renamed");
+    assert_eq!(
+        warning.inner().to_string(),
+        "alias `old.renamed` is deprecated since 0.2.0 and will be removed in 0.4.0; use `test.renamed` instead"
+    );
+    assert_matches!(
+        warning.origin(),
+        ValueOrigin::Path { path, .. } if path == "old.renamed"
+    );
+
+    // Param aliases declared in the config are reported as well.
+    let json = config!("test.experimental.enum": "second");
+    let repo = ConfigRepository::new(&schema).with(json);
+    let (_, warnings) = repo
+        .single::<NestedConfig>()
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap();
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    assert_eq!(
+        warnings[0].inner().to_string(),
+        "alias `test.experimental.enum` is deprecated; use `test.renamed` instead"
+    );
+
+    // Non-deprecated aliases don't produce warnings.
+    let json = config!("test.enum": "second");
+    let repo = ConfigRepository::new(&schema).with(json);
+    let (_, warnings) = repo
+        .single::<NestedConfig>()
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(repo.used_deprecated_aliases().count(), 0);
+
+    // Deprecated aliases overridden by the canonical path in a later source don't produce warnings.
+    let repo = ConfigRepository::new(&schema)
+        .with(config!("old.renamed": "first"))
+        .with(config!("test.renamed": "second"));
+    let (config, warnings) = repo
+        .single::<NestedConfig>()
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap();
+    assert_eq!(config.simple_enum, SimpleEnum::Second);
+    assert!(warnings.is_empty(), "{warnings:?}");
+    assert_eq!(repo.used_deprecated_aliases().count(), 0);
+}
+
+#[test]
+fn conditional_aliases() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    static LEGACY_NAMES: AtomicBool = AtomicBool::new(false);
+
+    let mut schema = ConfigSchema::default();
+    schema
+        .insert(&NestedConfig::DESCRIPTION, "test")
+        .unwrap()
+        .push_alias_with_options(
+            "old",
+            AliasOptions::new().with_condition(|| LEGACY_NAMES.load(Ordering::Relaxed)),
+        )
+        .unwrap();
+
+    let json = config!("old.renamed": "first", "old.other_int": 3);
+    // The condition is evaluated when parsing sources, not when the alias is inserted.
+    let repo = ConfigRepository::new(&schema).with(json.clone());
+    let err = repo.single::<NestedConfig>().unwrap().parse().unwrap_err();
+    let err = err.first();
+    assert_eq!(err.path(), "test.renamed");
+    assert!(err.inner().to_string().contains("missing field"), "{err}");
+    // Keys for inactive aliases are unknown.
+    let unknown_keys: Vec<_> = repo.unknown_keys().map(|(path, _)| path).collect();
+    assert_eq!(unknown_keys, ["old.other_int", "old.renamed"]);
+
+    LEGACY_NAMES.store(true, Ordering::Relaxed);
+    let repo = ConfigRepository::new(&schema).with(json);
+    let config: NestedConfig = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.simple_enum, SimpleEnum::First);
+    assert_eq!(config.other_int, 3);
+    assert_eq!(repo.unknown_keys().count(), 0);
+}
+
+#[test]
+fn tracking_overridden_origins() {
+    let schema = ConfigSchema::new(&NestedConfig::DESCRIPTION, "nested");
+    let base = Json::new(
+        "base.json",
+        serde_json::from_value(serde_json::json!({
+            "nested": { "renamed": "first", "map": { "first": 1 } },
+        }))
+        .unwrap(),
+    );
+    let overrides = Json::new(
+        "overrides.json",
+        serde_json::from_value(serde_json::json!({
+            "nested": { "renamed": "second", "map": { "second": 2 }, "other_int": 5 },
+        }))
+        .unwrap(),
+    );
+    let env = Environment::from_iter("APP_", [("APP_NESTED_RENAMED", "first")]);
+    let repo = ConfigRepository::new(&schema)
+        .with(base)
+        .with(overrides)
+        .with(env);
+
+    let origin = repo.origin_of("nested.renamed").unwrap();
+    assert_matches!(
+        origin.as_ref(),
+        ValueOrigin::Path { source, path } if matches!(source.as_ref(), ValueOrigin::EnvVars) && path == "APP_NESTED_RENAMED"
+    );
+    let overridden: Vec<_> = repo
+        .overridden_origins("nested.renamed")
+        .map(ToString::to_string)
+        .collect();
+    assert_eq!(
+        overridden,
+        [
+            "JSON file 'base.json' -> path 'nested.renamed'",
+            "JSON file 'overrides.json' -> path 'nested.renamed'"
+        ]
+    );
+
+    // Object params are overridden atomically.
+    let overridden: Vec<_> = repo.overridden_origins("nested.map").collect();
+    assert_eq!(overridden.len(), 1);
+    assert_matches!(
+        overridden[0].as_ref(),
+        ValueOrigin::Path { path, .. } if path == "nested.map"
+    );
+    assert_eq!(repo.overridden_origins("nested.map.first").count(), 0);
+
+    // Values provided by a single source are not overridden.
+    assert!(repo.origin_of("nested.other_int").is_some());
+    assert_eq!(repo.overridden_origins("nested.other_int").count(), 0);
+    assert!(repo.origin_of("nested.missing").is_none());
+}
+
+#[test]
+fn parsing_all_configs() {
+    let mut schema = ConfigSchema::new(&NestedConfig::DESCRIPTION, "nested");
+    schema
+        .insert(&DefaultingConfig::DESCRIPTION, "defaults")
+        .unwrap();
+    schema.insert(&KvTestConfig::DESCRIPTION, "kv").unwrap();
+
+    let json = config!("nested.renamed": "bogus", "defaults.int": "??", "kv.nested_int": 5);
+    let repo = ConfigRepository::new(&schema).with(json);
+    let errors = repo.parse_all().unwrap_err();
+    let mut paths: Vec<_> = errors.iter().map(ParseError::path).collect();
+    paths.sort_unstable();
+    assert_eq!(paths, ["defaults.int", "nested.renamed"], "{errors:?}");
+
+    let json = config!("nested.renamed": "second", "kv.nested_int": 5);
+    let repo = ConfigRepository::new(&schema).with(json);
+    let mut configs = repo.parse_all().unwrap();
+    assert_eq!(
+        configs.get::<NestedConfig>("nested").unwrap().simple_enum,
+        SimpleEnum::Second
+    );
+    assert_eq!(configs.get::<DefaultingConfig>("defaults").unwrap().int, 12);
+    // Nested configs are not parsed separately.
+    assert!(configs.get::<DefaultingConfig>("kv.nested").is_none());
+    assert!(configs.get::<NestedConfig>("defaults").is_none());
+
+    let kv_config: KvTestConfig = configs.take("kv").unwrap();
+    assert_eq!(kv_config.nested_int, 5);
+    assert!(configs.take::<KvTestConfig>("kv").is_none());
+}
+
+#[test]
+fn collecting_parse_stats() {
+    let mut schema = ConfigSchema::new(&NestedConfig::DESCRIPTION, "nested");
+    schema
+        .insert(&ConfigWithFallbacks::DESCRIPTION, "")
+        .unwrap();
+    schema
+        .insert(&ConfigWithNesting::DESCRIPTION, "nesting")
+        .unwrap();
+
+    let guard = MockEnvGuard::default();
+    guard.set_env("SMART_CONFIG_INT".into(), "23".into());
+    let json = config!(
+        "nested.renamed": "second",
+        "nesting.value": 3,
+        "nesting.nested.renamed": "first",
+        "nesting.nested.other_int": 5,
+    );
+    let repo = ConfigRepository::new(&schema).with(json);
+    let stats = repo.stats();
+    assert_eq!(stats.configs_parsed, 0);
+    assert_eq!(stats.fallbacks_hit, 1);
+    assert_eq!(stats.params_resolved, 5);
+    assert_eq!(stats.parsing_elapsed, Duration::ZERO);
+
+    let (configs, stats) = repo.parse_all_with_stats();
+    let configs = configs.unwrap();
+    assert_eq!(configs.get::<ConfigWithFallbacks>("").unwrap().int, 23);
+    assert_eq!(stats.configs_parsed, 3);
+    assert_eq!(stats.fallbacks_hit, 1);
+    assert_eq!(stats.params_resolved, 5);
+
+    let repo = ConfigRepository::new(&schema).with(config!("nesting.value": "??"));
+    let (configs, stats) = repo.parse_all_with_stats();
+    configs.unwrap_err();
+    assert_eq!(stats.configs_parsed, 3);
+    assert_eq!(stats.fallbacks_hit, 1);
+    assert_eq!(stats.params_resolved, 2);
+
+    // Overridden fallback values must not be counted.
+    let repo = ConfigRepository::new(&schema).with(config!("int": 5));
+    let config = repo
+        .single::<ConfigWithFallbacks>()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(config.int, 5);
+    let stats = repo.stats();
+    assert_eq!(stats.fallbacks_hit, 0);
+    assert_eq!(stats.params_resolved, 1);
+}
+
 #[test]
 fn parsing_enum_config_with_schema() {
     let schema = ConfigSchema::new(&EnumConfig::DESCRIPTION, "");
@@ -261,6 +513,36 @@ fn parsing_compound_config_with_schema_error() {
     test_parsing_compound_config_with_schema_error(json, "nested_opt");
 }
 
+#[test]
+fn error_with_json_location() {
+    let text = r#"{
+  "renamed": "second",
+  "nested": {
+    "renamed": "what"
+  }
+}"#;
+    let json = Json::parse("config.json", text).unwrap();
+    let schema = ConfigSchema::new(&CompoundConfig::DESCRIPTION, "");
+    let repo = ConfigRepository::new(&schema).with(json);
+    let err = repo
+        .single::<CompoundConfig>()
+        .unwrap()
+        .parse()
+        .unwrap_err();
+    let err = err.first();
+    assert_eq!(err.path(), "nested.renamed");
+    assert_matches!(
+        err.origin(),
+        ValueOrigin::FileLocation {
+            line: 4,
+            column: 16,
+            ..
+        }
+    );
+    let err = err.to_string();
+    assert!(err.contains("(config.json:4:16)"), "{err}");
+}
+
 #[test]
 fn nesting_json() {
     let env = Environment::from_iter(
@@ -563,6 +845,425 @@ fn merging_configs() {
     assert_eq!(sources[1].param_count, 3);
 }
 
+#[test]
+fn unsetting_values() {
+    let base = config!(
+        "value": 123,
+        "merged": "!!",
+        "nested.other_int": 321,
+        "nested.renamed": "first",
+    );
+    let overrides = Json::new(
+        "overrides.json",
+        serde_json::json!({
+            "alias": { "$unset": true },
+            "nested": {
+                "other_int": { "$unset": true },
+            },
+        })
+        .as_object()
+        .unwrap()
+        .clone(),
+    );
+
+    let schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "");
+    let repo = ConfigRepository::new(&schema).with(base).with(overrides);
+    let Value::Object(merged) = &repo.merged().inner else {
+        panic!("unexpected merged value");
+    };
+    assert!(!merged.contains_key("merged"), "{merged:#?}");
+    assert!(!merged.contains_key("alias"), "{merged:#?}");
+    assert_matches!(&merged["nested"].inner, Value::Object(items) if items.len() == 1);
+
+    let unset_values: Vec<_> = repo.unset_values().collect();
+    assert_eq!(unset_values.len(), 2, "{unset_values:#?}");
+    for (path, origin) in unset_values {
+        assert!(path == "merged" || path == "nested.other_int", "{path}");
+        assert_matches!(
+            origin.as_ref(),
+            ValueOrigin::Path { source, .. } if extract_json_name(source) == "overrides.json"
+        );
+    }
+
+    let config: ConfigWithNesting = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.value, 123);
+    assert_eq!(config.merged, "");
+    assert_eq!(config.nested.other_int, 42);
+    assert_eq!(config.nested.simple_enum, SimpleEnum::First);
+}
+
+#[test]
+fn unsetting_entire_config() {
+    let base = config!("value": 123, "nested.other_int": 321);
+    let overrides = Json::new(
+        "overrides.json",
+        serde_json::json!({ "nested": { "$unset": true } })
+            .as_object()
+            .unwrap()
+            .clone(),
+    );
+
+    let schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "");
+    let repo = ConfigRepository::new(&schema)
+        .with(base)
+        .with(overrides.clone());
+    assert!(repo.merged().get(Pointer("nested")).is_none());
+    let unset_paths: Vec<_> = repo.unset_values().map(|(path, _)| path).collect();
+    assert_eq!(unset_paths, ["nested"]);
+
+    // Setting a value in a later source should overwrite the unset marker only for this value.
+    let repo = repo.with(config!("nested.renamed": "second"));
+    let unset_paths: Vec<_> = repo.unset_values().map(|(path, _)| path).collect();
+    assert_eq!(unset_paths, ["nested"]);
+    let config: ConfigWithNesting = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.nested.other_int, 42);
+    assert_eq!(config.nested.simple_enum, SimpleEnum::Second);
+
+    let param_overrides = config!("nested.other_int": serde_json::json!({ "$unset": true }));
+    let repo = repo.with(param_overrides);
+    let unset_paths: Vec<_> = repo.unset_values().map(|(path, _)| path).collect();
+    assert_eq!(unset_paths, ["nested", "nested.other_int"]);
+    let repo = repo.with(config!("nested.other_int": 777));
+    let unset_paths: Vec<_> = repo.unset_values().map(|(path, _)| path).collect();
+    assert_eq!(unset_paths, ["nested"]);
+
+    // Unset markers in the source with no values to unset are no-ops.
+    let repo = ConfigRepository::new(&schema).with(overrides);
+    assert_matches!(&repo.merged().inner, Value::Object(map) if map.is_empty());
+}
+
+#[test]
+fn configuring_unset_marker() {
+    let base = config!("value": 123, "nested.other_int": 321);
+    let overrides = config!(
+        "nested.other_int": serde_json::json!({ "$delete": true }),
+        "nested.map": serde_json::json!({ "$unset": true }),
+    );
+
+    let mut schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "");
+    schema.unset_marker(Some("$delete"));
+    let repo = ConfigRepository::new(&schema)
+        .with(base.clone())
+        .with(overrides.clone());
+    let unset_paths: Vec<_> = repo.unset_values().map(|(path, _)| path).collect();
+    assert_eq!(unset_paths, ["nested.other_int"]);
+    // The default marker is treated as a regular value.
+    let map = repo.merged().get(Pointer("nested.map")).unwrap();
+    assert_matches!(&map.inner, Value::Object(map) if map.contains_key("$unset"));
+
+    schema.unset_marker(None);
+    let repo = ConfigRepository::new(&schema).with(base).with(overrides);
+    assert_eq!(repo.unset_values().count(), 0);
+    let other_int = repo.merged().get(Pointer("nested.other_int")).unwrap();
+    assert_matches!(&other_int.inner, Value::Object(map) if map.contains_key("$delete"));
+}
+
+#[derive(Debug, DescribeConfig, DeserializeConfig)]
+#[config(crate = crate)]
+struct ConfigWithRest {
+    #[config(default, alias = "value_alias")]
+    value: u32,
+    #[config(default_t = Duration::from_secs(1))]
+    timeout: Duration,
+    #[config(nest)]
+    nested: NestedConfig,
+    #[config(rest, default)]
+    extra: HashMap<String, String>,
+}
+
+#[test]
+fn capturing_unclaimed_entries_into_rest_param() {
+    let schema = ConfigSchema::new(&ConfigWithRest::DESCRIPTION, "test");
+    let base = config!(
+        "test.value_alias": 3,
+        "test.timeout_ms": 500,
+        "test.nested.renamed": "first",
+        "test.unknown": "what",
+        "test.other": "?",
+    );
+    let overrides = config!("test.extra.third": "!", "test.unknown": "overridden");
+    let repo = ConfigRepository::new(&schema).with(base).with(overrides);
+
+    let unknown = repo.merged().get(Pointer("test.extra.other")).unwrap();
+    assert_matches!(
+        unknown.origin.as_ref(),
+        ValueOrigin::Path { path, .. } if path == "test.other"
+    );
+    let config: ConfigWithRest = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.value, 3);
+    assert_eq!(config.timeout, Duration::from_millis(500));
+    assert_eq!(config.nested.simple_enum, SimpleEnum::First);
+    assert_eq!(
+        config.extra,
+        HashMap::from([
+            ("unknown".to_owned(), "overridden".to_owned()),
+            ("other".to_owned(), "?".to_owned()),
+            ("third".to_owned(), "!".to_owned()),
+        ])
+    );
+}
+
+#[test]
+fn replacing_rest_param_entries() {
+    let schema = ConfigSchema::new(&ConfigWithRest::DESCRIPTION, "test");
+    let base = config!(
+        "test.value": 3,
+        "test.timeout_ms": 500,
+        "test.nested.renamed": "first",
+        "test.unknown": "what",
+        "test.other": "?",
+    );
+    let overrides = config!("test.value": 5, "test.extra.third": "!");
+    let repo = ConfigRepository::new(&schema)
+        .with(base)
+        .with_merge_strategy(overrides, MergeStrategy::Replace);
+
+    let config: ConfigWithRest = repo.single().unwrap().parse().unwrap();
+    // Config objects are still merged.
+    assert_eq!(config.value, 5);
+    assert_eq!(config.timeout, Duration::from_millis(500));
+    assert_eq!(config.nested.simple_enum, SimpleEnum::First);
+    assert_eq!(
+        config.extra,
+        HashMap::from([("third".to_owned(), "!".to_owned())])
+    );
+    let overridden: Vec<_> = repo.overridden_origins("test.extra").collect();
+    assert_eq!(overridden.len(), 1, "{overridden:?}");
+
+    // Check that the default strategy is unchanged.
+    let overrides = config!("test.extra.third": "!");
+    let repo = ConfigRepository::new(&schema)
+        .with(config!("test.nested.renamed": "first", "test.other": "?"))
+        .with_merge_strategy(overrides, MergeStrategy::DeepMerge);
+    let config: ConfigWithRest = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.extra.len(), 2, "{:?}", config.extra);
+}
+
+#[test]
+fn collecting_unknown_keys() {
+    let mut schema = ConfigSchema::new(&ConfigWithRest::DESCRIPTION, "test");
+    schema.insert(&ConfigWithNesting::DESCRIPTION, "").unwrap();
+    let base = config!(
+        "value": 1,
+        "alias": "!",
+        "nested.renamed": "first",
+        "nested.renamd": "second",
+        // Not claimed since `nested.other_int` doesn't support units.
+        "nested.other_int_ms": 3,
+        "test.value_alias": 3,
+        "test.timeout_ms": 500,
+        "test.unknown": "captured",
+        "test.nested.renamed": "first",
+        "test.nested.what": "?",
+        "unknown.value": 42,
+        "unknown.other": [1, 2],
+    );
+    let overrides = config!(
+        "nested.other_int": serde_json::json!({ "$unset": true }),
+        "test.nested.map": serde_json::json!({ "arbitrary": 1 }),
+    );
+    let mut repo = ConfigRepository::new(&schema).with(base).with(overrides);
+
+    let unknown_keys: Vec<_> = repo.unknown_keys().map(|(path, _)| path).collect();
+    assert_eq!(
+        unknown_keys,
+        [
+            "nested.other_int_ms",
+            "nested.renamd",
+            "test.nested.what",
+            "unknown.other",
+            "unknown.value"
+        ]
+    );
+    let (_, origin) = repo.unknown_keys().nth(1).unwrap();
+    assert_matches!(
+        origin.as_ref(),
+        ValueOrigin::Path { path, .. } if path == "nested.renamd"
+    );
+
+    // Unknown keys are ignored by default.
+    let (_, warnings) = repo
+        .single::<ConfigWithNesting>()
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+
+    repo.deserializer_options().unknown_keys = de::UnknownKeys::Warn;
+    let (_, warnings) = repo
+        .single::<ConfigWithNesting>()
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap();
+    let warning_paths: Vec<_> = warnings.iter().map(ParseError::path).collect();
+    // Other unknown keys belong to nested configs.
+    assert_eq!(warning_paths, ["unknown.other", "unknown.value"]);
+    assert!(warnings
+        .iter()
+        .all(|warning| warning.code() == "unknown_key"));
+
+    repo.deserializer_options().unknown_keys = de::UnknownKeys::Deny;
+    let errors = repo
+        .get::<NestedConfig>("test.nested")
+        .unwrap()
+        .parse()
+        .unwrap_err();
+    assert_eq!(errors.len(), 1);
+    let err = errors.first();
+    assert_eq!(err.path(), "test.nested.what");
+    assert_eq!(err.inner().to_string(), "unknown key `test.nested.what`");
+    repo.single::<ConfigWithRest>().unwrap().parse().unwrap();
+}
+
+#[test]
+fn denying_unknown_keys_per_config() {
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate, deny_unknown)]
+    struct StrictConfig {
+        #[config(default)]
+        value: u32,
+        #[config(default_t = Duration::ZERO)]
+        timeout: Duration,
+    }
+
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct OpenConfig {
+        #[config(default)]
+        flag: bool,
+        #[config(nest)]
+        strict: StrictConfig,
+    }
+
+    let schema = ConfigSchema::new(&OpenConfig::DESCRIPTION, "test");
+    let config_ref = schema.single(&OpenConfig::DESCRIPTION).unwrap();
+    assert!(!config_ref.metadata().deny_unknown);
+    let config_ref = schema.single(&StrictConfig::DESCRIPTION).unwrap();
+    assert!(config_ref.metadata().deny_unknown);
+
+    let json = config!("test.flag": true, "test.what": 1, "test.strict.value": 3);
+    let repo = ConfigRepository::new(&schema).with(json);
+    let config: OpenConfig = repo.single().unwrap().parse().unwrap();
+    assert!(config.flag);
+    assert_eq!(config.strict.value, 3);
+
+    // Only suffixes supported by the param type are claimed.
+    let json = config!("test.strict.timeout_ms": 100, "test.strict.value_ms": 3);
+    let repo = ConfigRepository::new(&schema).with(json);
+    let errors = repo.single::<OpenConfig>().unwrap().parse().unwrap_err();
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors.first().path(), "test.strict.value_ms");
+
+    let json = config!("test.what": 1, "test.strict.value": 3, "test.strict.bogus": 5);
+    let mut repo = ConfigRepository::new(&schema).with(json);
+    let errors = repo.single::<OpenConfig>().unwrap().parse().unwrap_err();
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    let err = errors.first();
+    assert_eq!(err.path(), "test.strict.bogus");
+    assert_eq!(err.code(), "unknown_key");
+    assert_eq!(err.severity(), Severity::Error);
+
+    let errors = repo
+        .get::<StrictConfig>("test.strict")
+        .unwrap()
+        .parse()
+        .unwrap_err();
+    assert_eq!(errors.len(), 1, "{errors:?}");
+    assert_eq!(errors.first().path(), "test.strict.bogus");
+
+    // The global policy still applies to other configs.
+    repo.deserializer_options().unknown_keys = de::UnknownKeys::Warn;
+    let errors = repo.single::<OpenConfig>().unwrap().parse().unwrap_err();
+    let paths_and_severities: Vec<_> = errors
+        .iter()
+        .map(|err| (err.path(), err.severity()))
+        .collect();
+    assert_eq!(
+        paths_and_severities,
+        [
+            ("test.strict.bogus", Severity::Error),
+            ("test.what", Severity::Warning)
+        ]
+    );
+}
+
+#[test]
+fn rest_params_are_checked_on_insertion() {
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct MultipleRestConfig {
+        #[config(rest, default)]
+        first: HashMap<String, String>,
+        #[config(rest, default)]
+        second: HashMap<String, String>,
+    }
+
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct NonObjectRestConfig {
+        #[config(rest, default)]
+        rest: Vec<String>,
+    }
+
+    let mut schema = ConfigSchema::default();
+    let err = schema
+        .insert(&MultipleRestConfig::DESCRIPTION, "test")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("multiple `rest` params"), "{err}");
+    let err = schema
+        .insert(&NonObjectRestConfig::DESCRIPTION, "test")
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("must accept objects"), "{err}");
+}
+
+#[test]
+fn capturing_unknown_keys_into_catch_all_param() {
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct PluginsConfig {
+        #[config(default, with = de::Serde![object])]
+        plugins: HashMap<String, serde_json::Value>,
+    }
+
+    let mut schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "");
+    schema.insert(&PluginsConfig::DESCRIPTION, "").unwrap();
+    schema.set_catch_all("value").unwrap_err();
+    schema.set_catch_all("plugins").unwrap();
+
+    let base = config!(
+        "value": 123,
+        "alias": "!",
+        "nested.renamed": "first",
+        "my_plugin.enabled": true,
+    );
+    let overrides = config!("other_plugin": 42, "plugins.third_plugin": "test");
+    let repo = ConfigRepository::new(&schema).with(base).with(overrides);
+
+    let my_plugin = repo.merged().get(Pointer("plugins.my_plugin")).unwrap();
+    assert_matches!(
+        my_plugin.origin.as_ref(),
+        ValueOrigin::Path { path, .. } if path == "my_plugin"
+    );
+    let config: ConfigWithNesting = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.value, 123);
+    assert_eq!(config.merged, "!");
+
+    let config: PluginsConfig = repo.single().unwrap().parse().unwrap();
+    assert_eq!(
+        config.plugins,
+        HashMap::from([
+            (
+                "my_plugin".to_owned(),
+                serde_json::json!({ "enabled": true })
+            ),
+            ("other_plugin".to_owned(), 42.into()),
+            ("third_plugin".to_owned(), "test".into()),
+        ])
+    );
+}
+
 #[test]
 fn using_aliases_with_object_config() {
     let mut schema = ConfigSchema::default();
@@ -1143,6 +1844,58 @@ fn env_config_with_composed_deserializers() {
     test_config_roundtrip(&config);
 }
 
+#[test]
+fn nesting_kvs_with_custom_delimiter() {
+    let mut schema = ConfigSchema::new(&ComposedConfig::DESCRIPTION, "test");
+    schema.kv_delimiter('/');
+    let env = Environment::from_iter(
+        "",
+        [
+            ("test/durations/0", "1 sec"),
+            ("/test/durations/1", "3 min"),
+            ("test/map_of_sizes/small_size", "3 KiB"),
+            ("test//map_of_ints/5", "10ms"),
+            // Not split on `_`s, so this key isn't recognized.
+            ("test_delimited_durations", "3ms,5sec"),
+        ],
+    );
+    let repo = ConfigRepository::new(&schema).with(env);
+    let durations = repo.merged().get(Pointer("test.durations")).unwrap();
+    assert_matches!(
+        durations.origin.as_ref(),
+        ValueOrigin::Synthetic { transform, .. } if transform.contains("array param")
+    );
+
+    let config: ComposedConfig = repo.single().unwrap().parse().unwrap();
+    assert_eq!(
+        config.durations,
+        [Duration::from_secs(1), Duration::from_secs(3 * 60)]
+    );
+    assert_eq!(
+        config.map_of_sizes,
+        HashMap::from([("small_size".to_owned(), ByteSize(3 << 10))])
+    );
+    assert_eq!(
+        config.map_of_ints,
+        HashMap::from([(5, Duration::from_millis(10))])
+    );
+    assert!(config.delimited_durations.is_empty());
+
+    // Check that the default behavior can be restored.
+    schema.kv_delimiter('_');
+    let env = Environment::from_iter("", [("test_delimited_durations", "3ms,5sec")]);
+    let config: ComposedConfig = ConfigRepository::new(&schema)
+        .with(env)
+        .single()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert_eq!(
+        config.delimited_durations,
+        [Duration::from_millis(3), Duration::from_secs(5)]
+    );
+}
+
 #[test]
 fn nesting_with_composed_deserializers_errors() {
     let mut env = Environment::from_iter("", [("arrays:json", "[[1, 2], [3, 4], [-5, 6]]")]);
@@ -1197,6 +1950,32 @@ fn nesting_with_composed_deserializers_errors() {
     assert!(inner.contains("invalid type"), "{inner}");
 }
 
+#[test]
+fn redacting_secrets() {
+    let schema = ConfigSchema::new(&SecretConfig::DESCRIPTION, "secrets");
+    let json = config!(
+        "secrets.key": "super_secret",
+        "secrets.int": 123,
+        "secrets.opt": (),
+    );
+    let repo = ConfigRepository::new(&schema).with(json);
+    let redacted = repo.merged().redacted(&schema);
+
+    let key = redacted.get(Pointer("secrets.key")).unwrap();
+    assert_matches!(&key.inner, Value::String(StrValue::Plain(s)) if s == "[redacted]");
+    assert_matches!(key.origin.as_ref(), ValueOrigin::Path { path, .. } if path == "secrets.key");
+    let int = redacted.get(Pointer("secrets.int")).unwrap();
+    assert_matches!(&int.inner, Value::String(StrValue::Plain(s)) if s == "[redacted]");
+    // Null values are not redacted since they don't leak anything.
+    let opt = redacted.get(Pointer("secrets.opt")).unwrap();
+    assert_matches!(opt.inner, Value::Null);
+
+    // The original tree is not changed.
+    let key = repo.merged().get(Pointer("secrets.key")).unwrap();
+    assert_eq!(key.inner.as_plain_str(), None);
+    assert_matches!(&key.inner, Value::String(StrValue::Secret(s)) if s.expose_secret() == "super_secret");
+}
+
 #[test]
 fn reading_secrets() {
     let schema = ConfigSchema::new(&SecretConfig::DESCRIPTION, "");
@@ -1308,6 +2087,34 @@ fn aliasing_for_nested_config() {
     assert_eq!(config.nested.str, "!");
 }
 
+#[test]
+fn prioritized_config_sources() {
+    let schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "");
+    let sources = ConfigSources::default()
+        .with_source(10, config!("value": 3))
+        .with_source(
+            0,
+            config!("value": 1, "merged": "low", "nested.renamed": "first"),
+        )
+        .with_source(10, config!("value": 4, "nested.other_int": 5))
+        .with_source(-1, config!("merged": "lowest", "nested.other_int": 1));
+    let repo = ConfigRepository::new(&schema).with_all(sources.clone());
+    assert_eq!(repo.sources().len(), 4);
+    assert_eq!(repo.sources()[0].param_count, 2);
+    assert_eq!(repo.sources()[3].param_count, 2);
+
+    let config: ConfigWithNesting = sources.parse(&schema).unwrap();
+    assert_eq!(config.value, 4);
+    assert_eq!(config.merged, "low");
+    assert_eq!(config.nested.simple_enum, SimpleEnum::First);
+    assert_eq!(config.nested.other_int, 5);
+
+    let err = ConfigSources::default()
+        .parse::<NestedConfig>(&schema)
+        .unwrap_err();
+    assert!(err.to_string().contains("NestedConfig"), "{err:#}");
+}
+
 #[test]
 fn reading_fallbacks() {
     let schema = ConfigSchema::new(&ConfigWithFallbacks::DESCRIPTION, "test");
@@ -1338,6 +2145,14 @@ fn reading_fallbacks() {
     let config: ConfigWithFallbacks = repo.single().unwrap().parse().unwrap();
     assert_eq!(config.int, 23);
     assert_eq!(config.str.unwrap().expose_secret(), "correct horse");
+    assert!(repo.used_fallback("test.int"));
+    assert!(repo.used_fallback("test.str"));
+    assert!(!repo.used_fallback("test.missing"));
+
+    let repo = repo.with(config!("test.int": 5));
+    assert!(!repo.used_fallback("test.int"));
+    assert!(!repo.origin_of("test.int").unwrap().is_fallback());
+    assert!(repo.used_fallback("test.str"));
 
     // Mock env vars are read in `test::*` methods as well
     let mut tester = testing::Tester::default();
@@ -1348,9 +2163,307 @@ fn reading_fallbacks() {
     assert_eq!(config.int, 23);
     assert!(config.str.is_none());
 
-    let config: ConfigWithFallbacks = tester.test(config!("int": 555)).unwrap();
-    assert_eq!(config.int, 555);
-    assert!(config.str.is_none());
+    let config: ConfigWithFallbacks = tester.test(config!("int": 555)).unwrap();
+    assert_eq!(config.int, 555);
+    assert!(config.str.is_none());
+}
+
+#[test]
+fn setting_multiple_mock_env_vars() {
+    let mut tester = testing::Tester::default();
+    tester
+        .set_env("SMART_CONFIG_INT", "23")
+        .set_envs([("SMART_CONFIG_STR", "correct horse")]);
+    let config: ConfigWithFallbacks = tester.test(config!()).unwrap();
+    assert_eq!(config.int, 23);
+    assert_eq!(config.str.unwrap().expose_secret(), "correct horse");
+
+    tester.set_envs(HashMap::from([
+        ("SMART_CONFIG_INT".to_owned(), "42".to_owned()),
+        ("SMART_CONFIG_STR".to_owned(), "unset".to_owned()),
+    ]));
+    let config: ConfigWithFallbacks = tester.test(config!()).unwrap();
+    assert_eq!(config.int, 42);
+    assert!(config.str.is_none());
+}
+
+#[test]
+fn clearing_and_scoping_mock_env_vars() {
+    let mut tester = testing::Tester::<ConfigWithFallbacks>::default();
+    tester.set_env("SMART_CONFIG_INT", "23");
+    {
+        let mut guard = tester.with_env_guard();
+        guard
+            .set_env("SMART_CONFIG_INT", "42")
+            .set_env("SMART_CONFIG_STR", "correct horse");
+        let config: ConfigWithFallbacks = guard.test(config!()).unwrap();
+        assert_eq!(config.int, 42);
+        assert_eq!(config.str.unwrap().expose_secret(), "correct horse");
+    }
+
+    let config: ConfigWithFallbacks = tester.test(config!()).unwrap();
+    assert_eq!(config.int, 23);
+    assert!(config.str.is_none());
+
+    {
+        let mut guard = tester.with_env_guard();
+        guard.clear_env();
+        let config = guard.test(config!()).unwrap();
+        assert_eq!(config.int, 42); // default value
+    }
+    let config: ConfigWithFallbacks = tester.test(config!()).unwrap();
+    assert_eq!(config.int, 23);
+
+    tester.clear_env();
+    let config = tester.test(config!()).unwrap();
+    assert_eq!(config.int, 42);
+}
+
+#[test]
+fn combined_fallback_origin_lists_env_vars() {
+    const VARS: [fallback::Env; 2] = [
+        fallback::Env("SMART_CONFIG_HOST"),
+        fallback::Env("SMART_CONFIG_PORT"),
+    ];
+    const COMBINED: &dyn fallback::FallbackSource =
+        &fallback::Manual::new("$SMART_CONFIG_HOST:$SMART_CONFIG_PORT", || {
+            let host = VARS[0].get_raw()?;
+            let port = VARS[1].get_raw()?;
+            let origin = fallback::Env::combined_origin(&VARS);
+            Some(WithOrigin::new(format!("{host}:{port}").into(), origin))
+        });
+
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct ConfigWithCombinedFallback {
+        #[config(fallback = COMBINED)]
+        addr: std::net::SocketAddr,
+    }
+
+    let err = testing::Tester::<ConfigWithCombinedFallback>::default()
+        .set_env("SMART_CONFIG_HOST", "127.0.0.1")
+        .set_env("SMART_CONFIG_PORT", "what")
+        .test(config!())
+        .unwrap_err();
+    assert_eq!(err.len(), 1);
+    let origin = err.first().origin().to_string();
+    assert!(
+        origin.contains("combined from `SMART_CONFIG_HOST`, `SMART_CONFIG_PORT`"),
+        "{origin}"
+    );
+    assert!(
+        origin.contains("fallback for `ConfigWithCombinedFallback.addr`"),
+        "{origin}"
+    );
+}
+
+#[test]
+fn boxed_manual_fallback_with_runtime_state() {
+    use std::sync::OnceLock;
+
+    use crate::fallback::FallbackSource as _;
+
+    static FIRST_SET_VAR: OnceLock<fallback::ManualBoxed> = OnceLock::new();
+
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct ConfigWithBoxedFallback {
+        #[config(default_t = 42)]
+        #[config(fallback = &fallback::Manual::new("first set var", || {
+            FIRST_SET_VAR.get()?.provide_value()
+        }))]
+        int: u32,
+    }
+
+    // Var names are only known in runtime.
+    let vars: Vec<_> = ["PRIMARY", "SECONDARY"]
+        .into_iter()
+        .map(|name| fallback::Env(format!("SMART_CONFIG_{name}_INT").leak()))
+        .collect();
+    let fallback = fallback::ManualBoxed::new("first set var", move || {
+        vars.iter().find_map(fallback::Env::provide_value)
+    });
+    assert_eq!(fallback.to_string(), "first set var");
+    FIRST_SET_VAR.set(fallback).unwrap();
+
+    let mut tester = testing::Tester::<ConfigWithBoxedFallback>::default();
+    let config = tester.test(config!()).unwrap();
+    assert_eq!(config.int, 42);
+
+    tester.set_env("SMART_CONFIG_SECONDARY_INT", "23");
+    let config = tester.test(config!()).unwrap();
+    assert_eq!(config.int, 23);
+
+    tester.set_env("SMART_CONFIG_PRIMARY_INT", "5");
+    let repo = tester.new_repository();
+    let config: ConfigWithBoxedFallback = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.int, 5);
+    assert!(repo.used_fallback("int"));
+}
+
+#[derive(Debug, DescribeConfig, DeserializeConfig)]
+#[config(crate = crate)]
+struct ConfigWithHighPriorityFallback {
+    #[config(
+        default_t = "info".into(),
+        fallback = &fallback::HighPriority(fallback::Env("SMART_CONFIG_LOG"))
+    )]
+    log: String,
+    #[config(default_t = 42, fallback = &fallback::Env("SMART_CONFIG_INT"))]
+    int: u32,
+}
+
+#[test]
+fn file_contents_fallback() {
+    use crate::fallback::FallbackSource as _;
+
+    let path = std::env::temp_dir().join(format!("smart-config-secret-{}", std::process::id()));
+    std::fs::write(&path, "correct horse\r\n").unwrap();
+    let path: &'static str = Box::leak(path.to_str().unwrap().to_owned().into_boxed_str());
+
+    let fallback = fallback::FileContents(path);
+    let value = fallback.provide_value();
+    std::fs::remove_file(path).unwrap();
+    let value = value.unwrap();
+    assert_matches!(&value.inner, Value::String(StrValue::Plain(s)) if s == "correct horse");
+    assert_matches!(
+        value.origin.as_ref(),
+        ValueOrigin::File { name, format: FileFormat::Text } if name == path
+    );
+    assert_eq!(value.origin.kind(), OriginKind::File);
+
+    // The file is removed now.
+    assert!(fallback.provide_value().is_none());
+}
+
+#[test]
+fn const_fallbacks() {
+    use crate::fallback::FallbackSource as _;
+
+    let value = fallback::Const("info").provide_value().unwrap();
+    assert_matches!(&value.inner, Value::String(StrValue::Plain(s)) if s == "info");
+    assert_matches!(
+        value.origin.as_ref(),
+        ValueOrigin::Synthetic { transform, .. } if transform == "const fallback"
+    );
+
+    let value = fallback::ConstJson(r#"{"port": 3000}"#)
+        .provide_value()
+        .unwrap();
+    let port = value.get(Pointer("port")).unwrap();
+    assert_matches!(&port.inner, Value::Number(num) if *num == 3000_u64.into());
+    assert_matches!(
+        port.origin.as_ref(),
+        ValueOrigin::Path { source, path } if path == "port"
+            && matches!(source.as_ref(), ValueOrigin::Synthetic { transform, .. } if transform == "const fallback")
+    );
+
+    assert!(fallback::ConstJson("{ invalid").provide_value().is_none());
+}
+
+#[test]
+fn getting_resolved_tree_for_config() {
+    let schema = ConfigSchema::new(&ConfigWithFallbacks::DESCRIPTION, "test");
+    let repo = ConfigRepository::new(&schema);
+    assert!(repo
+        .single::<ConfigWithFallbacks>()
+        .unwrap()
+        .resolved_tree()
+        .is_none());
+
+    let guard = MockEnvGuard::default();
+    guard.set_env("SMART_CONFIG_INT".into(), "23".into());
+    let repo = ConfigRepository::new(&schema).with(config!("test.str": "correct horse"));
+    drop(guard);
+    let parser = repo.single::<ConfigWithFallbacks>().unwrap();
+    let tree = parser.resolved_tree().unwrap();
+    let int = tree.get(Pointer("int")).unwrap();
+    assert_matches!(
+        int.origin.as_ref(),
+        ValueOrigin::Fallback { source, param, .. }
+            if param == "ConfigWithFallbacks.int"
+                && matches!(source.as_ref(), ValueOrigin::Path { path, .. } if path == "SMART_CONFIG_INT")
+    );
+    let str = tree.get(Pointer("str")).unwrap();
+    assert_matches!(
+        str.origin.as_ref(),
+        ValueOrigin::Path { path, .. } if path == "test.str"
+    );
+
+    let config = parser.parse().unwrap();
+    assert_eq!(config.int, 23);
+}
+
+#[test]
+fn reading_high_priority_fallbacks() {
+    let schema = ConfigSchema::new(&ConfigWithHighPriorityFallback::DESCRIPTION, "test");
+    let guard = MockEnvGuard::default();
+    guard.set_env("SMART_CONFIG_LOG".into(), "warn".into());
+    guard.set_env("SMART_CONFIG_INT".into(), "23".into());
+    let repo = ConfigRepository::new(&schema);
+    drop(guard);
+
+    assert_eq!(repo.sources().len(), 2);
+    let config: ConfigWithHighPriorityFallback = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.log, "warn");
+    assert_eq!(config.int, 23);
+
+    // File sources do not override high-priority fallbacks, but do override low-priority ones.
+    let repo = repo.with(config!("test.log": "debug", "test.int": 5));
+    let config: ConfigWithHighPriorityFallback = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.log, "warn");
+    assert_eq!(config.int, 5);
+    assert_eq!(repo.sources().len(), 3);
+
+    // Env sources override high-priority fallbacks...
+    let env = Environment::from_iter("APP_", [("APP_TEST_LOG", "trace")]);
+    let repo = repo.with(env);
+    let config: ConfigWithHighPriorityFallback = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.log, "trace");
+
+    // ...and file sources added after env sources override env values as usual.
+    let repo = repo.with(config!("test.log": "error"));
+    let config: ConfigWithHighPriorityFallback = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.log, "error");
+    assert_eq!(repo.sources().len(), 5);
+
+    let log_origin = &repo.merged().get(Pointer("test.log")).unwrap().origin;
+    assert_matches!(log_origin.as_ref(), ValueOrigin::Path { path, .. } if path == "test.log");
+}
+
+#[test]
+fn high_priority_fallbacks_are_not_overridden_by_later_file_sources() {
+    let schema = ConfigSchema::new(&ConfigWithHighPriorityFallback::DESCRIPTION, "test");
+    let guard = MockEnvGuard::default();
+    guard.set_env("SMART_CONFIG_LOG".into(), "warn".into());
+    let repo = ConfigRepository::new(&schema);
+    drop(guard);
+
+    let repo = repo
+        .with(config!("test.log": "debug"))
+        .with(config!("test.log": "error", "test.int": 5));
+    let config: ConfigWithHighPriorityFallback = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.log, "warn");
+    assert_eq!(config.int, 5);
+
+    let log_origin = &repo.merged().get(Pointer("test.log")).unwrap().origin;
+    assert_matches!(
+        log_origin.as_ref(),
+        ValueOrigin::Fallback {
+            priority: FallbackPriority::High,
+            ..
+        }
+    );
+
+    // Non-file sources override the fallback regardless of the preceding file sources.
+    let origin = ValueOrigin::Custom("overrides".to_owned());
+    let serde_json::Value::Object(object) = serde_json::json!({ "test": { "log": "trace" } })
+    else {
+        unreachable!();
+    };
+    let repo = repo.with(Json::with_origin(origin, object));
+    let config: ConfigWithHighPriorityFallback = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.log, "trace");
 }
 
 #[test]
@@ -1365,6 +2478,79 @@ fn reading_env_vars_using_env_source() {
     assert_eq!(config.other_int, 23);
 }
 
+#[derive(Debug, DescribeConfig, DeserializeConfig)]
+#[config(crate = crate)]
+#[config(validate(
+    Self::has_consistent_connections,
+    "`max_connections` must not be less than `min_connections`"
+))]
+struct PoolConfig {
+    #[config(default_t = 1)]
+    min_connections: u32,
+    #[config(default_t = 10)]
+    max_connections: u32,
+}
+
+impl PoolConfig {
+    fn has_consistent_connections(&self) -> bool {
+        self.max_connections >= self.min_connections
+    }
+}
+
+#[test]
+fn cross_param_config_validations() {
+    let schema = ConfigSchema::new(&PoolConfig::DESCRIPTION, "pool");
+    let json = config!("pool.min_connections": 5, "pool.max_connections": 5);
+    let repo = ConfigRepository::new(&schema).with(json);
+    let config: PoolConfig = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.min_connections, 5);
+
+    let json = config!("pool.min_connections": 20);
+    let repo = ConfigRepository::new(&schema).with(json);
+    let err = repo.single::<PoolConfig>().unwrap().parse().unwrap_err();
+    assert_eq!(err.len(), 1, "{err:?}");
+    let err = err.first();
+    assert_eq!(err.path(), "pool");
+    assert!(err.param().is_none());
+    assert_eq!(err.config().ty.id(), any::TypeId::of::<PoolConfig>());
+    assert_eq!(
+        err.validation(),
+        Some("`max_connections` must not be less than `min_connections`")
+    );
+}
+
+#[derive(Debug, DescribeConfig, DeserializeConfig)]
+#[config(crate = crate)]
+struct ConfigWithNotes {
+    #[config(validate(validate_unprivileged_port, "must be unprivileged"))]
+    port: u16,
+}
+
+#[allow(clippy::trivially_copy_pass_by_ref)] // required by the validation fn signature
+fn validate_unprivileged_port(port: &u16) -> Result<(), ErrorWithOrigin> {
+    if *port < 1_024 {
+        let err = ErrorWithOrigin::custom(format!("port {port} is privileged"));
+        return Err(err.with_note("use a port >= 1024, e.g. 8080"));
+    }
+    Ok(())
+}
+
+#[test]
+fn validation_errors_with_notes() {
+    let err = testing::test::<ConfigWithNotes>(config!("port": 80)).unwrap_err();
+    assert_eq!(err.len(), 1, "{err:?}");
+    let first = err.first();
+    assert_eq!(first.note(), Some("use a port >= 1024, e.g. 8080"));
+    let err_string = first.to_string();
+    assert!(
+        err_string.ends_with("port 80 is privileged\n  help: use a port >= 1024, e.g. 8080"),
+        "{err_string}"
+    );
+
+    let err = err.into_iter().next().unwrap().with_note("other note");
+    assert_eq!(err.note(), Some("other note"));
+}
+
 #[test]
 fn config_validations() {
     let json = config!("len": 4, "secret": "test");
@@ -1409,6 +2595,56 @@ fn config_validations() {
     assert!(inner.contains("value is empty"), "{inner}");
 }
 
+#[test]
+fn parsing_is_deterministic() {
+    let json = config!(
+        "renamed": "third",
+        "other_int": "what",
+        "nested.enum": "first",
+        "nested.other_int": -1,
+        "default.map": 5,
+        "nested_opt.map.value": "??",
+        "test.value": 123,
+        "test.alias": "!",
+        "test.nest.renamed": "second",
+    );
+
+    // The schema is rebuilt on each iteration since its internal maps may be randomized.
+    let parse = || {
+        let mut schema = ConfigSchema::new(&CompoundConfig::DESCRIPTION, "");
+        schema
+            .insert(&ConfigWithNesting::DESCRIPTION, "test")
+            .unwrap();
+        let configs: Vec<_> = schema
+            .iter()
+            .map(|config| {
+                let ty = config.metadata().ty.name_in_code();
+                format!("{ty} at {}", config.prefix())
+            })
+            .collect();
+        let repo = ConfigRepository::new(&schema).with(json.clone());
+        let errors = repo
+            .single::<CompoundConfig>()
+            .unwrap()
+            .parse()
+            .unwrap_err();
+        let errors: Vec<_> = errors.iter().map(ToString::to_string).collect();
+        let nested: ConfigWithNesting = repo.single().unwrap().parse().unwrap();
+        (configs, format!("{:?}", repo.merged()), errors, nested)
+    };
+
+    let (configs, merged, errors, nested) = parse();
+    assert!(errors.len() > 1, "{errors:#?}");
+    assert_eq!(nested.value, 123);
+    for _ in 0..5 {
+        let (other_configs, other_merged, other_errors, other_nested) = parse();
+        assert_eq!(other_configs, configs);
+        assert_eq!(other_merged, merged);
+        assert_eq!(other_errors, errors);
+        assert_eq!(other_nested, nested);
+    }
+}
+
 #[test]
 fn multiple_validation_failures() {
     let json = config!("len": 1_666, "secret": "!");
@@ -1425,6 +2661,260 @@ fn multiple_validation_failures() {
     );
 }
 
+#[test]
+fn coalescing_validation_failures() {
+    let json = config!("len": 1_666, "secret": "!");
+    let err = testing::Tester::<ConfigWithValidations>::default()
+        .coalesce_validation_errors()
+        .test(json)
+        .unwrap_err();
+    assert_eq!(err.len(), 1, "{err:?}");
+    let err = err.first();
+    assert_eq!(err.path(), "len");
+    assert_eq!(err.param().unwrap().name, "len");
+    assert_eq!(err.severity(), Severity::Error);
+    assert_eq!(
+        err.validation(),
+        Some("must be in range ..1000, must not be cursed")
+    );
+    // Kind is taken from the first failure.
+    assert_eq!(err.kind(), ErrorKind::InvalidValue);
+    let inner = err.inner().to_string();
+    assert!(inner.contains("expected value in range ..1000"), "{inner}");
+    assert!(inner.contains("; "), "{inner}");
+    assert_matches!(err.origin(), ValueOrigin::Path { path, .. } if path == "len");
+
+    // A single failure is reported as usual.
+    let json = config!("len": 2_000, "secret": "!".repeat(2_000));
+    let err = testing::Tester::<ConfigWithValidations>::default()
+        .coalesce_validation_errors()
+        .test(json)
+        .unwrap_err();
+    assert_eq!(err.len(), 1, "{err:?}");
+    assert_eq!(err.first().validation(), Some("must be in range ..1000"));
+}
+
+#[test]
+fn range_validations_for_numeric_params() {
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct RangeConfig {
+        #[config(default_t = 8080, range = 1024..=65535)]
+        port: u16,
+        #[config(default_t = 0.5, range = 0.0..1.0)]
+        ratio: f64,
+        #[config(range = -10..)]
+        offset: Option<i32>,
+    }
+
+    let params = RangeConfig::DESCRIPTION.params;
+    let ranges: Vec<_> = params.iter().map(ParamMetadata::range).collect();
+    assert_eq!(
+        ranges,
+        [Some("1024..=65535"), Some("0.0..1.0"), Some("-10..")]
+    );
+
+    let config: RangeConfig = testing::test(config!("port": 3000, "ratio": 0.25)).unwrap();
+    assert_eq!(config.port, 3000);
+
+    let json = config!("port": 80, "ratio": 1.5, "offset": -20);
+    let errors = testing::test::<RangeConfig>(json).unwrap_err();
+    assert_eq!(errors.len(), 3);
+    let port_err = errors.iter().find(|err| err.path() == "port").unwrap();
+    assert_eq!(port_err.validation(), Some("range 1024..=65535"));
+    assert_eq!(port_err.code(), "validation_failed");
+    assert_matches!(
+        port_err.origin(),
+        ValueOrigin::Path { path, .. } if path == "port"
+    );
+    let ratio_err = errors.iter().find(|err| err.path() == "ratio").unwrap();
+    assert_eq!(ratio_err.validation(), Some("range 0.0..1.0"));
+    assert_matches!(
+        ratio_err.origin(),
+        ValueOrigin::Path { path, .. } if path == "ratio"
+    );
+    let offset_err = errors.iter().find(|err| err.path() == "offset").unwrap();
+    assert_eq!(offset_err.validation(), Some("range -10.."));
+}
+
+#[test]
+fn non_empty_validations() {
+    #[derive(Debug, DescribeConfig, DeserializeConfig)]
+    #[config(crate = crate)]
+    struct NonEmptyConfig {
+        #[config(non_empty)]
+        name: String,
+        #[config(default_t = vec![1], non_empty)]
+        items: Vec<u32>,
+        #[config(default, non_empty)]
+        labels: HashMap<String, String>,
+    }
+
+    let json = config!("name": "test", "labels.env": "prod");
+    let config: NonEmptyConfig = testing::test(json).unwrap();
+    assert_eq!(config.name, "test");
+    assert_eq!(config.items, [1]);
+
+    let json = config!("name": "", "items": serde_json::json!([]), "labels.env": "prod");
+    let errors = testing::test::<NonEmptyConfig>(json).unwrap_err();
+    let mut error_paths: Vec<_> = errors.iter().map(ParseError::path).collect();
+    error_paths.sort_unstable();
+    assert_eq!(error_paths, ["items", "name"]);
+    for err in errors.iter() {
+        assert_eq!(err.validation(), Some("non_empty"));
+        assert_eq!(err.code(), "validation_failed");
+    }
+
+    let json = config!("name": "test");
+    let errors = testing::test::<NonEmptyConfig>(json).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors.first().path(), "labels");
+}
+
+#[test]
+fn validation_warnings() {
+    let schema = ConfigSchema::new(&ConfigWithWarnings::DESCRIPTION, "");
+    let repo = ConfigRepository::new(&schema).with(config!("timeout_sec": 1_000));
+    let config: ConfigWithWarnings = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.timeout_sec, 1_000);
+
+    let (config, warnings) = repo
+        .single::<ConfigWithWarnings>()
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap();
+    assert_eq!(config.timeout_sec, 1_000);
+    assert_eq!(warnings.len(), 1);
+    let warning = &warnings[0];
+    assert_eq!(warning.severity(), Severity::Warning);
+    assert_eq!(warning.path(), "timeout_sec");
+    assert_eq!(warning.validation(), Some("must be in range ..=100"));
+    assert!(warning.to_string().starts_with("warning "), "{warning}");
+
+    let repo = ConfigRepository::new(&schema).with(config!("timeout_sec": 1_000, "retries": 20));
+    let errors = repo
+        .single::<ConfigWithWarnings>()
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors.has_errors());
+    let error_paths: Vec<_> = errors.errors().map(ParseError::path).collect();
+    assert_eq!(error_paths, ["retries"]);
+    let warning_paths: Vec<_> = errors.warnings().map(ParseError::path).collect();
+    assert_eq!(warning_paths, ["timeout_sec"]);
+}
+
+#[derive(Debug, DescribeConfig, DeserializeConfig)]
+#[config(crate = crate)]
+struct ConfigWithRestrictedOrigins {
+    #[config(default, env_only)]
+    pod_name: String,
+    #[config(default, file_only)]
+    version: u32,
+}
+
+#[test]
+fn env_only_param() {
+    let schema = ConfigSchema::new(&ConfigWithRestrictedOrigins::DESCRIPTION, "");
+    let param = &ConfigWithRestrictedOrigins::DESCRIPTION.params[0];
+    assert_eq!(param.allowed_origin(), Some(OriginKind::EnvVars));
+
+    let env = Environment::from_iter("APP_", [("APP_POD_NAME", "pod-1")]);
+    let repo = ConfigRepository::new(&schema).with(env);
+    let config: ConfigWithRestrictedOrigins = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.pod_name, "pod-1");
+
+    let repo = repo.with(config!("pod_name": "pod-2"));
+    let err = repo
+        .single::<ConfigWithRestrictedOrigins>()
+        .unwrap()
+        .parse()
+        .unwrap_err();
+    assert_eq!(err.len(), 1, "{err:?}");
+    let err = err.first();
+    assert_eq!(err.path(), "pod_name");
+    assert_eq!(err.origin().kind(), OriginKind::File);
+    let inner = err.inner().to_string();
+    assert!(
+        inner.contains("can only be set from env variables, but its value is obtained from files"),
+        "{inner}"
+    );
+}
+
+#[test]
+fn file_only_param() {
+    let schema = ConfigSchema::new(&ConfigWithRestrictedOrigins::DESCRIPTION, "");
+    let param = &ConfigWithRestrictedOrigins::DESCRIPTION.params[1];
+    assert_eq!(param.allowed_origin(), Some(OriginKind::File));
+
+    let repo = ConfigRepository::new(&schema).with(config!("version": 3));
+    let config: ConfigWithRestrictedOrigins = repo.single().unwrap().parse().unwrap();
+    assert_eq!(config.version, 3);
+
+    let env = Environment::from_iter("APP_", [("APP_VERSION", "5")]);
+    let repo = repo.with(env);
+    let err = repo
+        .single::<ConfigWithRestrictedOrigins>()
+        .unwrap()
+        .parse()
+        .unwrap_err();
+    assert_eq!(err.len(), 1, "{err:?}");
+    let err = err.first();
+    assert_eq!(err.path(), "version");
+    assert_matches!(err.origin(), ValueOrigin::Path { path, .. } if path == "APP_VERSION");
+    let inner = err.inner().to_string();
+    assert!(
+        inner.contains("can only be set from files, but its value is obtained from env variables"),
+        "{inner}"
+    );
+}
+
+#[test]
+fn deprecated_config_warning() {
+    let mut schema = ConfigSchema::new(&ConfigWithNesting::DESCRIPTION, "test");
+    let _ = schema
+        .insert(&NestedConfig::DESCRIPTION, "old")
+        .unwrap()
+        .mark_deprecated("use `test.nested` instead");
+    let old_config = schema.get(&NestedConfig::DESCRIPTION, "old").unwrap();
+    assert_eq!(old_config.deprecation(), Some("use `test.nested` instead"));
+    let nested_config = schema
+        .get(&NestedConfig::DESCRIPTION, "test.nested")
+        .unwrap();
+    assert_eq!(nested_config.deprecation(), None);
+
+    let repo = ConfigRepository::new(&schema).with(config!(
+        "test.value": 1,
+        "test.nested.renamed": "first",
+        "old.renamed": "second",
+    ));
+    let (config, warnings) = repo
+        .get::<NestedConfig>("old")
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap();
+    assert_eq!(config.simple_enum, SimpleEnum::Second);
+    assert_eq!(warnings.len(), 1, "{warnings:?}");
+    let warning = &warnings[0];
+    assert_eq!(warning.severity(), Severity::Warning);
+    assert_eq!(warning.path(), "old");
+    assert!(warning.param().is_none());
+    let warning = warning.to_string();
+    assert!(
+        warning.contains("config is deprecated: use `test.nested` instead"),
+        "{warning}"
+    );
+
+    // Values for non-deprecated configs don't produce warnings.
+    let (_, warnings) = repo
+        .get::<NestedConfig>("test.nested")
+        .unwrap()
+        .parse_with_warnings()
+        .unwrap();
+    assert!(warnings.is_empty(), "{warnings:?}");
+}
+
 #[test]
 fn config_nested_validations() {
     let json = config!("nested.len": 4, "nested.secret": "test");
@@ -1608,6 +3098,143 @@ fn coercing_serde_enum_negative_cases() {
     let err = tester.test(json).unwrap_err();
     assert_eq!(err.len(), 1);
     assert_eq!(err.first().path(), "type");
+
+    // Unknown variant field
+    let json = config!("without_fields.string": "!");
+    let err = tester.test(json).unwrap_err();
+    assert_eq!(err.len(), 1);
+    assert_eq!(err.first().path(), "type");
+    assert_eq!(err.first().code(), "missing_field");
+}
+
+fn external_json(value: serde_json::Value) -> Json {
+    Json::new("test.json", serde_json::from_value(value).unwrap())
+}
+
+#[test]
+fn accepting_externally_tagged_enum() {
+    let mut tester = testing::Tester::default();
+    let tester = tester.accept_external_tag().insert::<EnumConfig>("");
+
+    let json = external_json(serde_json::json!({
+        "WithFields": { "str": "!", "set": [1, 2] },
+    }));
+    let config = tester.test(json).unwrap();
+    assert_eq!(
+        config,
+        EnumConfig::WithFields {
+            string: Some("!".to_owned()),
+            flag: true,
+            set: HashSet::from([1, 2]),
+        }
+    );
+
+    // Variant aliases and snake_cased names are recognized as well.
+    for key in ["Fields", "with_fields"] {
+        let json = external_json(serde_json::json!({ key: { "flag": false } }));
+        let config = tester.test(json).unwrap();
+        assert_matches!(config, EnumConfig::WithFields { flag: false, .. });
+    }
+
+    let json = config!("first": serde_json::json!({}));
+    let config = tester.test(json).unwrap();
+    assert_eq!(config, EnumConfig::First);
+
+    // The tag takes precedence over the externally tagged form.
+    let json = external_json(serde_json::json!({
+        "type": "first",
+        "Nested": { "renamed": "second" },
+    }));
+    let config = tester.test(json).unwrap();
+    assert_eq!(config, EnumConfig::First);
+}
+
+#[test]
+fn accepting_externally_tagged_nested_enum() {
+    let mut tester = testing::Tester::default();
+    let tester = tester
+        .accept_external_tag()
+        .insert::<RenamedEnumConfig>("test");
+
+    let json = external_json(serde_json::json!({
+        "test": { "v3": { "WithFields": { "flag": false } } },
+    }));
+    let config = tester.test(json).unwrap();
+    assert_matches!(
+        config,
+        RenamedEnumConfig::V3(EnumConfig::WithFields { flag: false, .. })
+    );
+}
+
+#[test]
+fn unknown_externally_tagged_enum_variant() {
+    let mut tester = testing::Tester::default();
+    let tester = tester.accept_external_tag().insert::<EnumConfig>("");
+
+    let json = external_json(serde_json::json!({ "Unknown": { "flag": false } }));
+    let err = tester.test(json).unwrap_err();
+    assert_eq!(err.len(), 1);
+    let err = err.first();
+    assert_eq!(err.code(), "unknown_tag");
+    assert_eq!(err.path(), "type");
+    let message = err.inner().to_string();
+    assert!(
+        message.contains("unknown variant key `Unknown`"),
+        "{message}"
+    );
+    assert!(
+        message.contains("`first`, `Nested`, `WithFields`"),
+        "{message}"
+    );
+    assert_matches!(
+        err.origin(),
+        ValueOrigin::Path { path, .. } if path == "Unknown"
+    );
+
+    // The error is reported even if the enum has a default variant.
+    let mut tester = testing::Tester::default();
+    let tester = tester
+        .accept_external_tag()
+        .insert::<RenamedEnumConfig>("test");
+    let json = config!("test.v5": serde_json::json!({}));
+    let err = tester.test(json).unwrap_err();
+    assert_eq!(err.len(), 1);
+    assert_eq!(err.first().code(), "unknown_tag");
+    assert_eq!(err.first().path(), "test.version");
+
+    // Without the option, the tag is missing.
+    let json = external_json(serde_json::json!({ "Unknown": { "flag": false } }));
+    let err = testing::test::<EnumConfig>(json).unwrap_err();
+    assert_eq!(err.first().code(), "missing_field");
+}
+
+#[test]
+fn coercing_externally_tagged_serde_enum() {
+    let mut tester = testing::Tester::default();
+    let tester = tester.coerce_serde_enums().insert::<EnumConfig>("");
+
+    let json = config!(
+        "with_fields": serde_json::json!({ "string": "!", "set": [1, 2] }),
+    );
+    let config = tester.test(json).unwrap();
+    assert_eq!(
+        config,
+        EnumConfig::WithFields {
+            string: Some("!".to_owned()),
+            flag: true,
+            set: HashSet::from([1, 2]),
+        }
+    );
+
+    let json = config!("nested": serde_json::json!({ "renamed": "second" }));
+    let config = tester.test(json).unwrap();
+    assert_matches!(
+        config,
+        EnumConfig::Nested(NestedConfig {
+            simple_enum: SimpleEnum::Second,
+            ..
+        })
+    );
 }
 
 #[test]
@@ -1995,3 +3622,80 @@ fn coercing_enum_with_suffixes() {
         }
     );
 }
+
+#[test]
+fn stripping_nulls_from_source() {
+    let json = config!(
+        "test.int": (),
+        "test.url": (),
+        "test.float": 0.5,
+        "array": serde_json::json!([1, null, { "value": null }]),
+    );
+    let mut value = json.inner().clone();
+    value.strip_nulls();
+    assert!(value.pointer("test.int").is_none());
+    assert!(value.pointer("test.url").is_none());
+    assert!(value.pointer("test.float").is_some());
+    // Arrays are not recursed into
+    assert_matches!(value.pointer("array.1").unwrap().inner, Value::Null);
+    assert_matches!(value.pointer("array.2.value").unwrap().inner, Value::Null);
+
+    let schema = ConfigSchema::new(&DefaultingConfig::DESCRIPTION, "test");
+    let repo = ConfigRepository::new(&schema).with(json.clone());
+    let err = repo
+        .single::<DefaultingConfig>()
+        .unwrap()
+        .parse()
+        .unwrap_err();
+    assert_eq!(err.first().path(), "test.int");
+    assert!(err.first().inner().to_string().contains("null"), "{err}");
+
+    let repo = ConfigRepository::new(&schema).with(json.strip_nulls());
+    let config: DefaultingConfig = repo.single().unwrap().parse().unwrap();
+    assert_eq!(
+        config,
+        DefaultingConfig {
+            float: Some(0.5),
+            ..DefaultingConfig::default()
+        }
+    );
+}
+
+#[test]
+fn filtering_fallbacks_by_prefix() {
+    let mut schema = ConfigSchema::new(&ConfigWithFallbacks::DESCRIPTION, "test");
+    schema
+        .insert(&ConfigWithFallbacks::DESCRIPTION, "other")
+        .unwrap();
+
+    let guard = MockEnvGuard::default();
+    guard.set_env("SMART_CONFIG_INT".into(), "23".into());
+    let repo = ConfigRepository::new_with_fallback_filter(&schema, |prefix| prefix != "other");
+    drop(guard);
+
+    assert_eq!(repo.sources().len(), 1);
+    assert_eq!(repo.sources()[0].param_count, 1);
+    assert!(repo.used_fallback("test.int"));
+    assert!(!repo.used_fallback("other.int"));
+
+    let config: ConfigWithFallbacks = repo.get("test").unwrap().parse().unwrap();
+    assert_eq!(config.int, 23);
+    let config: ConfigWithFallbacks = repo.get("other").unwrap().parse().unwrap();
+    assert_eq!(config.int, 42);
+}
+
+#[test]
+fn serialization_ordering() {
+    let json = SerializerOptions::default().serialize(&DefaultingConfig::default());
+    let keys: Vec<_> = json.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["float", "int", "set", "url"]);
+
+    let config = ConfigWithNesting {
+        value: 1,
+        merged: String::new(),
+        nested: NestedConfig::default_nested(),
+    };
+    let json = SerializerOptions::default().serialize(&config);
+    let keys: Vec<_> = json.keys().map(String::as_str).collect();
+    assert_eq!(keys, ["merged", "nested", "value"]);
+}
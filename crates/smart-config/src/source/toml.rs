@@ -0,0 +1,176 @@
+use std::sync::Arc;
+
+use anyhow::Context;
+
+use super::{ConfigSource, Hierarchical};
+use crate::value::{FileFormat, Map, Pointer, Value, ValueOrigin, WithOrigin};
+
+/// TOML-based configuration source.
+///
+/// Both dotted keys and `[table.subtable]` headers are resolved into nested objects during parsing. TOML datetimes
+/// are mapped to [`Value::String`]s in the RFC 3339 format.
+#[derive(Debug, Clone)]
+pub struct Toml {
+    origin: Arc<ValueOrigin>,
+    inner: Map,
+}
+
+impl Toml {
+    /// Creates a source with the specified name and contents.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input doesn't conform to the JSON object model; e.g., if it contains
+    /// non-finite floating-point numbers.
+    pub fn new(filename: &str, table: toml::Table) -> anyhow::Result<Self> {
+        let origin = Arc::new(ValueOrigin::File {
+            name: filename.to_owned(),
+            format: FileFormat::Toml,
+        });
+        let inner = Self::map_value(toml::Value::Table(table), &origin, String::new())?.inner;
+        let Value::Object(inner) = inner else {
+            unreachable!();
+        };
+        Ok(Self { origin, inner })
+    }
+
+    fn map_value(
+        value: toml::Value,
+        file_origin: &Arc<ValueOrigin>,
+        path: String,
+    ) -> anyhow::Result<WithOrigin> {
+        let inner = match value {
+            toml::Value::Boolean(value) => value.into(),
+            toml::Value::Integer(value) => Value::Number(value.into()),
+            toml::Value::Float(value) => Value::Number(
+                serde_json::Number::from_f64(value)
+                    .with_context(|| format!("unsupported number at {path:?}: {value:?}"))?,
+            ),
+            toml::Value::String(value) => value.into(),
+            toml::Value::Datetime(value) => value.to_string().into(),
+            toml::Value::Array(items) => Value::Array(
+                items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, value)| {
+                        let child_path = Pointer(&path).join(&i.to_string());
+                        Self::map_value(value, file_origin, child_path)
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+            toml::Value::Table(items) => Value::Object(
+                items
+                    .into_iter()
+                    .map(|(key, value)| {
+                        let child_path = Pointer(&path).join(&key);
+                        anyhow::Ok((key, Self::map_value(value, file_origin, child_path)?))
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+            ),
+        };
+
+        Ok(WithOrigin {
+            inner,
+            origin: if path.is_empty() {
+                file_origin.clone()
+            } else {
+                Arc::new(ValueOrigin::Path {
+                    source: file_origin.clone(),
+                    path,
+                })
+            },
+        })
+    }
+}
+
+impl ConfigSource for Toml {
+    type Kind = Hierarchical;
+
+    fn into_contents(self) -> WithOrigin<Map> {
+        WithOrigin::new(self.inner, self.origin)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+    use crate::value::StrValue;
+
+    const TOML_CONFIG: &str = r#"
+bool = true
+dotted.key = "value"
+created_at = 1979-05-27T07:32:00Z
+
+[nested]
+int = 123
+string = "what?"
+
+[nested.deeper]
+float = 0.5
+
+[[array]]
+test = 23
+"#;
+
+    fn filename(source: &ValueOrigin) -> &str {
+        if let ValueOrigin::File {
+            name,
+            format: FileFormat::Toml,
+        } = source
+        {
+            name
+        } else {
+            panic!("unexpected source: {source:?}");
+        }
+    }
+
+    #[test]
+    fn creating_toml_config() {
+        let table: toml::Table = toml::from_str(TOML_CONFIG).unwrap();
+        let toml = Toml::new("test.toml", table).unwrap();
+
+        assert_matches!(toml.inner["bool"].inner, Value::Bool(true));
+        assert_matches!(
+            toml.inner["bool"].origin.as_ref(),
+            ValueOrigin::Path { path, source } if filename(source) == "test.toml" && path == "bool"
+        );
+
+        let dotted = toml.inner["dotted"].get(Pointer("key")).unwrap();
+        assert_matches!(&dotted.inner, Value::String(StrValue::Plain(s)) if s == "value");
+        assert_matches!(
+            dotted.origin.as_ref(),
+            ValueOrigin::Path { path, .. } if path == "dotted.key"
+        );
+
+        let str = toml.inner["nested"].get(Pointer("string")).unwrap();
+        assert_matches!(&str.inner, Value::String(StrValue::Plain(s)) if s == "what?");
+        assert_matches!(
+            str.origin.as_ref(),
+            ValueOrigin::Path { path, source } if filename(source) == "test.toml" && path == "nested.string"
+        );
+        let float = toml.inner["nested"].get(Pointer("deeper.float")).unwrap();
+        assert_matches!(&float.inner, Value::Number(num) if num.as_f64() == Some(0.5));
+
+        let inner_int = toml.inner["array"].get(Pointer("0.test")).unwrap();
+        assert_matches!(&inner_int.inner, Value::Number(num) if *num == 23_u64.into());
+        assert_matches!(
+            inner_int.origin.as_ref(),
+            ValueOrigin::Path { path, .. } if path == "array.0.test"
+        );
+
+        assert_matches!(
+            &toml.inner["created_at"].inner,
+            Value::String(StrValue::Plain(s)) if s == "1979-05-27T07:32:00Z"
+        );
+    }
+
+    #[test]
+    fn unsupported_float() {
+        let table: toml::Table = toml::from_str("value = nan").unwrap();
+        let err = Toml::new("test.toml", table).unwrap_err().to_string();
+        assert!(err.contains("unsupported number"), "{err}");
+        assert!(err.contains("value"), "{err}");
+    }
+}
@@ -1,11 +1,17 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use anyhow::Context;
 
-use super::{ConfigSource, Hierarchical};
+use super::{ConfigSource, Hierarchical, Json};
 use crate::value::{FileFormat, Map, Pointer, Value, ValueOrigin, WithOrigin};
 
 /// YAML-based configuration source.
+///
+/// YAML anchors and aliases are resolved during parsing, and YAML nulls are mapped to [`Value::Null`].
+/// Merge keys (`<<`) are not applied automatically; use [`serde_yaml::Value::apply_merge()`] before creating the source
+/// if necessary.
+///
+/// Use [`Self::parse()`] to create a source from YAML text so that value origins record line / column locations.
 #[derive(Debug, Clone)]
 pub struct Yaml {
     origin: Arc<ValueOrigin>,
@@ -32,6 +38,30 @@ impl Yaml {
         Ok(Self { origin, inner })
     }
 
+    /// Parses a source with the specified name from YAML text. Unlike [`Self::new()`], origins of values
+    /// in the created source record line / column locations of values in `text`, so that they are reported
+    /// in [`ParseError`](crate::ParseError)s.
+    ///
+    /// Locations are recorded for values in block mappings and sequences. Values nested in flow collections
+    /// (e.g., `{ port: 8080 }`) or brought in via aliases only record their path.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `text` is not valid YAML, is not a YAML mapping, or doesn't conform to the JSON object model
+    /// (see [`Self::new()`]).
+    pub fn parse(filename: &str, text: &str) -> anyhow::Result<Self> {
+        let value: serde_yaml::Value = serde_yaml::from_str(text)?;
+        let serde_yaml::Value::Mapping(object) = value else {
+            anyhow::bail!("YAML document is not a mapping");
+        };
+        let mut this = Self::new(filename, object)?;
+        let locations = LocationScanner::scan(text);
+        for value in this.inner.values_mut() {
+            Json::attach_locations(value, &locations);
+        }
+        Ok(this)
+    }
+
     fn map_key(key: serde_yaml::Value, parent_path: &str) -> anyhow::Result<String> {
         Ok(match key {
             serde_yaml::Value::String(value) => value,
@@ -55,7 +85,7 @@ impl Yaml {
         })
     }
 
-    fn map_value(
+    pub(crate) fn map_value(
         value: serde_yaml::Value,
         file_origin: &Arc<ValueOrigin>,
         path: String,
@@ -112,6 +142,184 @@ impl ConfigSource for Yaml {
     }
 }
 
+/// Entry in the [`LocationScanner`] stack, corresponding to a block mapping key or a block sequence item.
+#[derive(Debug)]
+struct ScannedEntry {
+    /// 0-based indent of the key / the `-` sequence item marker.
+    indent: usize,
+    /// Path to the value.
+    path: String,
+    is_item: bool,
+    /// Number of block sequence items encountered in the value.
+    item_count: usize,
+}
+
+/// Lenient line-based scanner of YAML text recording start locations of values in block collections.
+/// Assumes that the text is valid YAML; constructs that are not recognized are skipped.
+#[derive(Debug, Default)]
+struct LocationScanner {
+    stack: Vec<ScannedEntry>,
+    /// Indent of a block scalar (`|` or `>`) currently being skipped.
+    block_scalar_indent: Option<usize>,
+    locations: HashMap<String, (usize, usize)>,
+}
+
+impl LocationScanner {
+    fn scan(text: &str) -> HashMap<String, (usize, usize)> {
+        let mut scanner = Self::default();
+        for (i, line) in text.lines().enumerate() {
+            scanner.line(i + 1, line);
+        }
+        scanner.locations
+    }
+
+    fn line(&mut self, line_number: usize, line: &str) {
+        let content = line.trim_start_matches(' ');
+        let indent = line.len() - content.len();
+        if content.is_empty() || content.starts_with('#') {
+            return;
+        }
+        if let Some(scalar_indent) = self.block_scalar_indent {
+            if indent > scalar_indent {
+                return;
+            }
+            self.block_scalar_indent = None;
+        }
+        if indent == 0 && (content.starts_with("---") || content.starts_with("...")) {
+            self.stack.clear();
+            return;
+        }
+
+        let is_item = content == "-" || content.starts_with("- ");
+        while let Some(top) = self.stack.last() {
+            let is_parent =
+                top.indent < indent || (top.indent == indent && is_item && !top.is_item);
+            if is_parent {
+                break;
+            }
+            self.stack.pop();
+        }
+
+        // `indent` is measured in bytes, but since it only covers spaces, it's equal to the char count.
+        let mut column = indent;
+        let mut content = content;
+        let mut is_item = is_item;
+        while is_item {
+            let parent = self.stack.last_mut();
+            let index = parent.as_ref().map_or(0, |parent| parent.item_count);
+            if let Some(parent) = parent {
+                parent.item_count += 1;
+            }
+            let parent_path = self.stack.last().map_or("", |parent| &parent.path);
+            let path = Pointer(parent_path).join(&index.to_string());
+
+            let rest = content[1..].trim_start_matches(' ');
+            let item_column = column + content.len() - rest.len();
+            self.locations
+                .insert(path.clone(), (line_number, item_column + 1));
+            self.stack.push(ScannedEntry {
+                indent: column,
+                path,
+                is_item: true,
+                item_count: 0,
+            });
+
+            column = item_column;
+            content = rest;
+            is_item = content == "-" || content.starts_with("- ");
+        }
+
+        if !content.is_empty() {
+            self.key_value(line_number, column, content);
+        }
+    }
+
+    /// Handles a `key: value` pair starting at the specified column.
+    fn key_value(&mut self, line_number: usize, column: usize, content: &str) {
+        let Some((key, rest)) = Self::split_key(content) else {
+            // Not a key-value pair; e.g., a scalar sequence item or a multiline scalar.
+            let indent = self
+                .stack
+                .last()
+                .filter(|entry| entry.is_item)
+                .map_or(column, |entry| entry.indent);
+            self.block_scalar_indent = Self::block_scalar_start(content, indent);
+            return;
+        };
+
+        let parent_path = self.stack.last().map_or("", |parent| &parent.path);
+        let path = Pointer(parent_path).join(&key);
+        let value = rest.trim_start_matches(' ');
+        let location = if value.is_empty() || value.starts_with('#') {
+            // Block collection value; record the key location.
+            (line_number, column + 1)
+        } else {
+            let value_column = column + content[..content.len() - value.len()].chars().count();
+            (line_number, value_column + 1)
+        };
+        self.locations.insert(path.clone(), location);
+        self.block_scalar_indent = Self::block_scalar_start(value, column);
+        self.stack.push(ScannedEntry {
+            indent: column,
+            path,
+            is_item: false,
+            item_count: 0,
+        });
+    }
+
+    fn block_scalar_start(value: &str, column: usize) -> Option<usize> {
+        let value = value.strip_prefix(['|', '>'])?;
+        let is_header = value
+            .chars()
+            .take_while(|&ch| ch != '#')
+            .all(|ch| ch.is_ascii_digit() || matches!(ch, '+' | '-' | ' '));
+        is_header.then_some(column)
+    }
+
+    /// Splits a `key: value` pair, unquoting the key if necessary.
+    fn split_key(content: &str) -> Option<(String, &str)> {
+        let (key, rest) = match content.chars().next()? {
+            quote @ ('"' | '\'') => {
+                let mut key = String::new();
+                let mut chars = content.char_indices().skip(1);
+                let end = loop {
+                    let (i, ch) = chars.next()?;
+                    if ch == quote {
+                        if quote == '\'' && content[i + 1..].starts_with('\'') {
+                            chars.next();
+                            key.push('\'');
+                            continue;
+                        }
+                        break i + 1;
+                    } else if ch == '\\' && quote == '"' {
+                        key.push(chars.next()?.1);
+                    } else {
+                        key.push(ch);
+                    }
+                };
+                (key, content[end..].trim_start_matches(' '))
+            }
+            '{' | '[' | '&' | '*' | '!' | '|' | '>' | '%' | '@' | '`' => return None,
+            _ => {
+                let colon_pos = content
+                    .match_indices(':')
+                    .map(|(i, _)| i)
+                    .find(|&i| matches!(content.as_bytes().get(i + 1), None | Some(b' ')))?;
+                let key = content[..colon_pos].trim_end_matches(' ');
+                if key.contains(" #") {
+                    return None;
+                }
+                (key.to_owned(), &content[colon_pos..])
+            }
+        };
+        let rest = rest.strip_prefix(':')?;
+        if !rest.is_empty() && !rest.starts_with(' ') {
+            return None;
+        }
+        Some((key, rest))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert_matches::assert_matches;
@@ -165,6 +373,100 @@ array:
         assert_matches!(&inner_int.inner, Value::Number(num) if *num == 23_u64.into());
     }
 
+    #[test]
+    fn yaml_anchors_and_nulls() {
+        let yaml = r"
+defaults: &defaults
+    port: 8080
+    host: ~
+server:
+    <<: *defaults
+    name: test
+copy: *defaults
+        ";
+        let mut yaml: serde_yaml::Value = serde_yaml::from_str(yaml).unwrap();
+        yaml.apply_merge().unwrap();
+        let serde_yaml::Value::Mapping(yaml) = yaml else {
+            unreachable!();
+        };
+        let yaml = Yaml::new("test.yml", yaml).unwrap();
+
+        let copied_port = yaml.inner["copy"].get(Pointer("port")).unwrap();
+        assert_matches!(&copied_port.inner, Value::Number(num) if *num == 8080_u64.into());
+        assert_matches!(
+            copied_port.origin.as_ref(),
+            ValueOrigin::Path { path, .. } if path == "copy.port"
+        );
+        let merged_port = yaml.inner["server"].get(Pointer("port")).unwrap();
+        assert_matches!(&merged_port.inner, Value::Number(num) if *num == 8080_u64.into());
+        let host = yaml.inner["server"].get(Pointer("host")).unwrap();
+        assert_matches!(host.inner, Value::Null);
+    }
+
+    #[test]
+    fn parsing_yaml_with_locations() {
+        let text = r#"# Test config
+bool: true
+nested:
+  string: "what?" # comment
+  'quoted key': 1
+  text: |
+    fake: value
+  array:
+  - 1
+  - test: 23
+    other: [1, 2]
+  -   - nested
+after: null
+"#;
+        let yaml = Yaml::parse("test.yml", text).unwrap();
+
+        let assert_location = |path: &str, expected_line: usize, expected_column: usize| {
+            let (first_segment, rest) = path.split_once('.').unwrap_or((path, ""));
+            let value = yaml.inner[first_segment].get(Pointer(rest)).unwrap();
+            assert_matches!(
+                value.origin.as_ref(),
+                ValueOrigin::FileLocation { path: origin_path, source, line, column }
+                    if origin_path == path
+                        && filename(source) == "test.yml"
+                        && (*line, *column) == (expected_line, expected_column),
+                "{path}"
+            );
+        };
+        assert_location("bool", 2, 7);
+        assert_location("nested", 3, 1);
+        assert_location("nested.string", 4, 11);
+        assert_location("nested.quoted key", 5, 17);
+        assert_location("nested.text", 6, 9);
+        assert_location("nested.array", 8, 3);
+        assert_location("nested.array.0", 9, 5);
+        assert_location("nested.array.1", 10, 5);
+        assert_location("nested.array.1.test", 10, 11);
+        assert_location("nested.array.1.other", 11, 12);
+        assert_location("nested.array.2", 12, 7);
+        assert_location("nested.array.2.0", 12, 9);
+        assert_location("after", 13, 8);
+
+        // Values in flow collections only record their path.
+        let flow_item = yaml.inner["nested"]
+            .get(Pointer("array.1.other.0"))
+            .unwrap();
+        assert_matches!(
+            flow_item.origin.as_ref(),
+            ValueOrigin::Path { path, .. } if path == "nested.array.1.other.0"
+        );
+        assert!(yaml.inner["nested"].get(Pointer("text.fake")).is_none());
+
+        let str = yaml.inner["nested"].get(Pointer("string")).unwrap();
+        assert_eq!(
+            str.origin.to_string(),
+            "YAML file 'test.yml' -> path 'nested.string' (test.yml:4:11)"
+        );
+
+        let err = Yaml::parse("test.yml", "- 1\n- 2").unwrap_err().to_string();
+        assert!(err.contains("not a mapping"), "{err}");
+    }
+
     #[test]
     fn unsupported_key() {
         let yaml = r"
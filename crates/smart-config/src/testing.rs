@@ -1,6 +1,6 @@
 //! Testing tools for configurations.
 
-use std::{any, cell::RefCell, collections::HashMap, marker::PhantomData, mem};
+use std::{any, cell::RefCell, collections::HashMap, marker::PhantomData, mem, ops};
 
 use crate::{
     de::DeserializerOptions,
@@ -42,6 +42,16 @@ impl MockEnvGuard {
     pub(crate) fn set_env(&self, name: String, value: String) {
         MOCK_ENV_VARS.with_borrow_mut(|vars| vars.insert(name, value));
     }
+
+    #[allow(clippy::unused_self)] // used for better type safety
+    fn replace_all(&self, vars: HashMap<String, String>) -> HashMap<String, String> {
+        MOCK_ENV_VARS.replace(vars)
+    }
+
+    #[allow(clippy::unused_self)] // used for better type safety
+    fn snapshot(&self) -> HashMap<String, String> {
+        MOCK_ENV_VARS.with_borrow(Clone::clone)
+    }
 }
 
 impl Drop for MockEnvGuard {
@@ -500,19 +510,44 @@ impl Tester<'_, ()> {
     }
 }
 
-impl<C> Tester<'_, C> {
+impl<'a, C> Tester<'a, C> {
     /// Enables coercion of enum variant names.
     pub fn coerce_variant_names(&mut self) -> &mut Self {
         self.data.as_mut().de_options.coerce_variant_names = true;
         self
     }
 
+    /// Enables [lenient coercion](crate::de::DeserializerOptions::coerce_bool_strings) of strings to Boolean values.
+    pub fn coerce_bool_strings(&mut self) -> &mut Self {
+        self.data.as_mut().de_options.coerce_bool_strings = true;
+        self
+    }
+
+    /// [Disables coercion](crate::de::DeserializerOptions::deny_numeric_strings) of numeric strings to numbers
+    /// for values from JSON, YAML and TOML sources.
+    pub fn deny_numeric_strings(&mut self) -> &mut Self {
+        self.data.as_mut().de_options.deny_numeric_strings = true;
+        self
+    }
+
+    /// Enables [coalescing validation errors](crate::de::DeserializerOptions::coalesce_validation_errors) for params.
+    pub fn coalesce_validation_errors(&mut self) -> &mut Self {
+        self.data.as_mut().de_options.coalesce_validation_errors = true;
+        self
+    }
+
     /// Enables coercion of serde-style enums.
     pub fn coerce_serde_enums(&mut self) -> &mut Self {
         self.data.as_mut().schema.coerce_serde_enums(true);
         self
     }
 
+    /// Enables accepting [externally tagged](ConfigSchema::accept_external_tag()) enum configs.
+    pub fn accept_external_tag(&mut self) -> &mut Self {
+        self.data.as_mut().schema.accept_external_tag(true);
+        self
+    }
+
     /// Sets mock environment variables that will be recognized by [`Environment`](crate::Environment)
     /// and [`Env`](crate::fallback::Env) fallbacks.
     ///
@@ -525,6 +560,63 @@ impl<C> Tester<'_, C> {
         self
     }
 
+    /// Sets multiple mock environment variables at once. Previously set variables are retained
+    /// (unless overwritten by `vars`). See [`Self::set_env()`] for details.
+    pub fn set_envs<K, V>(&mut self, vars: impl IntoIterator<Item = (K, V)>) -> &mut Self
+    where
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (var_name, value) in vars {
+            self.set_env(var_name, value);
+        }
+        self
+    }
+
+    /// Removes all mock environment variables set previously via [`Self::set_env()`] or [`Self::set_envs()`].
+    ///
+    /// Like mock variables themselves, this only affects the current thread.
+    pub fn clear_env(&mut self) -> &mut Self {
+        self.data.as_mut().env_guard.replace_all(HashMap::new());
+        self
+    }
+
+    /// Creates a scoped guard for mock environment variables. The guard provides access to the tester (e.g., to set
+    /// additional env vars); once the guard is dropped, mock env vars are restored to the state
+    /// at the guard creation.
+    ///
+    /// Like mock variables themselves, the guard only affects the current thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{testing::Tester, DescribeConfig, DeserializeConfig};
+    /// #[derive(DescribeConfig, DeserializeConfig)]
+    /// struct TestConfig {
+    ///     #[config(default_t = 1, fallback = &smart_config::fallback::Env("APP_PORT"))]
+    ///     port: u16,
+    /// }
+    ///
+    /// let mut tester = Tester::<TestConfig>::default();
+    /// {
+    ///     let mut guard = tester.with_env_guard();
+    ///     guard.set_env("APP_PORT", "8080");
+    ///     let config = guard.test(smart_config::config!())?;
+    ///     assert_eq!(config.port, 8080);
+    /// }
+    /// // Env vars are restored once the guard is dropped.
+    /// let config = tester.test(smart_config::config!())?;
+    /// assert_eq!(config.port, 1);
+    /// # anyhow::Ok(())
+    /// ```
+    pub fn with_env_guard(&mut self) -> EnvGuard<'_, 'a, C> {
+        let saved_vars = self.data.as_ref().env_guard.snapshot();
+        EnvGuard {
+            tester: self,
+            saved_vars,
+        }
+    }
+
     /// Creates an empty repository based on the tester schema and the deserialization options.
     pub fn new_repository(&self) -> ConfigRepository<'_> {
         let data = self.data.as_ref();
@@ -618,6 +710,36 @@ impl<C: DeserializeConfig + VisitConfig> Tester<'_, C> {
     }
 }
 
+/// Scoped guard for mock environment variables returned by [`Tester::with_env_guard()`]. Dereferences
+/// to the underlying [`Tester`]. Restores mock env vars to their state at the guard creation on drop.
+#[derive(Debug)]
+#[must_use = "Mock env vars are restored when the guard is dropped"]
+pub struct EnvGuard<'t, 'a, C> {
+    tester: &'t mut Tester<'a, C>,
+    saved_vars: HashMap<String, String>,
+}
+
+impl<'a, C> ops::Deref for EnvGuard<'_, 'a, C> {
+    type Target = Tester<'a, C>;
+
+    fn deref(&self) -> &Self::Target {
+        self.tester
+    }
+}
+
+impl<C> ops::DerefMut for EnvGuard<'_, '_, C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.tester
+    }
+}
+
+impl<C> Drop for EnvGuard<'_, '_, C> {
+    fn drop(&mut self) {
+        let saved_vars = mem::take(&mut self.saved_vars);
+        self.tester.data.as_ref().env_guard.replace_all(saved_vars);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
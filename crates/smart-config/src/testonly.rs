@@ -22,7 +22,7 @@ use crate::{
     fallback::FallbackSource,
     metadata::{BasicTypes, ParamMetadata, SizeUnit, TimeUnit},
     testing,
-    validation::NotEmpty,
+    validation::{NotEmpty, Warn},
     value::{FileFormat, Value, ValueOrigin, WithOrigin},
     ByteSize, ConfigSource, DescribeConfig, DeserializeConfig, Environment, ErrorWithOrigin,
     ExampleConfig, Json, ParseErrors, SerializerOptions,
@@ -167,6 +167,15 @@ pub(crate) struct DefaultingConfig {
     pub set: HashSet<SimpleEnum>,
 }
 
+#[derive(Debug, PartialEq, DescribeConfig, DeserializeConfig)]
+#[config(crate = crate, derive(Default))]
+pub(crate) struct TristateConfig {
+    #[config(default)]
+    pub port: de::Maybe<u16>,
+    #[config(default)]
+    pub timeout: de::Maybe<Duration>,
+}
+
 #[derive(Debug, PartialEq, DescribeConfig, DeserializeConfig)]
 #[config(crate = crate)]
 pub(crate) struct KvTestConfig {
@@ -366,6 +375,15 @@ pub(crate) struct ConfigWithNestedValidations {
     pub nested: ConfigWithValidations,
 }
 
+#[derive(Debug, DescribeConfig, DeserializeConfig)]
+#[config(crate = crate)]
+pub(crate) struct ConfigWithWarnings {
+    #[config(validate(Warn(..=100)))]
+    pub timeout_sec: u64,
+    #[config(default_t = 3, validate(..10))]
+    pub retries: u32,
+}
+
 pub(crate) fn wrap_into_value(env: Environment) -> WithOrigin {
     WithOrigin {
         inner: Value::Object(env.into_contents().inner),
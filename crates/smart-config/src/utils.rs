@@ -2,6 +2,80 @@
 
 use crate::metadata::ConfigMetadata;
 
+#[cfg(feature = "tracing")]
+pub(crate) use ::tracing;
+
+/// No-op replacement for the subset of the `tracing` API used in the crate. Used if the `tracing` feature is off.
+#[cfg(not(feature = "tracing"))]
+pub(crate) mod tracing {
+    /// Consumes fields and message args of a tracing macro, so that they are considered used
+    /// if the `tracing` feature is disabled.
+    macro_rules! consume {
+        () => {};
+        ($name:ident = ? $value:expr $(, $($rest:tt)*)?) => {
+            let _ = &$value;
+            $crate::utils::tracing::consume!($($($rest)*)?);
+        };
+        ($name:ident = % $value:expr $(, $($rest:tt)*)?) => {
+            let _ = &$value;
+            $crate::utils::tracing::consume!($($($rest)*)?);
+        };
+        ($name:ident = $value:expr $(, $($rest:tt)*)?) => {
+            let _ = &$value;
+            $crate::utils::tracing::consume!($($($rest)*)?);
+        };
+        (? $value:expr $(, $($rest:tt)*)?) => {
+            let _ = &$value;
+            $crate::utils::tracing::consume!($($($rest)*)?);
+        };
+        (% $value:expr $(, $($rest:tt)*)?) => {
+            let _ = &$value;
+            $crate::utils::tracing::consume!($($($rest)*)?);
+        };
+        ($message:literal $(, $arg:expr)* $(,)?) => {
+            let _ = ::core::format_args!($message $(, $arg)*);
+        };
+        ($name:ident $(, $($rest:tt)*)?) => {
+            let _ = &$name;
+            $crate::utils::tracing::consume!($($($rest)*)?);
+        };
+    }
+
+    macro_rules! event {
+        ($($tt:tt)*) => {{
+            $crate::utils::tracing::consume!($($tt)*);
+        }};
+    }
+
+    macro_rules! span {
+        ($name:literal $(, $($fields:tt)*)?) => {{
+            $crate::utils::tracing::consume!($($($fields)*)?);
+            $crate::utils::tracing::Span
+        }};
+    }
+
+    pub(crate) use consume;
+    pub(crate) use {event as debug, event as info, event as trace, event as warn};
+    pub(crate) use {span as info_span, span as trace_span};
+
+    #[derive(Debug)]
+    pub(crate) struct Span;
+
+    impl Span {
+        pub(crate) fn current() -> Self {
+            Self
+        }
+
+        pub(crate) fn entered(self) -> Self {
+            self
+        }
+
+        pub(crate) fn record<V>(&self, _field: &str, _value: V) -> &Self {
+            self
+        }
+    }
+}
+
 /// Sealed trait marker. Intentionally not re-exported publicly.
 pub trait Sealed {}
 
@@ -193,6 +267,24 @@ impl<'a> EnumVariant<'a> {
     }
 }
 
+/// Computes the Levenshtein (edit) distance between two strings.
+pub(crate) fn levenshtein_distance(lhs: &str, rhs: &str) -> usize {
+    let rhs: Vec<char> = rhs.chars().collect();
+    let mut prev_row: Vec<usize> = (0..=rhs.len()).collect();
+    let mut row = vec![0; rhs.len() + 1];
+    for (i, lhs_ch) in lhs.chars().enumerate() {
+        row[0] = i + 1;
+        for (j, &rhs_ch) in rhs.iter().enumerate() {
+            let substitution_cost = usize::from(lhs_ch != rhs_ch);
+            row[j + 1] = (prev_row[j] + substitution_cost)
+                .min(prev_row[j + 1] + 1)
+                .min(row[j] + 1);
+        }
+        std::mem::swap(&mut row, &mut prev_row);
+    }
+    prev_row[rhs.len()]
+}
+
 pub(crate) type JsonObject = serde_json::Map<String, serde_json::Value>;
 
 pub(crate) fn merge_json(
@@ -244,6 +336,16 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn computing_levenshtein_distance() {
+        assert_eq!(levenshtein_distance("", ""), 0);
+        assert_eq!(levenshtein_distance("port", "port"), 0);
+        assert_eq!(levenshtein_distance("port", ""), 4);
+        assert_eq!(levenshtein_distance("port", "prot"), 2);
+        assert_eq!(levenshtein_distance("port", "ports"), 1);
+        assert_eq!(levenshtein_distance("kitten", "sitting"), 3);
+    }
+
     #[test]
     fn detecting_cases() {
         let variant = EnumVariant::new("snake_case10_12").unwrap();
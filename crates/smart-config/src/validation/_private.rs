@@ -1,6 +1,6 @@
 use std::{any, fmt, marker::PhantomData};
 
-use crate::{validation::Validate, ErrorWithOrigin};
+use crate::{validation::Validate, ErrorWithOrigin, Severity};
 
 /// Tag for `WithDescription` wrapping a type that already implements a validation.
 #[derive(Debug)]
@@ -43,6 +43,10 @@ impl<T, V: Validate<T>> Validate<T> for WithDescription<V, Delegated> {
     fn validate(&self, target: &T) -> Result<(), ErrorWithOrigin> {
         self.inner.validate(target)
     }
+
+    fn severity(&self) -> Severity {
+        self.inner.severity()
+    }
 }
 
 impl<T, F> Validate<T> for WithDescription<F, BoolPredicate>
@@ -101,4 +105,8 @@ impl<T: 'static, V: Validate<T>> Validate<dyn any::Any> for ErasedValidation<T,
             .expect("Internal error: unexpected target type");
         self.validation.validate(target)
     }
+
+    fn severity(&self) -> Severity {
+        self.validation.severity()
+    }
 }
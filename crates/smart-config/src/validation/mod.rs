@@ -98,12 +98,11 @@
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt, ops,
-    sync::Arc,
 };
 
 use serde::de;
 
-use crate::ErrorWithOrigin;
+use crate::{ErrorWithOrigin, Severity};
 
 #[doc(hidden)] // only used in proc macros
 pub mod _private;
@@ -130,6 +129,14 @@ pub trait Validate<T: ?Sized>: 'static + Send + Sync {
     ///
     /// Should return an error if validation fails.
     fn validate(&self, target: &T) -> Result<(), ErrorWithOrigin>;
+
+    /// Returns the severity of errors reported by this validation. If the severity is [`Severity::Warning`],
+    /// validation errors are reported, but do not prevent the param / config from being parsed.
+    ///
+    /// The default implementation returns [`Severity::Error`].
+    fn severity(&self) -> Severity {
+        Severity::Error
+    }
 }
 
 impl<T: 'static + ?Sized> fmt::Debug for dyn Validate<T> {
@@ -156,6 +163,10 @@ impl<T: ?Sized, V: Validate<T> + ?Sized> Validate<T> for &'static V {
     fn validate(&self, target: &T) -> Result<(), ErrorWithOrigin> {
         (**self).validate(target)
     }
+
+    fn severity(&self) -> Severity {
+        (**self).severity()
+    }
 }
 
 macro_rules! impl_validate_for_range {
@@ -170,11 +181,10 @@ macro_rules! impl_validate_for_range {
 
             fn validate(&self, target: &T) -> Result<(), ErrorWithOrigin> {
                 if !self.contains(target) {
-                    let err = de::Error::invalid_value(
+                    return Err(de::Error::invalid_value(
                         de::Unexpected::Other(&format!("{target:?}")),
                         &format!("value in range {self:?}").as_str(),
-                    );
-                    return Err(ErrorWithOrigin::json(err, Arc::default()));
+                    ));
                 }
                 Ok(())
             }
@@ -188,6 +198,43 @@ impl_validate_for_range!(ops::RangeTo<T>);
 impl_validate_for_range!(ops::RangeToInclusive<T>);
 impl_validate_for_range!(ops::RangeFrom<T>);
 
+/// Wrapper downgrading errors reported by the wrapped validation to [warnings](Severity::Warning).
+///
+/// # Examples
+///
+/// ```
+/// use smart_config::{config, validation, ConfigRepository, ConfigSchema, DescribeConfig, DeserializeConfig};
+///
+/// #[derive(DescribeConfig, DeserializeConfig)]
+/// struct TestConfig {
+///     #[config(validate(validation::Warn(..=100)))]
+///     timeout_sec: u64,
+/// }
+///
+/// let schema = ConfigSchema::new(&TestConfig::DESCRIPTION, "");
+/// let repo = ConfigRepository::new(&schema).with(config!("timeout_sec": 1_000));
+/// let (config, warnings) = repo.single::<TestConfig>()?.parse_with_warnings()?;
+/// assert_eq!(config.timeout_sec, 1_000);
+/// assert_eq!(warnings.len(), 1);
+/// # anyhow::Ok(())
+/// ```
+#[derive(Debug)]
+pub struct Warn<V>(pub V);
+
+impl<T: ?Sized, V: Validate<T>> Validate<T> for Warn<V> {
+    fn describe(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.describe(formatter)
+    }
+
+    fn validate(&self, target: &T) -> Result<(), ErrorWithOrigin> {
+        self.0.validate(target)
+    }
+
+    fn severity(&self) -> Severity {
+        Severity::Warning
+    }
+}
+
 /// Validates that a string or a data collection (e.g., [`Vec`]) is not empty.
 #[derive(Debug)]
 pub struct NotEmpty;
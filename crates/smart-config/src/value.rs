@@ -1,10 +1,10 @@
 //! Enriched JSON object model that allows to associate values with origins.
 
-use std::{collections::BTreeMap, fmt, iter, mem, sync::Arc};
+use std::{collections::BTreeMap, fmt, iter, mem, path::Path, sync::Arc};
 
 pub use secrecy::{ExposeSecret, SecretString};
 
-use crate::metadata::BasicTypes;
+use crate::{fallback::FallbackPriority, metadata::BasicTypes};
 
 /// Supported file formats.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -14,8 +14,12 @@ pub enum FileFormat {
     Json,
     /// YAML file.
     Yaml,
+    /// TOML file.
+    Toml,
     /// `.env` file.
     Dotenv,
+    /// Plain text file containing a single value, e.g. a secret mounted as a file.
+    Text,
 }
 
 impl fmt::Display for FileFormat {
@@ -23,7 +27,9 @@ impl fmt::Display for FileFormat {
         formatter.write_str(match self {
             Self::Json => "JSON",
             Self::Yaml => "YAML",
+            Self::Toml => "TOML",
             Self::Dotenv => ".env",
+            Self::Text => "text",
         })
     }
 }
@@ -53,6 +59,26 @@ pub enum ValueOrigin {
         /// Dot-separated path in the source, like `api.http.port`.
         path: String,
     },
+    /// Path from a structured source together with the location of the value in the source text.
+    FileLocation {
+        /// Source of structured data, e.g. a JSON file.
+        source: Arc<Self>,
+        /// Dot-separated path in the source, like `api.http.port`.
+        path: String,
+        /// 1-based line number of the value start.
+        line: usize,
+        /// 1-based column number of the value start (in chars).
+        column: usize,
+    },
+    /// Value provided by a [fallback](crate::fallback) for a config param.
+    Fallback {
+        /// Original value source, e.g. an env variable.
+        source: Arc<Self>,
+        /// Param the fallback was applied to, in the `Config.field` form (e.g., `TestConfig.log_directives`).
+        param: String,
+        /// Priority of the fallback.
+        priority: FallbackPriority,
+    },
     /// Synthetic value.
     Synthetic {
         /// Original value source.
@@ -60,6 +86,11 @@ pub enum ValueOrigin {
         /// Human-readable description of the transform.
         transform: String,
     },
+    /// Custom origin, e.g. a remote configuration endpoint.
+    Custom(
+        /// Human-readable description of the origin.
+        String,
+    ),
 }
 
 impl fmt::Display for ValueOrigin {
@@ -78,13 +109,248 @@ impl fmt::Display for ValueOrigin {
                     write!(formatter, "{source} -> path '{path}'")
                 }
             }
+            Self::FileLocation {
+                source,
+                path,
+                line,
+                column,
+            } => {
+                write!(formatter, "{source} -> path '{path}' ")?;
+                if let Self::File { name, .. } = source.as_ref() {
+                    write!(formatter, "({name}:{line}:{column})")
+                } else {
+                    write!(formatter, "(line {line}, column {column})")
+                }
+            }
+            Self::Fallback { source, param, .. } => {
+                write!(formatter, "{source} -> fallback for `{param}`")
+            }
             Self::Synthetic { source, transform } => {
                 write!(formatter, "{source} -> {transform}")
             }
+            Self::Custom(description) => formatter.write_str(description),
         }
     }
 }
 
+impl ValueOrigin {
+    /// Makes file paths in this origin relative to the `base` directory. Returns the original origin if it
+    /// doesn't reference files within `base`.
+    pub(crate) fn relative_to(self: &Arc<Self>, base: &Path) -> Arc<Self> {
+        let relative = match self.as_ref() {
+            Self::File { name, format } => {
+                let Ok(relative_name) = Path::new(name).strip_prefix(base) else {
+                    return self.clone();
+                };
+                Self::File {
+                    name: relative_name.display().to_string(),
+                    format: *format,
+                }
+            }
+            Self::Path { source, path } => {
+                let relative_source = source.relative_to(base);
+                if Arc::ptr_eq(&relative_source, source) {
+                    return self.clone();
+                }
+                Self::Path {
+                    source: relative_source,
+                    path: path.clone(),
+                }
+            }
+            Self::FileLocation {
+                source,
+                path,
+                line,
+                column,
+            } => {
+                let relative_source = source.relative_to(base);
+                if Arc::ptr_eq(&relative_source, source) {
+                    return self.clone();
+                }
+                Self::FileLocation {
+                    source: relative_source,
+                    path: path.clone(),
+                    line: *line,
+                    column: *column,
+                }
+            }
+            Self::Synthetic { source, transform } => {
+                let relative_source = source.relative_to(base);
+                if Arc::ptr_eq(&relative_source, source) {
+                    return self.clone();
+                }
+                Self::Synthetic {
+                    source: relative_source,
+                    transform: transform.clone(),
+                }
+            }
+            Self::Fallback {
+                source,
+                param,
+                priority,
+            } => {
+                let relative_source = source.relative_to(base);
+                if Arc::ptr_eq(&relative_source, source) {
+                    return self.clone();
+                }
+                Self::Fallback {
+                    source: relative_source,
+                    param: param.clone(),
+                    priority: *priority,
+                }
+            }
+            Self::Unknown | Self::EnvVars | Self::Fallbacks | Self::Custom(_) => {
+                return self.clone();
+            }
+        };
+        Arc::new(relative)
+    }
+
+    /// Unwinds this origin into a human-readable provenance chain, starting from the original source
+    /// and followed by paths / transforms applied to it. For example, a value obtained from a `RUST_LOG` env var
+    /// and then transformed may have the chain `["env variable 'RUST_LOG'", "fallback transform"]`.
+    /// The chain always has at least one entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use smart_config::value::{FileFormat, ValueOrigin};
+    /// let file = Arc::new(ValueOrigin::File {
+    ///     name: "config.yml".into(),
+    ///     format: FileFormat::Yaml,
+    /// });
+    /// let path = Arc::new(ValueOrigin::Path {
+    ///     source: file,
+    ///     path: "api.port".into(),
+    /// });
+    /// let origin = ValueOrigin::Synthetic {
+    ///     source: path,
+    ///     transform: "parsed from string".into(),
+    /// };
+    /// assert_eq!(
+    ///     origin.chain(),
+    ///     ["YAML file 'config.yml'", "path 'api.port'", "parsed from string"]
+    /// );
+    /// ```
+    pub fn chain(&self) -> Vec<String> {
+        let mut chain = vec![];
+        self.push_chain(&mut chain);
+        if chain.is_empty() {
+            chain.push(Self::Unknown.to_string());
+        }
+        chain
+    }
+
+    fn push_chain(&self, chain: &mut Vec<String>) {
+        match self {
+            Self::Unknown => { /* unknown sources are skipped */ }
+            Self::Path { source, path } if matches!(source.as_ref(), Self::EnvVars) => {
+                chain.push(format!("env variable '{path}'"));
+            }
+            Self::Path { source, path } => {
+                source.push_chain(chain);
+                chain.push(format!("path '{path}'"));
+            }
+            Self::FileLocation {
+                source,
+                path,
+                line,
+                column,
+            } => {
+                source.push_chain(chain);
+                chain.push(format!("path '{path}' (line {line}, column {column})"));
+            }
+            Self::Synthetic { source, transform } => {
+                source.push_chain(chain);
+                chain.push(transform.clone());
+            }
+            Self::Fallback { source, param, .. } => {
+                source.push_chain(chain);
+                chain.push(format!("fallback for `{param}`"));
+            }
+            Self::EnvVars | Self::Fallbacks | Self::File { .. } | Self::Custom(_) => {
+                chain.push(self.to_string());
+            }
+        }
+    }
+
+    /// Returns the kind of the original source for this origin. Path and synthetic origins are resolved
+    /// to the kind of their source.
+    pub fn kind(&self) -> OriginKind {
+        match self {
+            Self::EnvVars => OriginKind::EnvVars,
+            Self::File { .. } => OriginKind::File,
+            Self::Path { source, .. }
+            | Self::FileLocation { source, .. }
+            | Self::Synthetic { source, .. }
+            | Self::Fallback { source, .. } => source.kind(),
+            Self::Unknown | Self::Fallbacks | Self::Custom(_) => OriginKind::Other,
+        }
+    }
+
+    /// Checks whether this origin corresponds to a value provided by a [fallback](crate::fallback)
+    /// (possibly transformed afterwards).
+    pub fn is_fallback(&self) -> bool {
+        self.fallback_priority().is_some()
+    }
+
+    /// Checks whether the value originates from a file format natively supporting numbers (JSON, YAML or TOML).
+    pub(crate) fn has_native_numbers(&self) -> bool {
+        match self {
+            Self::File { format, .. } => {
+                matches!(
+                    format,
+                    FileFormat::Json | FileFormat::Yaml | FileFormat::Toml
+                )
+            }
+            Self::Path { source, .. }
+            | Self::FileLocation { source, .. }
+            | Self::Synthetic { source, .. }
+            | Self::Fallback { source, .. } => source.has_native_numbers(),
+            Self::Unknown | Self::EnvVars | Self::Fallbacks | Self::Custom(_) => false,
+        }
+    }
+
+    /// Returns the priority of the [fallback](crate::fallback) that provided the value, or `None`
+    /// if the value isn't provided by a fallback.
+    pub(crate) fn fallback_priority(&self) -> Option<FallbackPriority> {
+        match self {
+            Self::Fallback { priority, .. } => Some(*priority),
+            Self::Path { source, .. }
+            | Self::FileLocation { source, .. }
+            | Self::Synthetic { source, .. } => source.fallback_priority(),
+            Self::Unknown
+            | Self::EnvVars
+            | Self::Fallbacks
+            | Self::File { .. }
+            | Self::Custom(_) => None,
+        }
+    }
+}
+
+/// Kind of the original source of a value, as returned by [`ValueOrigin::kind()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OriginKind {
+    /// Environment variables.
+    EnvVars,
+    /// File source, e.g. a JSON or YAML file.
+    File,
+    /// Other origins, e.g. unknown or custom ones.
+    Other,
+}
+
+impl fmt::Display for OriginKind {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(match self {
+            Self::EnvVars => "env variables",
+            Self::File => "files",
+            Self::Other => "other sources",
+        })
+    }
+}
+
 /// String value: either a plaintext one, or a secret.
 #[derive(Clone)]
 pub enum StrValue {
@@ -237,6 +503,20 @@ impl From<Map> for Value {
 }
 
 impl Value {
+    /// Returns the basic type of this value, or `None` for `null`s. Unlike [`Self::is_supported_by()`],
+    /// this doesn't account for coercions.
+    pub(crate) fn basic_type(&self) -> Option<BasicTypes> {
+        Some(match self {
+            Self::Null => return None,
+            Self::Bool(_) => BasicTypes::BOOL,
+            Self::Number(number) if number.is_u64() || number.is_i64() => BasicTypes::INTEGER,
+            Self::Number(_) => BasicTypes::FLOAT,
+            Self::String(_) => BasicTypes::STRING,
+            Self::Array(_) => BasicTypes::ARRAY,
+            Self::Object(_) => BasicTypes::OBJECT,
+        })
+    }
+
     pub(crate) fn is_supported_by(&self, types: BasicTypes) -> bool {
         match self {
             Self::Null => true,
@@ -308,7 +588,22 @@ impl<T> WithOrigin<T> {
 }
 
 impl WithOrigin {
-    pub(crate) fn get(&self, pointer: Pointer<'_>) -> Option<&Self> {
+    /// Returns value at the specified pointer together with its origin. Returns `None` if any segment is missing
+    /// or descends into a non-container value. See also [`Self::pointer()`], which accepts a string path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{value::{Pointer, Value, WithOrigin}, ConfigSource};
+    /// let contents = smart_config::config!("api.hosts": ["alpha", "beta"]).into_contents();
+    /// let root = WithOrigin::new(Value::Object(contents.inner), contents.origin);
+    /// let host = root.get(Pointer("api.hosts.0")).unwrap();
+    /// assert_eq!(host.inner.as_plain_str(), Some("alpha"));
+    /// assert!(root.get(Pointer("api.ports")).is_none());
+    /// // The empty pointer refers to the value itself.
+    /// assert!(std::ptr::eq(root.get(Pointer("")).unwrap(), &root));
+    /// ```
+    pub fn get(&self, pointer: Pointer<'_>) -> Option<&Self> {
         pointer
             .segments()
             .try_fold(self, |ptr, segment| match &ptr.inner {
@@ -318,7 +613,25 @@ impl WithOrigin {
             })
     }
 
-    /// Returns value at the specified pointer.
+    /// Returns value at the specified pointer, which is a dot-separated path like `api.http.port`. The value is returned
+    /// together with its origin. This is a shortcut for [`Self::get()`]. Numeric path segments index arrays (e.g., `api.hosts.0`); an empty pointer refers
+    /// to this value itself. Returns `None` if any segment is missing or descends into a non-container value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use smart_config::{value::{Value, ValueOrigin, WithOrigin}, ConfigSource};
+    /// let contents = smart_config::config!("api.hosts": ["alpha", "beta"]).into_contents();
+    /// let root = WithOrigin::new(Value::Object(contents.inner), contents.origin);
+    /// let host = root.pointer("api.hosts.1").unwrap();
+    /// assert_eq!(host.inner.as_plain_str(), Some("beta"));
+    /// assert!(matches!(
+    ///     host.origin.as_ref(),
+    ///     ValueOrigin::Path { path, .. } if path == "api.hosts.1"
+    /// ));
+    /// assert!(root.pointer("api.hosts.2").is_none());
+    /// assert!(root.pointer("api.hosts.1.what").is_none());
+    /// ```
     pub fn pointer(&self, pointer: &str) -> Option<&Self> {
         self.get(Pointer(pointer))
     }
@@ -379,6 +692,21 @@ impl WithOrigin {
         }
     }
 
+    /// Recursively removes object entries with `null` values from this value. Arrays are not recursed into
+    /// since `null` array elements may be meaningful.
+    pub fn strip_nulls(&mut self) {
+        if let Value::Object(map) = &mut self.inner {
+            Self::strip_nulls_from_map(map);
+        }
+    }
+
+    pub(crate) fn strip_nulls_from_map(map: &mut Map) {
+        map.retain(|_, value| !matches!(value.inner, Value::Null));
+        for value in map.values_mut() {
+            value.strip_nulls();
+        }
+    }
+
     /// Deep-merges self and `other`, with `other` having higher priority. Only objects are meaningfully merged;
     /// all other values are replaced.
     pub(crate) fn deep_merge(&mut self, overrides: Self) {
@@ -404,9 +732,10 @@ impl WithOrigin {
     }
 }
 
-// TODO: make public for increased type safety?
+/// Pointer to a value in a value tree, represented as a dot-separated path like `api.http.port`.
+/// Numeric path segments index arrays (e.g., `api.hosts.0`); the empty pointer refers to the root value.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub(crate) struct Pointer<'a>(pub &'a str);
+pub struct Pointer<'a>(pub &'a str);
 
 impl fmt::Display for Pointer<'_> {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -471,6 +800,72 @@ impl<'a> Pointer<'a> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn unwinding_origin_chain() {
+        assert_eq!(ValueOrigin::Unknown.chain(), ["unknown"]);
+        assert_eq!(ValueOrigin::Fallbacks.chain(), ["fallbacks"]);
+
+        let env_var = Arc::new(ValueOrigin::Path {
+            source: Arc::new(ValueOrigin::EnvVars),
+            path: "RUST_LOG".into(),
+        });
+        let origin = ValueOrigin::Fallback {
+            source: env_var,
+            param: "Config.log".into(),
+            priority: FallbackPriority::Low,
+        };
+        assert_eq!(
+            origin.chain(),
+            ["env variable 'RUST_LOG'", "fallback for `Config.log`"]
+        );
+        assert_eq!(
+            origin.to_string(),
+            "env variable 'RUST_LOG' -> fallback for `Config.log`"
+        );
+        assert!(origin.is_fallback());
+
+        // A synthetic origin mimicking a fallback one is not considered a fallback.
+        let origin = ValueOrigin::Synthetic {
+            source: Arc::new(ValueOrigin::EnvVars),
+            transform: "fallback for `Config.log`".into(),
+        };
+        assert!(!origin.is_fallback());
+
+        let file = Arc::new(ValueOrigin::File {
+            name: "config.json".into(),
+            format: FileFormat::Json,
+        });
+        let location = Arc::new(ValueOrigin::FileLocation {
+            source: file,
+            path: "api.port".into(),
+            line: 3,
+            column: 12,
+        });
+        let origin = ValueOrigin::Synthetic {
+            source: Arc::new(ValueOrigin::Synthetic {
+                source: location,
+                transform: "coerced".into(),
+            }),
+            transform: "const fallback".into(),
+        };
+        assert_eq!(
+            origin.chain(),
+            [
+                "JSON file 'config.json'",
+                "path 'api.port' (line 3, column 12)",
+                "coerced",
+                "const fallback"
+            ]
+        );
+
+        // Unknown sources should be skipped.
+        let origin = ValueOrigin::Synthetic {
+            source: Arc::default(),
+            transform: "default".into(),
+        };
+        assert_eq!(origin.chain(), ["default"]);
+    }
+
     #[test]
     fn splitting_pointer() {
         let pointer = Pointer("");
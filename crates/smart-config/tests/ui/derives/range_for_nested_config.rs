@@ -0,0 +1,14 @@
+use smart_config::DescribeConfig;
+
+#[derive(DescribeConfig)]
+struct NestedConfig {
+    field: u64,
+}
+
+#[derive(DescribeConfig)]
+struct TestConfig {
+    #[config(nest, range = 1..10)]
+    nested: NestedConfig,
+}
+
+fn main() {}